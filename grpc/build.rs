@@ -0,0 +1,5 @@
+fn main() {
+    // A bundled protoc, so building doesn't need one installed.
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+    tonic_build::compile_protos("proto/poker.proto").unwrap();
+}
@@ -0,0 +1,399 @@
+//! poker-engine over gRPC, for tools and agents written in other languages.
+//! `proto/poker.proto` is the API: `StartHand`, `SubmitAction` and
+//! `StreamEvents`, on games named by the caller.
+//!
+//! A [`PokerGame`] can't leave the thread it was made on, so every game lives
+//! on one thread of the [`Service`]'s own and requests are passed to it.
+
+// Every reply may carry a tonic `Status`, which is large.
+#![allow(clippy::result_large_err)]
+
+use poker_engine::{Action, ActionError, Card, Chips, GameEvent, GamePhase, PokerGame};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use tokio::sync::{mpsc as tokio_mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info};
+
+pub mod proto {
+    tonic::include_proto!("poker.v1");
+}
+
+use proto::poker_engine_server::{PokerEngine, PokerEngineServer};
+use proto::{event, ActionKind, Event, TableState};
+
+pub const DEFAULT_PORT: u16 = 47481;
+// The built-in bot only ever plays seat 1.
+const BOT_SEAT: usize = 1;
+
+type Reply = oneshot::Sender<Result<TableState, Status>>;
+type Watcher = tokio_mpsc::UnboundedSender<Result<Event, Status>>;
+
+enum Job {
+    StartHand {
+        game: String,
+        seed: Option<u64>,
+        reply: Reply,
+    },
+    /// `action` is `None` for the bot's move.
+    Act {
+        game: String,
+        seat: usize,
+        action: Option<Action>,
+        reply: Reply,
+    },
+    Watch {
+        game: String,
+        events: Watcher,
+    },
+}
+
+#[derive(Default)]
+struct Table {
+    game: PokerGame,
+    watchers: Vec<Watcher>,
+}
+
+impl Table {
+    fn publish(&mut self) {
+        let events: Vec<Event> = self
+            .game
+            .drain_events()
+            .iter()
+            .filter_map(to_event)
+            .collect();
+        // A watcher that hung up is dropped at the first event it misses.
+        self.watchers.retain(|watcher| {
+            events
+                .iter()
+                .all(|event| watcher.send(Ok(event.clone())).is_ok())
+        });
+    }
+}
+
+/// The `PokerEngine` service; clones share the same games.
+#[derive(Clone)]
+pub struct Service {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl Default for Service {
+    fn default() -> Self {
+        let (jobs, received) = mpsc::channel();
+        std::thread::spawn(move || run(received));
+        Service { jobs }
+    }
+}
+
+impl Service {
+    pub fn into_server(self) -> PokerEngineServer<Self> {
+        PokerEngineServer::new(self)
+    }
+
+    async fn ask(&self, job: impl FnOnce(Reply) -> Job) -> Result<Response<TableState>, Status> {
+        let (reply, answer) = oneshot::channel();
+        self.jobs
+            .send(job(reply))
+            .map_err(|_| Status::unavailable("the games have stopped"))?;
+        let state = answer
+            .await
+            .map_err(|_| Status::internal("the game was lost"))??;
+        Ok(Response::new(state))
+    }
+}
+
+#[tonic::async_trait]
+impl PokerEngine for Service {
+    async fn start_hand(
+        &self,
+        request: Request<proto::StartHandRequest>,
+    ) -> Result<Response<TableState>, Status> {
+        let request = request.into_inner();
+        self.ask(|reply| Job::StartHand {
+            game: request.game,
+            seed: request.seed,
+            reply,
+        })
+        .await
+    }
+
+    async fn submit_action(
+        &self,
+        request: Request<proto::SubmitActionRequest>,
+    ) -> Result<Response<TableState>, Status> {
+        let request = request.into_inner();
+        let amount = Chips::new(request.amount);
+        let action = match ActionKind::try_from(request.action) {
+            Ok(ActionKind::Fold) => Some(Action::Fold),
+            Ok(ActionKind::Check) => Some(Action::Check),
+            Ok(ActionKind::Call) => Some(Action::Call),
+            Ok(ActionKind::Bet) => Some(Action::Bet(amount)),
+            Ok(ActionKind::Raise) => Some(Action::Raise(amount)),
+            Ok(ActionKind::AllIn) => Some(Action::AllIn),
+            Ok(ActionKind::Bot) => None,
+            Err(_) => return Err(Status::invalid_argument("unknown action")),
+        };
+        self.ask(|reply| Job::Act {
+            game: request.game,
+            seat: request.seat as usize,
+            action,
+            reply,
+        })
+        .await
+    }
+
+    type StreamEventsStream = UnboundedReceiverStream<Result<Event, Status>>;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let (events, stream) = tokio_mpsc::unbounded_channel();
+        self.jobs
+            .send(Job::Watch {
+                game: request.into_inner().game,
+                events,
+            })
+            .map_err(|_| Status::unavailable("the games have stopped"))?;
+        Ok(Response::new(UnboundedReceiverStream::new(stream)))
+    }
+}
+
+fn run(jobs: mpsc::Receiver<Job>) {
+    let mut tables: HashMap<String, Table> = HashMap::new();
+    for job in jobs {
+        match job {
+            Job::StartHand { game, seed, reply } => {
+                let table = tables.entry(game).or_default();
+                let _ = reply.send(start_hand(table, seed));
+            }
+            Job::Act {
+                game,
+                seat,
+                action,
+                reply,
+            } => {
+                let result = match tables.get_mut(&game) {
+                    Some(table) => act(table, seat, action),
+                    None => Err(Status::not_found(format!("no game named {:?}", game))),
+                };
+                let _ = reply.send(result);
+            }
+            Job::Watch { game, events } => {
+                debug!(game, "watching");
+                tables.entry(game).or_default().watchers.push(events);
+            }
+        }
+    }
+}
+
+fn start_hand(table: &mut Table, seed: Option<u64>) -> Result<TableState, Status> {
+    let game = &mut table.game;
+    if game.hand_number > 0 && !game.hand_complete {
+        return Err(Status::failed_precondition("the hand isn't over"));
+    }
+    if game.hand_complete && game.is_game_over() {
+        return Err(Status::failed_precondition("the game is over"));
+    }
+    match seed {
+        Some(seed) => game.start_hand_seeded(seed),
+        None => game.start_hand(),
+    }
+    info!(hand = game.hand_number, "hand started over gRPC");
+    table.publish();
+    Ok(table_state(&table.game))
+}
+
+fn act(table: &mut Table, seat: usize, action: Option<Action>) -> Result<TableState, Status> {
+    let game = &mut table.game;
+    if game.hand_number == 0 || game.hand_complete {
+        return Err(Status::failed_precondition("no hand is being played"));
+    }
+    if seat >= game.players.len() {
+        return Err(Status::invalid_argument(format!("no seat {}", seat)));
+    }
+    match action {
+        Some(action) => game.seat_action(seat, action).map_err(rejected)?,
+        None if seat == BOT_SEAT && game.is_bot_turn() && seat == game.current_player => {
+            game.make_bot_move()
+        }
+        None => return Err(rejected(ActionError::OutOfTurn)),
+    }
+    while !game.hand_complete && game.is_betting_round_complete() {
+        game.next_phase();
+    }
+    table.publish();
+    Ok(table_state(&table.game))
+}
+
+fn rejected(error: ActionError) -> Status {
+    Status::failed_precondition(error.to_string())
+}
+
+fn table_state(game: &PokerGame) -> TableState {
+    let waiting = game.hand_complete || game.phase == GamePhase::Showdown;
+    TableState {
+        hand: game.hand_number,
+        phase: game.phase.name().to_string(),
+        button: game.dealer_position as u32,
+        to_act: (!waiting && !game.is_betting_round_complete())
+            .then_some(game.current_player as u32),
+        pot: game.pot.amount(),
+        current_bet: game.current_bet.amount(),
+        board: game.community_cards.iter().map(Card::to_string).collect(),
+        seats: game
+            .players
+            .iter()
+            .map(|player| proto::Seat {
+                name: player.name.clone(),
+                chips: player.chips.amount(),
+                bet: player.bet.amount(),
+                cards: player.cards.iter().map(Card::to_string).collect(),
+                folded: player.cards.is_empty(),
+            })
+            .collect(),
+        hand_complete: game.hand_complete,
+        game_over: game.hand_complete && game.is_game_over(),
+    }
+}
+
+fn to_event(event: &GameEvent) -> Option<Event> {
+    let kind = match event {
+        GameEvent::HandStarted {
+            hand_number,
+            dealer,
+        } => event::Kind::HandStarted(proto::HandStarted {
+            hand: *hand_number,
+            dealer: *dealer as u32,
+        }),
+        GameEvent::BlindPosted { seat, amount } => event::Kind::BlindPosted(proto::BlindPosted {
+            seat: *seat as u32,
+            amount: amount.amount(),
+        }),
+        GameEvent::CardsDealt { seat, cards } => event::Kind::CardsDealt(proto::CardsDealt {
+            seat: seat.map(|seat| seat as u32),
+            cards: cards.iter().map(Card::to_string).collect(),
+        }),
+        GameEvent::PlayerActed {
+            seat,
+            action,
+            amount,
+        } => event::Kind::PlayerActed(proto::PlayerActed {
+            seat: *seat as u32,
+            action: action_kind(action) as i32,
+            amount: amount.amount(),
+        }),
+        GameEvent::PhaseAdvanced { phase } => event::Kind::PhaseAdvanced(proto::PhaseAdvanced {
+            phase: phase.name().to_string(),
+        }),
+        GameEvent::PotAwarded { seat, amount, pot } => event::Kind::PotAwarded(proto::PotAwarded {
+            seat: *seat as u32,
+            amount: amount.amount(),
+            pot: *pot as u32,
+        }),
+        GameEvent::StateRestored => return None,
+    };
+    Some(Event { kind: Some(kind) })
+}
+
+fn action_kind(action: &Action) -> ActionKind {
+    match action {
+        Action::Fold => ActionKind::Fold,
+        Action::Check => ActionKind::Check,
+        Action::Call => ActionKind::Call,
+        Action::Bet(_) => ActionKind::Bet,
+        Action::Raise(_) => ActionKind::Raise,
+        Action::AllIn => ActionKind::AllIn,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::poker_engine_client::PokerEngineClient;
+    use proto::{StartHandRequest, StreamEventsRequest, SubmitActionRequest};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tokio_stream::StreamExt;
+    use tonic::transport::Server;
+
+    #[tokio::test]
+    async fn test_client_deals_acts_and_hears_the_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(Service::default().into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let mut client = PokerEngineClient::connect(format!("http://{}", address))
+            .await
+            .unwrap();
+        let game = "test".to_string();
+        let mut events = client
+            .stream_events(StreamEventsRequest { game: game.clone() })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let state = client
+            .start_hand(StartHandRequest {
+                game: game.clone(),
+                seed: Some(7),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(state.hand, 1);
+        assert_eq!(state.pot, 30);
+        let to_act = state.to_act.unwrap();
+        let again = client
+            .start_hand(StartHandRequest {
+                game: game.clone(),
+                seed: None,
+            })
+            .await;
+        assert_eq!(again.unwrap_err().code(), tonic::Code::FailedPrecondition);
+        let out_of_turn = client
+            .submit_action(SubmitActionRequest {
+                game: game.clone(),
+                seat: 1 - to_act,
+                action: ActionKind::Check as i32,
+                amount: 0,
+            })
+            .await;
+        assert_eq!(
+            out_of_turn.unwrap_err().code(),
+            tonic::Code::FailedPrecondition
+        );
+
+        let state = client
+            .submit_action(SubmitActionRequest {
+                game: game.clone(),
+                seat: to_act,
+                action: ActionKind::Fold as i32,
+                amount: 0,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(state.hand_complete);
+
+        let first = events.next().await.unwrap().unwrap();
+        assert_eq!(
+            first.kind,
+            Some(event::Kind::HandStarted(proto::HandStarted {
+                hand: 1,
+                dealer: state.button,
+            }))
+        );
+        loop {
+            let event = events.next().await.unwrap().unwrap();
+            if let Some(event::Kind::PotAwarded(awarded)) = event.kind {
+                assert_eq!(awarded.seat, 1 - to_act);
+                break;
+            }
+        }
+    }
+}
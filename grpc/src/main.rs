@@ -0,0 +1,33 @@
+//! Runs the gRPC API: `poker-grpc [address]`, by default on localhost at
+//! port 47481. `POKER_LOG` sets the log filter.
+
+use poker_grpc::{Service, DEFAULT_PORT};
+use std::net::SocketAddr;
+use tonic::transport::Server;
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() {
+    let filter = EnvFilter::try_from_env("POKER_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| format!("127.0.0.1:{}", DEFAULT_PORT));
+    let address: SocketAddr = match address.parse() {
+        Ok(address) => address,
+        Err(error) => {
+            error!(%address, %error, "not an address");
+            std::process::exit(1);
+        }
+    };
+    info!(%address, "gRPC listening");
+    let served = Server::builder()
+        .add_service(Service::default().into_server())
+        .serve(address)
+        .await;
+    if let Err(error) = served {
+        error!(%error, "gRPC stopped");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,80 @@
+//! Hand evaluation and equity benchmarks.
+//!
+//! `cargo bench -p poker-engine`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use poker_engine::{compare_hands, estimate_equity, evaluate_hand, full_deck, Card};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+const SAMPLE_HANDS: usize = 256;
+
+/// Random seven-card hands as (hole cards, board) pairs, the same on every run.
+fn sample_hands() -> Vec<(Vec<Card>, Vec<Card>)> {
+    let mut rng = StdRng::seed_from_u64(7);
+    (0..SAMPLE_HANDS)
+        .map(|_| {
+            let mut deck = full_deck();
+            deck.shuffle(&mut rng);
+            (deck[..2].to_vec(), deck[2..7].to_vec())
+        })
+        .collect()
+}
+
+fn bench_evaluate(c: &mut Criterion) {
+    let hands = sample_hands();
+    c.bench_function("evaluate_hand/7_cards", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            let (hole, board) = &hands[i % hands.len()];
+            i += 1;
+            evaluate_hand(black_box(hole), black_box(board))
+        })
+    });
+}
+
+fn bench_showdown(c: &mut Criterion) {
+    let hands = sample_hands();
+    c.bench_function("showdown/evaluate_and_compare", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            let (hole, board) = &hands[i % hands.len()];
+            let (other, _) = &hands[(i + 1) % hands.len()];
+            i += 1;
+            let first = evaluate_hand(black_box(hole), black_box(board));
+            let second = evaluate_hand(black_box(other), black_box(board));
+            compare_hands(&first, &second)
+        })
+    });
+}
+
+fn bench_equity(c: &mut Criterion) {
+    let hole = vec![Card::new("A", "♠", 14), Card::new("K", "♠", 13)];
+    let flop = vec![
+        Card::new("Q", "♠", 12),
+        Card::new("7", "♥", 7),
+        Card::new("2", "♦", 2),
+    ];
+    let mut group = c.benchmark_group("estimate_equity");
+    for iterations in [100, 300, 1000] {
+        group.bench_with_input(
+            BenchmarkId::new("preflop", iterations),
+            &iterations,
+            |b, &iterations| {
+                let mut rng = StdRng::seed_from_u64(11);
+                b.iter(|| estimate_equity(black_box(&hole), &[], iterations, &mut rng))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("flop", iterations),
+            &iterations,
+            |b, &iterations| {
+                let mut rng = StdRng::seed_from_u64(11);
+                b.iter(|| estimate_equity(black_box(&hole), &flop, iterations, &mut rng))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluate, bench_showdown, bench_equity);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+//! Serves games to scripts over JSON-RPC; see `poker_engine::json_rpc`.
+//!
+//! `cargo run -p poker-engine --features json-rpc --example json_rpc`, then
+//! for example
+//! `echo '{"jsonrpc": "2.0", "id": 1, "method": "deal", "params": {"seed": 7}}' | nc 127.0.0.1 47483`
+
+use poker_engine::json_rpc::{self, DEFAULT_PORT};
+use std::net::TcpListener;
+
+fn main() -> std::io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", DEFAULT_PORT))?;
+    println!("JSON-RPC on {}", listener.local_addr()?);
+    json_rpc::serve(listener)
+}
@@ -0,0 +1,49 @@
+//! Plays the bot against itself with no UI attached.
+//!
+//! `cargo run -p poker-engine --example self_play -- 200`
+//!
+//! Add `--features rest-api` to watch it at `http://127.0.0.1:47482/stats`
+//! while it runs.
+
+use poker_engine::{GamePhase, PokerGame};
+
+fn main() {
+    let hands: u32 = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(100);
+
+    let mut game = PokerGame::new();
+    #[cfg(feature = "rest-api")]
+    poker_engine::rest::Monitor::serve(("127.0.0.1", poker_engine::rest::DEFAULT_PORT))
+        .expect("cannot serve the REST API")
+        .attach(&mut game);
+    let mut showdowns = 0;
+    for _ in 0..hands {
+        if game.is_game_over() {
+            break;
+        }
+        game.start_hand();
+        while !game.hand_complete {
+            if game.is_betting_round_complete() {
+                game.next_phase();
+            } else {
+                game.make_bot_move();
+            }
+        }
+        if game.players.iter().all(|p| !p.cards.is_empty()) && game.phase == GamePhase::Showdown {
+            showdowns += 1;
+        }
+        game.dealer_position = (game.dealer_position + 1) % game.players.len();
+    }
+
+    println!(
+        "{} hands, {} showdowns | {}: {} | {}: {}",
+        game.hand_number,
+        showdowns,
+        game.players[0].name,
+        game.players[0].chips,
+        game.players[1].name,
+        game.players[1].chips
+    );
+}
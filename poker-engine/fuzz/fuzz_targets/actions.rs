@@ -0,0 +1,61 @@
+//! Feeds arbitrary action sequences into a headless game.
+//!
+//! `cargo fuzz run actions` (from `poker-engine/`)
+//!
+//! The first byte seeds the deck; every following pair of bytes is an action
+//! kind and an amount in units of ten chips. Rejected actions are fine, but
+//! the engine must never panic, create or destroy chips, or leave a hand that
+//! checking and calling can't finish.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use poker_engine::{Action, Chips, PokerGame};
+
+// Enough for every street of a hand with room to spare.
+const MAX_STEPS_TO_FINISH: usize = 32;
+
+fn decode(kind: u8, amount: u8) -> Action {
+    let amount = Chips::new(u32::from(amount) * 10);
+    match kind % 6 {
+        0 => Action::Fold,
+        1 => Action::Check,
+        2 => Action::Call,
+        3 => Action::Bet(amount),
+        4 => Action::Raise(amount),
+        _ => Action::AllIn,
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&seed, actions)) = data.split_first() else {
+        return;
+    };
+    let mut game = PokerGame::new();
+    let total = game.chips_in_play();
+    game.start_hand_seeded(u64::from(seed));
+
+    for (hand_seed, pair) in actions.chunks_exact(2).enumerate() {
+        if game.hand_complete {
+            if game.is_game_over() {
+                return;
+            }
+            game.dealer_position = (game.dealer_position + 1) % game.players.len();
+            game.start_hand_seeded(u64::from(seed) + hand_seed as u64);
+        }
+        let _ = game.apply(decode(pair[0], pair[1]));
+        assert_eq!(game.chips_in_play(), total);
+    }
+
+    for _ in 0..MAX_STEPS_TO_FINISH {
+        if game.hand_complete {
+            break;
+        }
+        let progressed = [Action::Check, Action::Call, Action::AllIn]
+            .into_iter()
+            .any(|action| game.apply(action).is_ok());
+        assert!(progressed, "no legal action in {:?}", game.phase);
+    }
+    assert!(game.hand_complete, "hand stuck in {:?}", game.phase);
+    assert_eq!(game.chips_in_play(), total);
+});
@@ -0,0 +1,125 @@
+use super::strategy::{StrategyParams, DEFAULT_BOT_LEVEL, MAX_BOT_LEVEL, MIN_BOT_LEVEL};
+use crate::engine::BIG_BLIND;
+use tracing::info;
+
+const DIFFICULTY_WINDOW_HANDS: usize = 8;
+const DIFFICULTY_MIN_HANDS_BETWEEN_ADJUSTMENTS: usize = 4;
+const DIFFICULTY_BAND_BB_PER_HAND: i32 = 3;
+
+/// Adjusts the bot's [`StrategyParams`] from the user's recent results when enabled.
+pub struct DifficultyController {
+    pub enabled: bool,
+    pub level: i32,
+    recent_results: std::collections::VecDeque<i32>,
+    hands_since_adjustment: usize,
+}
+
+impl Default for DifficultyController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DifficultyController {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            level: DEFAULT_BOT_LEVEL,
+            recent_results: std::collections::VecDeque::new(),
+            hands_since_adjustment: 0,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.level = DEFAULT_BOT_LEVEL;
+        self.recent_results.clear();
+        self.hands_since_adjustment = 0;
+    }
+
+    pub fn params(&self) -> StrategyParams {
+        if self.enabled {
+            StrategyParams::for_level(self.level)
+        } else {
+            StrategyParams::default()
+        }
+    }
+
+    /// Feeds the user's net chips for a finished hand and re-evaluates the level.
+    pub fn record_hand(&mut self, user_net_chips: i32) {
+        if !self.enabled {
+            return;
+        }
+        self.recent_results.push_back(user_net_chips);
+        if self.recent_results.len() > DIFFICULTY_WINDOW_HANDS {
+            self.recent_results.pop_front();
+        }
+        self.hands_since_adjustment += 1;
+        if self.hands_since_adjustment < DIFFICULTY_MIN_HANDS_BETWEEN_ADJUSTMENTS {
+            return;
+        }
+
+        let total: i32 = self.recent_results.iter().sum();
+        let avg_bb = total / (self.recent_results.len() as i32 * BIG_BLIND.as_i32());
+        let new_level = if avg_bb > DIFFICULTY_BAND_BB_PER_HAND {
+            self.level + 1
+        } else if avg_bb < -DIFFICULTY_BAND_BB_PER_HAND {
+            self.level - 1
+        } else {
+            self.level
+        }
+        .clamp(MIN_BOT_LEVEL, MAX_BOT_LEVEL);
+
+        if new_level != self.level {
+            info!(from = self.level, to = new_level, "bot difficulty adjusted");
+            self.level = new_level;
+            self.hands_since_adjustment = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_disabled_ignores_results() {
+        let mut controller = DifficultyController::new();
+        for _ in 0..DIFFICULTY_WINDOW_HANDS {
+            controller.record_hand(500);
+        }
+        assert_eq!(controller.level, DEFAULT_BOT_LEVEL);
+        assert_eq!(controller.params(), StrategyParams::default());
+    }
+
+    #[test]
+    fn test_difficulty_strengthens_when_user_wins() {
+        let mut controller = DifficultyController::new();
+        controller.set_enabled(true);
+        for _ in 0..DIFFICULTY_MIN_HANDS_BETWEEN_ADJUSTMENTS {
+            controller.record_hand(BIG_BLIND.as_i32() * 10);
+        }
+        assert_eq!(controller.level, DEFAULT_BOT_LEVEL + 1);
+    }
+
+    #[test]
+    fn test_difficulty_weakens_when_user_loses() {
+        let mut controller = DifficultyController::new();
+        controller.set_enabled(true);
+        for _ in 0..DIFFICULTY_MIN_HANDS_BETWEEN_ADJUSTMENTS {
+            controller.record_hand(-BIG_BLIND.as_i32() * 10);
+        }
+        assert_eq!(controller.level, DEFAULT_BOT_LEVEL - 1);
+    }
+
+    #[test]
+    fn test_difficulty_holds_inside_band() {
+        let mut controller = DifficultyController::new();
+        controller.set_enabled(true);
+        let big_blind = BIG_BLIND.as_i32();
+        for result in [big_blind, -big_blind, big_blind * 2, -big_blind].repeat(3) {
+            controller.record_hand(result);
+        }
+        assert_eq!(controller.level, DEFAULT_BOT_LEVEL);
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(feature = "exploit")]
+use super::strategy::MIN_BET_AMOUNT;
+#[cfg(feature = "exploit")]
+use crate::engine::MIN_RAISE;
+use crate::engine::{PlayerStats, PokerGame};
+
+#[cfg(feature = "exploit")]
+const EXPLOIT_HIGH_FOLD_PERCENT: u32 = 60;
+#[cfg(feature = "exploit")]
+const EXPLOIT_LOW_FOLD_PERCENT: u32 = 25;
+#[cfg(feature = "exploit")]
+const EXPLOIT_LOOSE_VPIP_PERCENT: u32 = 60;
+#[cfg(feature = "exploit")]
+const EXPLOIT_AGGRESSION_FACTOR: u32 = 3;
+
+/// A measured leak the exploitative bot can attack.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Exploit {
+    ThreeBetLight,
+    BluffFrequentFolder,
+    ValueBetCallingStation,
+    CallDownAggressor,
+}
+
+impl Exploit {
+    pub fn describe(&self, stats: &PlayerStats) -> String {
+        match self {
+            Exploit::ThreeBetLight => format!(
+                "3-bet light: you fold to 3-bets {}%",
+                stats.fold_to_three_bet().unwrap_or(0)
+            ),
+            Exploit::BluffFrequentFolder => format!(
+                "Bluffed: you fold to bets {}%",
+                stats.fold_to_bet().unwrap_or(0)
+            ),
+            Exploit::ValueBetCallingStation => format!(
+                "Thin value: you fold to bets only {}%",
+                stats.fold_to_bet().unwrap_or(0)
+            ),
+            Exploit::CallDownAggressor => format!(
+                "Called down: your aggression factor is {}",
+                stats.aggression_factor().unwrap_or(0)
+            ),
+        }
+    }
+}
+
+impl PokerGame {
+    #[cfg(feature = "exploit")]
+    pub(crate) fn find_exploit(
+        &self,
+        hand_strength: i32,
+        to_call: i32,
+        player_chips: i32,
+    ) -> Option<(Exploit, &'static str, i32)> {
+        let stats = &self.user_stats;
+        let params = &self.bot_params;
+        let is_weak = hand_strength < params.low_hand_threshold;
+        let is_medium = hand_strength >= params.low_hand_threshold
+            && hand_strength < params.high_hand_threshold;
+        let current_bet = self.current_bet.as_i32();
+        let pot = self.pot.as_i32();
+        let can_raise_to = |amount: i32| {
+            let to_bet = amount.max((self.current_bet + MIN_RAISE).as_i32());
+            (player_chips >= to_bet).then_some(to_bet)
+        };
+
+        if to_call > 0 && stats.raised_this_street {
+            if let Some(fold_pct) = stats.fold_to_three_bet() {
+                if fold_pct >= EXPLOIT_HIGH_FOLD_PERCENT {
+                    if let Some(to_bet) = can_raise_to(current_bet * 3) {
+                        return Some((Exploit::ThreeBetLight, "raise", to_bet));
+                    }
+                }
+            }
+        }
+
+        if to_call > 0 && !is_weak {
+            if let Some(af) = stats.aggression_factor() {
+                if af >= EXPLOIT_AGGRESSION_FACTOR && player_chips >= to_call {
+                    return Some((Exploit::CallDownAggressor, "call", 0));
+                }
+            }
+        }
+
+        if to_call == 0 {
+            let fold_pct = stats.fold_to_bet()?;
+            if is_weak && fold_pct >= EXPLOIT_HIGH_FOLD_PERCENT {
+                if let Some(to_bet) = can_raise_to((pot / 2).max(MIN_BET_AMOUNT)) {
+                    return Some((Exploit::BluffFrequentFolder, "bet", to_bet));
+                }
+            }
+            let is_loose = stats.vpip().unwrap_or(0) >= EXPLOIT_LOOSE_VPIP_PERCENT;
+            if is_medium && is_loose && fold_pct <= EXPLOIT_LOW_FOLD_PERCENT {
+                if let Some(to_bet) = can_raise_to((pot * 3 / 4).max(MIN_BET_AMOUNT)) {
+                    return Some((Exploit::ValueBetCallingStation, "bet", to_bet));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Without the `exploit` feature the exploitative style finds nothing and
+    /// plays the standard strategy.
+    #[cfg(not(feature = "exploit"))]
+    pub(crate) fn find_exploit(
+        &self,
+        _hand_strength: i32,
+        _to_call: i32,
+        _player_chips: i32,
+    ) -> Option<(Exploit, &'static str, i32)> {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "exploit"))]
+mod tests {
+    use super::*;
+    use crate::engine::{Chips, GamePhase};
+
+    #[test]
+    fn test_exploitative_bot_three_bets_frequent_folder() {
+        let mut game = PokerGame::new();
+        for _ in 0..5 {
+            game.user_stats.start_hand();
+            game.user_stats
+                .record_action("raise", 10, &GamePhase::PreFlop);
+            game.user_stats
+                .record_action("fold", 60, &GamePhase::PreFlop);
+        }
+        game.user_stats.start_hand();
+        game.user_stats
+            .record_action("raise", 10, &GamePhase::PreFlop);
+        game.current_bet = Chips::new(60);
+
+        let exploit = game.find_exploit(10, 40, 1000);
+        assert_eq!(exploit, Some((Exploit::ThreeBetLight, "raise", 180)));
+    }
+
+    #[test]
+    fn test_exploitative_bot_needs_samples() {
+        let game = PokerGame::new();
+        assert_eq!(game.find_exploit(10, 0, 1000), None);
+    }
+}
@@ -0,0 +1,16 @@
+//! Bot decision making layered on top of [`crate::engine::PokerGame`].
+
+mod difficulty;
+mod exploit;
+mod push_fold;
+mod strategy;
+mod trapping;
+
+pub use difficulty::DifficultyController;
+pub use exploit::Exploit;
+pub use push_fold::PUSH_FOLD_THRESHOLD_BB;
+pub use strategy::{
+    BotDecision, BotMove, BotPersonality, BotStyle, StrategyParams, DEFAULT_BOT_LEVEL,
+    MAX_BOT_LEVEL, MIN_BOT_LEVEL,
+};
+pub(crate) use trapping::TrapState;
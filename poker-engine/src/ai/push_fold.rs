@@ -0,0 +1,199 @@
+#[cfg(feature = "push-fold")]
+use super::strategy::BotLine;
+#[cfg(feature = "push-fold")]
+use crate::engine::{starting_hand_code, Card};
+use crate::engine::{GamePhase, PokerGame};
+
+/// Effective stack (in big blinds) at or below which preflop play switches to push/fold.
+pub const PUSH_FOLD_THRESHOLD_BB: i32 = 15;
+
+// Heads-up Nash push/fold charts: the largest effective stack (in big blinds,
+// capped at 20) at which a hand is shoved from the small blind or calls a
+// shove from the big blind. Rows/columns run A..2; suited hands sit above the
+// diagonal, offsuit hands below it.
+#[cfg(feature = "push-fold")]
+const NASH_PUSH_TABLE: [[u8; 13]; 13] = [
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20],
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20],
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20],
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 18, 16, 15],
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 17, 15, 14, 13],
+    [20, 20, 20, 20, 20, 20, 20, 20, 20, 15, 12, 10, 9],
+    [20, 20, 20, 17, 17, 17, 20, 20, 20, 17, 11, 9, 8],
+    [20, 20, 16, 12, 12, 11, 11, 20, 20, 18, 12, 8, 7],
+    [20, 20, 14, 10, 8, 8, 9, 9, 20, 20, 14, 9, 6],
+    [20, 18, 13, 9, 7, 6, 6, 7, 8, 20, 18, 11, 7],
+    [20, 17, 12, 8, 6, 5, 4, 4, 5, 6, 20, 9, 6],
+    [20, 16, 11, 7, 5, 4, 3, 3, 3, 4, 4, 20, 6],
+    [20, 15, 10, 6, 5, 3, 2, 2, 2, 3, 3, 3, 20],
+];
+
+#[cfg(feature = "push-fold")]
+const NASH_CALL_TABLE: [[u8; 13]; 13] = [
+    [20, 20, 20, 20, 20, 20, 20, 20, 18, 19, 17, 16, 15],
+    [20, 20, 20, 20, 20, 18, 14, 13, 12, 11, 10, 9, 9],
+    [20, 20, 20, 16, 14, 11, 9, 7, 7, 6, 6, 5, 5],
+    [20, 18, 12, 20, 12, 9, 7, 6, 5, 5, 4, 4, 4],
+    [20, 15, 10, 9, 20, 9, 7, 6, 5, 4, 4, 3, 3],
+    [20, 11, 8, 7, 7, 20, 7, 6, 5, 4, 3, 3, 3],
+    [18, 9, 6, 6, 5, 5, 20, 6, 5, 4, 3, 3, 2],
+    [16, 8, 5, 5, 4, 4, 4, 20, 5, 4, 3, 3, 2],
+    [14, 7, 5, 4, 4, 4, 4, 4, 17, 4, 4, 3, 2],
+    [14, 7, 4, 4, 3, 3, 3, 3, 3, 14, 4, 3, 3],
+    [13, 6, 4, 3, 3, 3, 3, 3, 3, 3, 12, 3, 3],
+    [12, 6, 4, 3, 3, 2, 2, 2, 2, 3, 3, 10, 3],
+    [11, 5, 4, 3, 3, 2, 2, 2, 2, 2, 2, 2, 8],
+];
+
+#[cfg(feature = "push-fold")]
+fn push_fold_cell(hole_cards: &[Card]) -> Option<(usize, usize)> {
+    let [first, second] = hole_cards else {
+        return None;
+    };
+    let high = (14 - first.value.max(second.value)) as usize;
+    let low = (14 - first.value.min(second.value)) as usize;
+    if first.suit == second.suit {
+        Some((high, low))
+    } else {
+        Some((low, high))
+    }
+}
+
+impl PokerGame {
+    /// Smallest starting stack of the hand, in big blinds.
+    pub fn effective_stack_bb(&self) -> i32 {
+        let smallest_stack = self
+            .hand_start_chips
+            .iter()
+            .min()
+            .copied()
+            .unwrap_or_default();
+        (smallest_stack.amount() / self.big_blind.amount()) as i32
+    }
+
+    /// True during preflop play with stacks at or below [`PUSH_FOLD_THRESHOLD_BB`].
+    pub fn is_push_fold(&self) -> bool {
+        self.phase == GamePhase::PreFlop && self.effective_stack_bb() <= PUSH_FOLD_THRESHOLD_BB
+    }
+
+    #[cfg(feature = "push-fold")]
+    pub(crate) fn push_fold_line(&self, to_call: i32, player_chips: i32) -> Option<BotLine> {
+        if !self.is_push_fold() || player_chips == 0 {
+            return None;
+        }
+        let cards = &self.players[self.current_player].cards;
+        let (row, col) = push_fold_cell(cards)?;
+        let facing_shove = self.current_bet > self.big_blind;
+        let (table, label) = if facing_shove {
+            (&NASH_CALL_TABLE, "call")
+        } else {
+            (&NASH_PUSH_TABLE, "push")
+        };
+        let limit = table[row][col] as i32;
+        let stack_bb = self.effective_stack_bb();
+        let reason = format!(
+            "Push/fold {} {} ({}BB vs {}BB)",
+            label,
+            starting_hand_code(cards),
+            stack_bb,
+            limit
+        );
+
+        let action = if stack_bb <= limit {
+            if facing_shove && player_chips > to_call {
+                "call"
+            } else {
+                "all-in"
+            }
+        } else if to_call == 0 {
+            "check"
+        } else {
+            "fold"
+        };
+        Some((reason, None, action, 0))
+    }
+
+    /// Without the `push-fold` feature short stacks play the regular strategy.
+    #[cfg(not(feature = "push-fold"))]
+    pub(crate) fn push_fold_line(
+        &self,
+        _to_call: i32,
+        _player_chips: i32,
+    ) -> Option<super::strategy::BotLine> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{BIG_BLIND, STARTING_CHIPS};
+
+    #[cfg(feature = "push-fold")]
+    fn create_card(rank: &str, suit: &str, value: i32) -> Card {
+        Card::new(rank, suit, value)
+    }
+
+    #[cfg(feature = "push-fold")]
+    #[test]
+    fn test_push_fold_cell_layout() {
+        let aks = vec![create_card("A", "♠", 14), create_card("K", "♠", 13)];
+        let ako = vec![create_card("K", "♥", 13), create_card("A", "♠", 14)];
+        let deuces = vec![create_card("2", "♥", 2), create_card("2", "♠", 2)];
+        assert_eq!(push_fold_cell(&aks), Some((0, 1)));
+        assert_eq!(push_fold_cell(&ako), Some((1, 0)));
+        assert_eq!(push_fold_cell(&deuces), Some((12, 12)));
+        assert_eq!(starting_hand_code(&ako), "AKo");
+        assert_eq!(starting_hand_code(&deuces), "22");
+    }
+
+    #[test]
+    fn test_push_fold_mode_needs_short_stack() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        assert!(!game.is_push_fold());
+
+        game.hand_start_chips = vec![BIG_BLIND * 12, STARTING_CHIPS];
+        assert!(game.is_push_fold());
+        game.phase = GamePhase::Flop;
+        assert!(!game.is_push_fold());
+    }
+
+    #[cfg(feature = "push-fold")]
+    #[test]
+    fn test_push_fold_shoves_range_and_folds_trash() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        game.hand_start_chips = vec![BIG_BLIND * 10, BIG_BLIND * 10];
+        game.current_bet = BIG_BLIND;
+
+        game.players[game.current_player].cards =
+            vec![create_card("K", "♥", 13), create_card("9", "♠", 9)];
+        let line = game.push_fold_line(10, 180).expect("push/fold line");
+        assert_eq!(line.2, "all-in");
+
+        game.players[game.current_player].cards =
+            vec![create_card("7", "♥", 7), create_card("2", "♠", 2)];
+        let line = game.push_fold_line(10, 180).expect("push/fold line");
+        assert_eq!(line.2, "fold");
+    }
+
+    #[cfg(feature = "push-fold")]
+    #[test]
+    fn test_push_fold_calls_shove_with_calling_range() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        game.hand_start_chips = vec![BIG_BLIND * 10, BIG_BLIND * 10];
+        game.current_bet = BIG_BLIND * 10;
+
+        game.players[game.current_player].cards =
+            vec![create_card("A", "♥", 14), create_card("7", "♠", 7)];
+        let line = game.push_fold_line(180, 180).expect("push/fold line");
+        assert_eq!(line.2, "all-in");
+
+        game.players[game.current_player].cards =
+            vec![create_card("Q", "♥", 12), create_card("6", "♠", 6)];
+        let line = game.push_fold_line(180, 180).expect("push/fold line");
+        assert_eq!(line.2, "fold");
+    }
+}
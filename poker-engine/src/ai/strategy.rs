@@ -0,0 +1,545 @@
+use super::TrapState;
+use crate::engine::{
+    estimate_equity, evaluate_hand, Action, Chips, GamePhase, GameSnapshot, PokerGame, PotOdds,
+    MIN_RAISE,
+};
+use rand::{rngs::StdRng, Rng};
+use tracing::{debug, instrument, warn};
+
+pub(crate) const MIN_BET_AMOUNT: i32 = 30;
+pub(crate) const MAX_BET_AMOUNT: i32 = 150;
+
+const HIGH_HAND_THRESHOLD: i32 = 700;
+const MEDIUM_HAND_THRESHOLD: i32 = 500;
+const LOW_HAND_THRESHOLD: i32 = 300;
+const HIGH_HAND_RAISE_CHANCE: i32 = 70;
+const MEDIUM_HAND_BET_CHANCE: i32 = 50;
+const MEDIUM_HAND_CALL_CHANCE: i32 = 60;
+const LOW_HAND_CHECK_CHANCE: i32 = 40;
+const LOW_HAND_CALL_CHANCE: i32 = 30;
+const WEAK_HAND_FOLD_CHANCE: i32 = 70;
+pub(crate) const CHECK_RAISE_CHANCE: i32 = 20;
+pub(crate) const DONK_BET_CHANCE: i32 = 15;
+
+/// Weakest level the adaptive difficulty can drop the bot to.
+pub const MIN_BOT_LEVEL: i32 = 1;
+/// Strongest level the adaptive difficulty can raise the bot to.
+pub const MAX_BOT_LEVEL: i32 = 9;
+/// Level matching the stock, non-adaptive bot.
+pub const DEFAULT_BOT_LEVEL: i32 = 5;
+const BOT_LEVEL_STEP_PERCENT: i32 = 5;
+
+const EQUITY_ITERATIONS: u32 = 300;
+const DECISION_LOG_CAPACITY: usize = 60;
+
+/// Tunable thresholds and frequencies that drive the bot's decisions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyParams {
+    pub high_hand_threshold: i32,
+    pub medium_hand_threshold: i32,
+    pub low_hand_threshold: i32,
+    pub high_hand_raise_chance: i32,
+    pub medium_hand_bet_chance: i32,
+    pub medium_hand_call_chance: i32,
+    pub low_hand_check_chance: i32,
+    pub low_hand_call_chance: i32,
+    pub weak_hand_fold_chance: i32,
+    pub check_raise_chance: i32,
+    pub donk_bet_chance: i32,
+    pub max_bet_amount: i32,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            high_hand_threshold: HIGH_HAND_THRESHOLD,
+            medium_hand_threshold: MEDIUM_HAND_THRESHOLD,
+            low_hand_threshold: LOW_HAND_THRESHOLD,
+            high_hand_raise_chance: HIGH_HAND_RAISE_CHANCE,
+            medium_hand_bet_chance: MEDIUM_HAND_BET_CHANCE,
+            medium_hand_call_chance: MEDIUM_HAND_CALL_CHANCE,
+            low_hand_check_chance: LOW_HAND_CHECK_CHANCE,
+            low_hand_call_chance: LOW_HAND_CALL_CHANCE,
+            weak_hand_fold_chance: WEAK_HAND_FOLD_CHANCE,
+            check_raise_chance: CHECK_RAISE_CHANCE,
+            donk_bet_chance: DONK_BET_CHANCE,
+            max_bet_amount: MAX_BET_AMOUNT,
+        }
+    }
+}
+
+impl StrategyParams {
+    // Level 5 is the stock bot. Higher levels value-bet harder, size up and
+    // give up on trash more often; lower levels play passive and loose.
+    /// Parameters for a difficulty level between [`MIN_BOT_LEVEL`] and [`MAX_BOT_LEVEL`].
+    pub fn for_level(level: i32) -> Self {
+        let shift = level.clamp(MIN_BOT_LEVEL, MAX_BOT_LEVEL) - DEFAULT_BOT_LEVEL;
+        let step = shift * BOT_LEVEL_STEP_PERCENT;
+        let chance = |base: i32, delta: i32| (base + delta).clamp(5, 95);
+        let base = Self::default();
+
+        Self {
+            high_hand_raise_chance: chance(base.high_hand_raise_chance, step),
+            medium_hand_bet_chance: chance(base.medium_hand_bet_chance, step),
+            medium_hand_call_chance: chance(base.medium_hand_call_chance, -step),
+            low_hand_check_chance: chance(base.low_hand_check_chance, -step),
+            low_hand_call_chance: chance(base.low_hand_call_chance, step),
+            weak_hand_fold_chance: chance(base.weak_hand_fold_chance, step),
+            check_raise_chance: chance(base.check_raise_chance, step),
+            donk_bet_chance: chance(base.donk_bet_chance, step),
+            max_bet_amount: (base.max_bet_amount + shift * 10).max(MIN_BET_AMOUNT + 20),
+            ..base
+        }
+    }
+}
+
+/// Whether the bot plays its stock strategy or exploits [`PlayerStats`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BotStyle {
+    Standard,
+    Exploitative,
+}
+
+/// Bot temperament; controls how often it traps with strong hands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BotPersonality {
+    Balanced,
+    Rock,
+    Maniac,
+    Trapper,
+}
+
+impl BotPersonality {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotPersonality::Balanced => "Balanced",
+            BotPersonality::Rock => "Rock",
+            BotPersonality::Maniac => "Maniac",
+            BotPersonality::Trapper => "Trapper",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            BotPersonality::Balanced => BotPersonality::Rock,
+            BotPersonality::Rock => BotPersonality::Maniac,
+            BotPersonality::Maniac => BotPersonality::Trapper,
+            BotPersonality::Trapper => BotPersonality::Balanced,
+        }
+    }
+
+    pub fn slowplay_chance(&self) -> i32 {
+        match self {
+            BotPersonality::Balanced => 25,
+            BotPersonality::Rock => 10,
+            BotPersonality::Maniac => 5,
+            BotPersonality::Trapper => 60,
+        }
+    }
+}
+
+pub(crate) type BotLine = (String, Option<i32>, &'static str, i32);
+
+/// Rationale recorded for every bot action.
+#[derive(Clone, Debug)]
+pub struct BotDecision {
+    pub hand_number: u32,
+    pub phase: GamePhase,
+    pub hand_strength: i32,
+    pub equity: f64,
+    pub pot_odds: f64,
+    pub line: String,
+    pub roll: Option<i32>,
+    pub action: &'static str,
+    pub amount: i32,
+}
+
+impl BotDecision {
+    /// One-line description suitable for a log view.
+    pub fn summary(&self) -> String {
+        let action = match self.action {
+            "bet" | "raise" => format!("{} ${}", self.action, self.amount),
+            other => other.to_string(),
+        };
+        let roll = self
+            .roll
+            .map(|r| format!(" (roll {})", r))
+            .unwrap_or_default();
+        format!(
+            "#{} {:?} | str {} | eq {:.0}% | odds {:.0}% | {} -> {}{}",
+            self.hand_number,
+            self.phase,
+            self.hand_strength,
+            self.equity * 100.0,
+            self.pot_odds * 100.0,
+            self.line,
+            action,
+            roll
+        )
+    }
+}
+
+/// A bot move worked out by [`PokerGame::plan_bot_move`] on a copy of the
+/// game, carrying the bot's bookkeeping along with the action so
+/// [`PokerGame::apply_bot_move`] can replay it on the real game.
+#[derive(Clone, Debug)]
+pub struct BotMove {
+    hand_number: u32,
+    action: Action,
+    decision: BotDecision,
+    last_exploit: Option<String>,
+    trap: TrapState,
+    check_raise_plan: Option<GamePhase>,
+    bot_rng: StdRng,
+}
+
+impl PokerGame {
+    /// Lets the bot decide and apply its action, logging the rationale.
+    pub fn make_bot_move(&mut self) {
+        if let Some((action, decision)) = self.choose_bot_action() {
+            self.record_decision(decision);
+            self.play_bot_action(action);
+        }
+    }
+
+    /// Decides the bot's move from `snapshot` without a live game, so the
+    /// equity sampling can run on a worker thread. Returns `None` when it is
+    /// not the bot's turn.
+    pub fn plan_bot_move(snapshot: &GameSnapshot) -> Option<BotMove> {
+        let mut scratch = PokerGame::new();
+        scratch.restore(snapshot);
+        if !scratch.is_bot_turn() {
+            return None;
+        }
+        let (action, decision) = scratch.choose_bot_action()?;
+        Some(BotMove {
+            hand_number: scratch.hand_number,
+            action,
+            decision,
+            last_exploit: scratch.last_exploit,
+            trap: scratch.trap,
+            check_raise_plan: scratch.check_raise_plan,
+            bot_rng: scratch.bot_rng,
+        })
+    }
+
+    /// Applies a move from [`PokerGame::plan_bot_move`]. Returns `false`, and
+    /// changes nothing, if the game has moved on since it was planned.
+    pub fn apply_bot_move(&mut self, bot_move: BotMove) -> bool {
+        if bot_move.hand_number != self.hand_number || !self.is_bot_turn() {
+            return false;
+        }
+        self.last_exploit = bot_move.last_exploit;
+        self.trap = bot_move.trap;
+        self.check_raise_plan = bot_move.check_raise_plan;
+        self.bot_rng = bot_move.bot_rng;
+        self.record_decision(bot_move.decision);
+        self.play_bot_action(bot_move.action);
+        true
+    }
+
+    #[instrument(level = "debug", skip_all, fields(hand = self.hand_number))]
+    fn choose_bot_action(&mut self) -> Option<(Action, BotDecision)> {
+        if self.hand_complete || self.phase == GamePhase::Showdown {
+            return None;
+        }
+
+        let player_chips = self.players[self.current_player].chips.as_i32();
+        let call_amount = self
+            .current_bet
+            .saturating_sub(self.players[self.current_player].bet)
+            .as_i32();
+
+        let bot_hand = evaluate_hand(
+            &self.players[self.current_player].cards,
+            &self.community_cards,
+        );
+        let hand_strength = bot_hand.rank as i32 * 100 + bot_hand.primary_value;
+
+        let exploit = match self.bot_style {
+            BotStyle::Exploitative => self.find_exploit(hand_strength, call_amount, player_chips),
+            BotStyle::Standard => None,
+        };
+
+        let trap_line = self
+            .push_fold_line(call_amount, player_chips)
+            .or_else(|| self.trap_line(hand_strength, call_amount, player_chips))
+            .or_else(|| self.postflop_line(hand_strength, call_amount, player_chips));
+        let (line, roll, action, bet_amount) = match (trap_line, exploit) {
+            (Some(line), _) => line,
+            (None, Some((exploit, action, amount))) => {
+                let description = exploit.describe(&self.user_stats);
+                self.last_exploit = Some(description.clone());
+                (format!("Exploit: {}", description), None, action, amount)
+            }
+            (None, None) => self.determine_bot_action(hand_strength, call_amount, player_chips),
+        };
+
+        let equity = estimate_equity(
+            &self.players[self.current_player].cards,
+            &self.community_cards,
+            EQUITY_ITERATIONS,
+            &mut self.bot_rng,
+        );
+        let pot_odds = self
+            .pot_odds(self.current_player)
+            .map_or(0.0, PotOdds::required_equity);
+        let decision = BotDecision {
+            hand_number: self.hand_number,
+            phase: self.phase.clone(),
+            hand_strength,
+            equity,
+            pot_odds,
+            line,
+            roll,
+            action,
+            amount: bet_amount,
+        };
+        debug!(decision = %decision.summary(), "bot decided");
+
+        let min_raise_to = self.current_bet + MIN_RAISE;
+        let to_bet = Chips::saturating_from(bet_amount).max(min_raise_to);
+        let chosen = match Action::from_name(action, to_bet) {
+            Some(Action::Fold) | None if call_amount == 0 => Action::Check,
+            Some(chosen) => chosen,
+            None => Action::Fold,
+        };
+        Some((chosen, decision))
+    }
+
+    fn record_decision(&mut self, decision: BotDecision) {
+        self.decision_log.push_back(decision);
+        if self.decision_log.len() > DECISION_LOG_CAPACITY {
+            self.decision_log.pop_front();
+        }
+    }
+
+    fn play_bot_action(&mut self, chosen: Action) {
+        if let Err(error) = self.player_action(chosen) {
+            warn!(action = ?chosen, %error, "bot action rejected, falling back");
+            let call_amount = self
+                .current_bet
+                .saturating_sub(self.players[self.current_player].bet);
+            let player_chips = self.players[self.current_player].chips;
+            let fallback = if call_amount.is_zero() {
+                Action::Check
+            } else if player_chips >= call_amount {
+                Action::Call
+            } else {
+                Action::AllIn
+            };
+            if self.player_action(fallback).is_err() {
+                let _ = self.player_action(Action::Fold);
+            }
+        }
+    }
+
+    fn determine_bot_action(
+        &mut self,
+        hand_strength: i32,
+        to_call: i32,
+        player_chips: i32,
+    ) -> (String, Option<i32>, &'static str, i32) {
+        // Taken out for the call so `self` can still be borrowed alongside it.
+        let mut rng = self.bot_rng.clone();
+        let params = &self.bot_params;
+
+        let (line, (action, roll)) = if hand_strength >= params.high_hand_threshold {
+            (
+                "Strong hand",
+                self.select_action_for_strength(
+                    GamePhase::PreFlop,
+                    to_call,
+                    &[
+                        (params.high_hand_raise_chance, "raise"),
+                        (100 - params.high_hand_raise_chance, "check"),
+                    ],
+                    &[(80, "call"), (20, "raise")],
+                    75,
+                    &mut rng,
+                ),
+            )
+        } else if hand_strength >= params.medium_hand_threshold {
+            (
+                "Medium hand",
+                self.select_action_for_strength(
+                    GamePhase::PreFlop,
+                    to_call,
+                    &[
+                        (params.medium_hand_bet_chance, "bet"),
+                        (100 - params.medium_hand_bet_chance, "check"),
+                    ],
+                    &[
+                        (params.medium_hand_call_chance, "call"),
+                        (100 - params.medium_hand_call_chance, "raise"),
+                    ],
+                    50,
+                    &mut rng,
+                ),
+            )
+        } else if hand_strength >= params.low_hand_threshold {
+            (
+                "Marginal hand",
+                self.select_action_for_strength(
+                    GamePhase::PreFlop,
+                    to_call,
+                    &[
+                        (params.low_hand_check_chance, "check"),
+                        (100 - params.low_hand_check_chance, "bet"),
+                    ],
+                    &[
+                        (params.low_hand_call_chance, "call"),
+                        (100 - params.low_hand_call_chance, "raise"),
+                    ],
+                    30,
+                    &mut rng,
+                ),
+            )
+        } else {
+            (
+                "Weak hand",
+                self.select_action_for_strength(
+                    GamePhase::PreFlop,
+                    to_call,
+                    &[
+                        (100 - params.weak_hand_fold_chance, "check"),
+                        (params.weak_hand_fold_chance, "fold"),
+                    ],
+                    &[
+                        (100 - params.weak_hand_fold_chance, "call"),
+                        (params.weak_hand_fold_chance, "fold"),
+                    ],
+                    20,
+                    &mut rng,
+                ),
+            )
+        };
+
+        let bet_amount = match action {
+            "bet" | "raise" => {
+                let base_amount = if hand_strength >= params.high_hand_threshold {
+                    player_chips.min(params.max_bet_amount + 50)
+                } else if hand_strength >= params.medium_hand_threshold {
+                    player_chips.min(params.max_bet_amount)
+                } else {
+                    player_chips.min(MIN_BET_AMOUNT + 20)
+                };
+                rng.gen_range(MIN_BET_AMOUNT..=base_amount)
+            }
+            _ => 0,
+        };
+
+        self.bot_rng = rng;
+        (line.to_string(), Some(roll), action, bet_amount)
+    }
+
+    fn select_action_for_strength<R: Rng>(
+        &self,
+        phase: GamePhase,
+        to_call: i32,
+        no_action_options: &[(i32, &'static str)],
+        call_options: &[(i32, &'static str)],
+        default_raise_chance: i32,
+        rng: &mut R,
+    ) -> (&'static str, i32) {
+        match phase {
+            GamePhase::PreFlop if to_call == 0 => self.pick_random_action(no_action_options, rng),
+            GamePhase::PreFlop => self.pick_random_action(call_options, rng),
+            _ => {
+                let roll = rng.gen_range(0..100);
+                if roll < default_raise_chance {
+                    ("raise", roll)
+                } else {
+                    ("call", roll)
+                }
+            }
+        }
+    }
+
+    fn pick_random_action<R: Rng>(
+        &self,
+        options: &[(i32, &'static str)],
+        rng: &mut R,
+    ) -> (&'static str, i32) {
+        let total: i32 = options.iter().map(|&(w, _)| w).sum();
+        let initial_roll = rng.gen_range(0..total);
+        let mut roll = initial_roll;
+
+        for &(weight, action) in options {
+            if roll < weight {
+                return (action, initial_roll);
+            }
+            roll -= weight;
+        }
+        (
+            options.last().map(|&(_, a)| a).unwrap_or("check"),
+            initial_roll,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_level_matches_stock_strategy() {
+        assert_eq!(
+            StrategyParams::for_level(DEFAULT_BOT_LEVEL),
+            StrategyParams::default()
+        );
+    }
+
+    #[test]
+    fn test_higher_level_is_more_aggressive() {
+        let weak = StrategyParams::for_level(MIN_BOT_LEVEL);
+        let strong = StrategyParams::for_level(MAX_BOT_LEVEL);
+        assert!(strong.high_hand_raise_chance > weak.high_hand_raise_chance);
+        assert!(strong.weak_hand_fold_chance > weak.weak_hand_fold_chance);
+        assert!(strong.max_bet_amount > weak.max_bet_amount);
+    }
+
+    #[test]
+    fn test_personality_slowplay_frequencies() {
+        assert!(
+            BotPersonality::Trapper.slowplay_chance() > BotPersonality::Balanced.slowplay_chance()
+        );
+        assert!(BotPersonality::Maniac.slowplay_chance() < BotPersonality::Rock.slowplay_chance());
+        assert_eq!(BotPersonality::Trapper.next(), BotPersonality::Balanced);
+    }
+
+    #[test]
+    fn test_bot_move_records_decision() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        while !game.is_bot_turn() {
+            game.move_to_next_player();
+        }
+        game.make_bot_move();
+        assert_eq!(game.decision_log.len(), 1);
+        let decision = &game.decision_log[0];
+        assert_eq!(decision.hand_number, 1);
+        assert!((0.0..=1.0).contains(&decision.equity));
+        assert!(decision.summary().starts_with("#1 PreFlop"));
+    }
+
+    #[test]
+    fn test_planned_bot_move_applies_once() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        while !game.is_bot_turn() {
+            game.move_to_next_player();
+        }
+        let snapshot = game.snapshot();
+        let planned = std::thread::spawn(move || PokerGame::plan_bot_move(&snapshot))
+            .join()
+            .unwrap()
+            .expect("bot to act");
+        assert!(game.decision_log.is_empty());
+
+        assert!(game.apply_bot_move(planned.clone()));
+        assert_eq!(game.decision_log.len(), 1);
+        assert!(!game.apply_bot_move(planned));
+        assert_eq!(game.decision_log.len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+use super::strategy::{BotLine, MIN_BET_AMOUNT};
+use crate::engine::{GamePhase, PokerGame, MIN_RAISE};
+use rand::Rng;
+
+const SLOWPLAY_HAND_THRESHOLD: i32 = 300;
+const CHECK_RAISE_HAND_THRESHOLD: i32 = 200;
+const DONK_BET_HAND_THRESHOLD: i32 = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TrapState {
+    Undecided,
+    Declined,
+    Slowplaying(GamePhase),
+    Sprung,
+}
+
+impl PokerGame {
+    pub(crate) fn trap_line(
+        &mut self,
+        hand_strength: i32,
+        to_call: i32,
+        player_chips: i32,
+    ) -> Option<BotLine> {
+        let is_monster = hand_strength >= SLOWPLAY_HAND_THRESHOLD;
+        let current_bet = self.current_bet.as_i32();
+        let can_raise_to = |amount: i32| {
+            let to_bet = amount.max((self.current_bet + MIN_RAISE).as_i32());
+            (player_chips >= to_bet).then_some(to_bet)
+        };
+        let passive_action = match to_call {
+            0 => Some("check"),
+            _ if player_chips >= to_call => Some("call"),
+            _ => None,
+        };
+
+        match self.trap.clone() {
+            TrapState::Undecided
+                if is_monster && matches!(self.phase, GamePhase::Flop | GamePhase::Turn) =>
+            {
+                let roll = self.bot_rng.gen_range(0..100);
+                let action = passive_action?;
+                if roll >= self.bot_personality.slowplay_chance() {
+                    self.trap = TrapState::Declined;
+                    return None;
+                }
+                self.trap = TrapState::Slowplaying(self.phase.clone());
+                Some((
+                    format!("Slow-play: {} {}", action, self.get_phase_name()),
+                    Some(roll),
+                    action,
+                    0,
+                ))
+            }
+            TrapState::Slowplaying(street) if street == self.phase => {
+                let action = passive_action?;
+                Some((
+                    format!("Slow-play: {} {}", action, self.get_phase_name()),
+                    None,
+                    action,
+                    0,
+                ))
+            }
+            TrapState::Slowplaying(_) => {
+                self.trap = TrapState::Sprung;
+                let (action, target) = if to_call == 0 {
+                    ("bet", (self.pot.as_i32() * 3 / 4).max(MIN_BET_AMOUNT))
+                } else {
+                    ("raise", current_bet * 3)
+                };
+                let to_bet = can_raise_to(target)?;
+                Some((
+                    format!("Trap sprung: {} {}", action, self.get_phase_name()),
+                    None,
+                    action,
+                    to_bet,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn postflop_line(
+        &mut self,
+        hand_strength: i32,
+        to_call: i32,
+        player_chips: i32,
+    ) -> Option<BotLine> {
+        if self.phase == GamePhase::PreFlop {
+            return None;
+        }
+        let params = &self.bot_params;
+        let bot = self.current_player;
+
+        if to_call > 0 && self.check_raise_plan.as_ref() == Some(&self.phase) {
+            self.check_raise_plan = None;
+            let current_bet = self.current_bet.as_i32();
+            let to_bet = (current_bet * 3).max(current_bet + MIN_RAISE.as_i32());
+            if player_chips < to_bet {
+                return None;
+            }
+            return Some((
+                format!("Check-raise {}", self.get_phase_name()),
+                None,
+                "raise",
+                to_bet,
+            ));
+        }
+
+        let first_to_act = to_call == 0 && self.betting.actions() == 0;
+        if !first_to_act {
+            return None;
+        }
+
+        let opponent_was_aggressor = self
+            .previous_street_aggressor
+            .is_some_and(|aggressor| aggressor != bot);
+        if opponent_was_aggressor && hand_strength >= DONK_BET_HAND_THRESHOLD {
+            let roll = self.bot_rng.gen_range(0..100);
+            let to_bet = (self.pot.as_i32() / 2).max(MIN_BET_AMOUNT);
+            if roll < params.donk_bet_chance && player_chips >= to_bet {
+                return Some((
+                    format!("Donk bet into aggressor on {}", self.get_phase_name()),
+                    Some(roll),
+                    "bet",
+                    to_bet,
+                ));
+            }
+        }
+
+        if hand_strength >= CHECK_RAISE_HAND_THRESHOLD {
+            let roll = self.bot_rng.gen_range(0..100);
+            if roll < params.check_raise_chance {
+                self.check_raise_plan = Some(self.phase.clone());
+                return Some((
+                    format!("Check to raise on {}", self.get_phase_name()),
+                    Some(roll),
+                    "check",
+                    0,
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::BotPersonality;
+    use crate::engine::{Card, Chips};
+
+    fn create_card(rank: &str, suit: &str, value: i32) -> Card {
+        Card::new(rank, suit, value)
+    }
+
+    fn set_hand(game: &mut PokerGame, hole: Vec<Card>, community: Vec<Card>) {
+        game.players[1].cards = hole;
+        game.community_cards = community;
+        game.current_player = 1;
+    }
+
+    fn flopped_set() -> (Vec<Card>, Vec<Card>) {
+        (
+            vec![create_card("9", "♠", 9), create_card("9", "♥", 9)],
+            vec![
+                create_card("9", "♦", 9),
+                create_card("4", "♣", 4),
+                create_card("K", "♠", 13),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_trapper_slowplays_flop_then_springs_on_turn() {
+        let mut game = PokerGame::new();
+        game.bot_personality = BotPersonality::Trapper;
+        game.trap = TrapState::Slowplaying(GamePhase::Flop);
+        game.phase = GamePhase::Flop;
+        game.pot = Chips::new(100);
+        let (hole, community) = flopped_set();
+        set_hand(&mut game, hole, community);
+
+        let flop = game.trap_line(309, 0, 1000).expect("slow-play line");
+        assert_eq!(flop.2, "check");
+
+        game.phase = GamePhase::Turn;
+        let turn = game.trap_line(309, 0, 1000).expect("trap line");
+        assert_eq!(turn.2, "bet");
+        assert_eq!(turn.3, 75);
+        assert_eq!(game.trap, TrapState::Sprung);
+        assert!(game.trap_line(309, 0, 1000).is_none());
+    }
+
+    #[test]
+    fn test_weak_hands_never_slowplay() {
+        let mut game = PokerGame::new();
+        game.bot_personality = BotPersonality::Trapper;
+        game.phase = GamePhase::Flop;
+        assert!(game.trap_line(110, 0, 1000).is_none());
+        assert_eq!(game.trap, TrapState::Undecided);
+    }
+
+    #[test]
+    fn test_check_raise_plan_raises_facing_bet() {
+        let mut game = PokerGame::new();
+        game.phase = GamePhase::Flop;
+        game.current_player = 1;
+        game.check_raise_plan = Some(GamePhase::Flop);
+        game.current_bet = Chips::new(40);
+
+        let line = game.postflop_line(210, 40, 1000).expect("check-raise line");
+        assert_eq!(line.2, "raise");
+        assert_eq!(line.3, 120);
+        assert!(game.check_raise_plan.is_none());
+    }
+
+    #[test]
+    fn test_check_raise_planned_when_always_on() {
+        let mut game = PokerGame::new();
+        game.phase = GamePhase::Turn;
+        game.current_player = 1;
+        game.bot_params.check_raise_chance = 100;
+        game.bot_params.donk_bet_chance = 0;
+
+        let line = game.postflop_line(210, 0, 1000).expect("check line");
+        assert_eq!(line.2, "check");
+        assert_eq!(game.check_raise_plan, Some(GamePhase::Turn));
+    }
+
+    #[test]
+    fn test_donk_bet_leads_into_previous_aggressor() {
+        let mut game = PokerGame::new();
+        game.phase = GamePhase::Flop;
+        game.current_player = 1;
+        game.pot = Chips::new(120);
+        game.previous_street_aggressor = Some(0);
+        game.bot_params.donk_bet_chance = 100;
+
+        let line = game.postflop_line(105, 0, 1000).expect("donk line");
+        assert_eq!(line.2, "bet");
+        assert_eq!(line.3, 60);
+    }
+
+    #[test]
+    fn test_no_postflop_lines_preflop_or_after_action() {
+        let mut game = PokerGame::new();
+        game.current_player = 1;
+        game.bot_params.check_raise_chance = 100;
+        assert!(game.postflop_line(210, 0, 1000).is_none());
+
+        game.phase = GamePhase::Flop;
+        game.betting.record(0, false);
+        assert!(game.postflop_line(210, 0, 1000).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use super::chips::Chips;
+use std::fmt;
+
+/// A betting decision. `Bet` and `Raise` carry the total the player's bet is
+/// raised to on this street, not the increment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(Chips),
+    Raise(Chips),
+    AllIn,
+}
+
+impl Action {
+    /// Builds an action from the names used in bot lines and the debug log.
+    pub fn from_name(name: &str, amount: Chips) -> Option<Self> {
+        match name {
+            "fold" => Some(Action::Fold),
+            "check" => Some(Action::Check),
+            "call" => Some(Action::Call),
+            "bet" => Some(Action::Bet(amount)),
+            "raise" => Some(Action::Raise(amount)),
+            "all-in" => Some(Action::AllIn),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Fold => "fold",
+            Action::Check => "check",
+            Action::Call => "call",
+            Action::Bet(_) => "bet",
+            Action::Raise(_) => "raise",
+            Action::AllIn => "all-in",
+        }
+    }
+}
+
+/// Why [`crate::PokerGame::player_action`] rejected an action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionError {
+    IllegalCheck,
+    InsufficientChips,
+    BelowMinRaise,
+    OutOfTurn,
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ActionError::IllegalCheck => "Cannot check",
+            ActionError::InsufficientChips => "Not enough chips",
+            ActionError::BelowMinRaise => "Raise is below the minimum",
+            ActionError::OutOfTurn => "Not your turn",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_names_round_trip() {
+        for action in [
+            Action::Fold,
+            Action::Check,
+            Action::Call,
+            Action::Bet(Chips::new(40)),
+            Action::Raise(Chips::new(60)),
+            Action::AllIn,
+        ] {
+            assert_eq!(
+                Action::from_name(action.name(), Chips::new(40)).map(|a| a.name()),
+                Some(action.name())
+            );
+        }
+        assert_eq!(
+            Action::from_name("raise", Chips::new(60)),
+            Some(Action::Raise(Chips::new(60)))
+        );
+        assert_eq!(Action::from_name("limp", Chips::ZERO), None);
+    }
+}
@@ -0,0 +1,147 @@
+use super::chips::Chips;
+
+/// Tracks who has acted on the current street. Posting a blind does not count
+/// as acting, which is what gives the big blind its preflop option. A bet or
+/// raise reopens the action for everyone else.
+#[derive(Clone, Debug, Default)]
+pub struct BettingRound {
+    acted: Vec<bool>,
+    actions: u32,
+}
+
+/// What a seat can still do on this street.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeatStatus {
+    Folded,
+    AllIn,
+    Active,
+}
+
+/// The price of a call: `call` more chips for a shot at `pot`, which already
+/// holds every bet on the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PotOdds {
+    pub call: Chips,
+    pub pot: Chips,
+}
+
+/// A share of the chips bet this hand and the seats that can win it. The
+/// main pot comes first; a side pot holds chips an all-in stack couldn't
+/// match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: Chips,
+    pub eligible: Vec<usize>,
+}
+
+impl PotOdds {
+    /// The share of the final pot the call makes up, which is the equity a
+    /// call needs to break even.
+    pub fn required_equity(self) -> f64 {
+        let total = (self.pot + self.call).amount();
+        if total == 0 {
+            return 0.0;
+        }
+        self.call.amount() as f64 / total as f64
+    }
+}
+
+impl BettingRound {
+    pub fn new(seats: usize) -> Self {
+        Self {
+            acted: vec![false; seats],
+            actions: 0,
+        }
+    }
+
+    pub fn record(&mut self, seat: usize, reopens_action: bool) {
+        if reopens_action {
+            self.acted.iter_mut().for_each(|acted| *acted = false);
+        }
+        self.acted[seat] = true;
+        self.actions += 1;
+    }
+
+    pub fn has_acted(&self, seat: usize) -> bool {
+        self.acted.get(seat).copied().unwrap_or(false)
+    }
+
+    /// Number of actions taken on this street so far.
+    pub fn actions(&self) -> u32 {
+        self.actions
+    }
+
+    /// Whether the street is over, given each seat's status and how much it
+    /// still owes to match the current bet.
+    pub fn is_complete(&self, seats: &[(SeatStatus, Chips)]) -> bool {
+        let live = seats
+            .iter()
+            .filter(|(status, _)| *status != SeatStatus::Folded)
+            .count();
+        if live <= 1 {
+            return true;
+        }
+
+        let active: Vec<(usize, Chips)> = seats
+            .iter()
+            .enumerate()
+            .filter(|(_, (status, _))| *status == SeatStatus::Active)
+            .map(|(seat, (_, owed))| (seat, *owed))
+            .collect();
+        if active.len() <= 1 {
+            return active.iter().all(|(_, owed)| owed.is_zero());
+        }
+        active
+            .iter()
+            .all(|(seat, owed)| self.has_acted(*seat) && owed.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTIVE: SeatStatus = SeatStatus::Active;
+
+    #[test]
+    fn test_big_blind_keeps_option_after_limp() {
+        let mut round = BettingRound::new(2);
+        assert!(!round.is_complete(&[(ACTIVE, Chips::new(10)), (ACTIVE, Chips::new(0))]));
+        round.record(0, false);
+        assert!(!round.is_complete(&[(ACTIVE, Chips::new(0)), (ACTIVE, Chips::new(0))]));
+        round.record(1, false);
+        assert!(round.is_complete(&[(ACTIVE, Chips::new(0)), (ACTIVE, Chips::new(0))]));
+    }
+
+    #[test]
+    fn test_raise_reopens_action() {
+        let mut round = BettingRound::new(2);
+        round.record(0, false);
+        round.record(1, true);
+        assert!(!round.has_acted(0));
+        assert!(!round.is_complete(&[(ACTIVE, Chips::new(40)), (ACTIVE, Chips::new(0))]));
+        round.record(0, false);
+        assert!(round.is_complete(&[(ACTIVE, Chips::new(0)), (ACTIVE, Chips::new(0))]));
+        assert_eq!(round.actions(), 3);
+    }
+
+    #[test]
+    fn test_pot_odds_required_equity() {
+        let odds = PotOdds {
+            call: Chips::new(40),
+            pot: Chips::new(140),
+        };
+        assert!((odds.required_equity() - 0.2222).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fold_or_all_in_ends_the_action() {
+        let round = BettingRound::new(2);
+        assert!(round.is_complete(&[(SeatStatus::Folded, Chips::new(0)), (ACTIVE, Chips::new(0))]));
+        assert!(round.is_complete(&[(SeatStatus::AllIn, Chips::new(0)), (ACTIVE, Chips::new(0))]));
+        assert!(!round.is_complete(&[
+            (SeatStatus::AllIn, Chips::new(0)),
+            (ACTIVE, Chips::new(200))
+        ]));
+    }
+}
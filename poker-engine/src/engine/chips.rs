@@ -0,0 +1,135 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+/// A non-negative amount of chips. Arithmetic that would overflow or go below
+/// zero panics; use [`Chips::checked_sub`] where a shortfall is expected and
+/// should become an error instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Chips(u32);
+
+impl Chips {
+    pub const ZERO: Chips = Chips(0);
+
+    pub const fn new(amount: u32) -> Self {
+        Chips(amount)
+    }
+
+    /// Converts a signed amount, treating anything below zero as zero.
+    pub fn saturating_from(amount: i32) -> Self {
+        Chips(amount.max(0) as u32)
+    }
+
+    pub fn amount(self) -> u32 {
+        self.0
+    }
+
+    /// The amount as an `i32`, for UI properties and the bot's heuristics.
+    pub fn as_i32(self) -> i32 {
+        i32::try_from(self.0).unwrap_or(i32::MAX)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Chips) -> Option<Chips> {
+        self.0.checked_add(other.0).map(Chips)
+    }
+
+    pub fn checked_sub(self, other: Chips) -> Option<Chips> {
+        self.0.checked_sub(other.0).map(Chips)
+    }
+
+    pub fn saturating_sub(self, other: Chips) -> Chips {
+        Chips(self.0.saturating_sub(other.0))
+    }
+
+    /// Splits into `parts` equal shares, returning the share and the odd
+    /// chips left over.
+    pub fn split(self, parts: u32) -> (Chips, Chips) {
+        (Chips(self.0 / parts), Chips(self.0 % parts))
+    }
+}
+
+impl fmt::Display for Chips {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}", self.0)
+    }
+}
+
+impl TryFrom<i32> for Chips {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(amount: i32) -> Result<Self, Self::Error> {
+        u32::try_from(amount).map(Chips)
+    }
+}
+
+impl Add for Chips {
+    type Output = Chips;
+
+    fn add(self, other: Chips) -> Chips {
+        self.checked_add(other).expect("chip count overflowed")
+    }
+}
+
+impl Sub for Chips {
+    type Output = Chips;
+
+    fn sub(self, other: Chips) -> Chips {
+        self.checked_sub(other)
+            .expect("chip count would go negative")
+    }
+}
+
+impl Mul<u32> for Chips {
+    type Output = Chips;
+
+    fn mul(self, factor: u32) -> Chips {
+        Chips(self.0.checked_mul(factor).expect("chip count overflowed"))
+    }
+}
+
+impl AddAssign for Chips {
+    fn add_assign(&mut self, other: Chips) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Chips {
+    fn sub_assign(&mut self, other: Chips) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Chips {
+    fn sum<I: Iterator<Item = Chips>>(iter: I) -> Chips {
+        iter.fold(Chips::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chips_arithmetic_is_checked() {
+        let stack = Chips::new(100);
+        assert_eq!(stack + Chips::new(20), Chips::new(120));
+        assert_eq!(stack.checked_sub(Chips::new(101)), None);
+        assert_eq!(stack.saturating_sub(Chips::new(101)), Chips::ZERO);
+        assert_eq!(Chips::new(u32::MAX).checked_add(Chips::new(1)), None);
+        assert_eq!(stack * 3, Chips::new(300));
+        assert_eq!(Chips::try_from(-5).ok(), None);
+        assert_eq!(Chips::saturating_from(-5), Chips::ZERO);
+        assert_eq!(Chips::new(25).split(2), (Chips::new(12), Chips::new(1)));
+        assert_eq!(stack.to_string(), "$100");
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn test_chips_never_go_negative() {
+        let _ = Chips::new(10) - Chips::new(20);
+    }
+}
@@ -0,0 +1,169 @@
+use rand::seq::SliceRandom;
+use rand::{thread_rng, RngCore};
+use std::fmt;
+
+/// A playing card; `value` runs from 2 to 14 (ace high).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub rank: String,
+    pub suit: String,
+    pub value: i32,
+}
+
+impl Card {
+    pub fn new(rank: &str, suit: &str, value: i32) -> Self {
+        Self {
+            rank: rank.to_string(),
+            suit: suit.to_string(),
+            value,
+        }
+    }
+}
+
+/// `10♥`: the rank, then the suit.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// Builds an ordered 52-card deck.
+pub fn full_deck() -> Vec<Card> {
+    let ranks = [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+    ];
+    let suits = ["♠", "♥", "♦", "♣"];
+    let mut deck = Vec::with_capacity(52);
+    for (value, rank) in (2..).zip(ranks.iter()) {
+        for suit in &suits {
+            deck.push(Card::new(rank, suit, value));
+        }
+    }
+    deck
+}
+
+/// Where a hand's cards come from: [`LocalDeck`], shuffled right here, or
+/// with the `mental-poker` feature a deck both players shuffled between
+/// them ([`crate::mental::MentalDeck`]). The game deals every card, hole
+/// cards and board alike, from the top of it.
+pub trait DeckSource: fmt::Debug + Send {
+    /// Gets a full deck ready for a new hand; `rng` is the hand's seeded
+    /// randomness, for a source that shuffles with it.
+    fn new_hand(&mut self, rng: &mut dyn RngCore);
+
+    /// The next card, or `None` if the deck is out.
+    fn deal(&mut self) -> Option<Card>;
+
+    /// Cards left to deal.
+    fn remaining(&self) -> usize;
+
+    /// Takes `cards` out of what's left, for cards put in play by hand.
+    /// A deck no one can see into can't be arranged, so by default this
+    /// and the two below do nothing.
+    fn take_out(&mut self, _cards: &[Card]) {}
+
+    /// Returns `cards` to the deck somewhere they can't be predicted.
+    fn put_back(&mut self, _cards: Vec<Card>) {}
+
+    /// Puts `cards` on top so they are dealt next, first card first.
+    fn stack(&mut self, _cards: Vec<Card>) {}
+
+    fn clone_box(&self) -> Box<dyn DeckSource>;
+}
+
+impl Clone for Box<dyn DeckSource> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A deck shuffled by the instance running the game.
+#[derive(Clone, Debug, Default)]
+pub struct LocalDeck {
+    // Dealt from the end.
+    cards: Vec<Card>,
+}
+
+impl DeckSource for LocalDeck {
+    fn new_hand(&mut self, rng: &mut dyn RngCore) {
+        self.cards = full_deck();
+        self.cards.shuffle(rng);
+    }
+
+    fn deal(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    fn take_out(&mut self, cards: &[Card]) {
+        self.cards.retain(|card| !cards.contains(card));
+    }
+
+    fn put_back(&mut self, cards: Vec<Card>) {
+        self.cards.extend(cards);
+        self.cards.shuffle(&mut thread_rng());
+    }
+
+    fn stack(&mut self, cards: Vec<Card>) {
+        self.take_out(&cards);
+        self.cards.extend(cards.into_iter().rev());
+    }
+
+    fn clone_box(&self) -> Box<dyn DeckSource> {
+        Box::new(self.clone())
+    }
+}
+
+/// Short starting-hand notation such as `AKs`, `T9o` or `77`.
+pub fn starting_hand_code(hole_cards: &[Card]) -> String {
+    let [first, second] = hole_cards else {
+        return String::new();
+    };
+    let rank_char = |card: &Card| match card.value {
+        10 => 'T',
+        _ => card.rank.chars().next().unwrap_or('?'),
+    };
+    let (high, low) = if first.value >= second.value {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    let suffix = if high.value == low.value {
+        ""
+    } else if high.suit == low.suit {
+        "s"
+    } else {
+        "o"
+    };
+    format!("{}{}{}", rank_char(high), rank_char(low), suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_card(rank: &str, suit: &str, value: i32) -> Card {
+        Card::new(rank, suit, value)
+    }
+
+    #[test]
+    fn test_full_deck_has_52_unique_cards() {
+        let deck = full_deck();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(deck.iter().filter(|c| c.value == 14).count(), 4);
+        assert_eq!(deck.iter().filter(|c| c.suit == "♠").count(), 13);
+    }
+    #[test]
+    fn test_starting_hand_code() {
+        let ace = create_card("A", "♠", 14);
+        let ten = create_card("10", "♠", 10);
+        let other_ace = create_card("A", "♥", 14);
+        assert_eq!(starting_hand_code(&[ten.clone(), ace.clone()]), "ATs");
+        assert_eq!(starting_hand_code(&[other_ace.clone(), ten]), "ATo");
+        assert_eq!(starting_hand_code(&[ace, other_ace]), "AA");
+        assert_eq!(starting_hand_code(&[]), "");
+    }
+}
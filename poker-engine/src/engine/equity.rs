@@ -0,0 +1,65 @@
+use super::deck::{full_deck, Card};
+use super::evaluator::{compare_hands, evaluate_hand};
+use rand::{seq::SliceRandom, Rng};
+
+/// Monte Carlo estimate of the chance `hole_cards` beat a random hand (ties count half).
+pub fn estimate_equity<R: Rng>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    iterations: u32,
+    rng: &mut R,
+) -> f64 {
+    let is_known = |card: &Card| {
+        hole_cards
+            .iter()
+            .chain(community_cards.iter())
+            .any(|k| k.value == card.value && k.suit == card.suit)
+    };
+    let remaining: Vec<Card> = full_deck().into_iter().filter(|c| !is_known(c)).collect();
+    let board_needed = 5usize.saturating_sub(community_cards.len());
+    if iterations == 0 || remaining.len() < board_needed + 2 {
+        return 0.0;
+    }
+
+    let mut score = 0.0;
+    for _ in 0..iterations {
+        let drawn: Vec<&Card> = remaining.choose_multiple(rng, board_needed + 2).collect();
+        let opponent: Vec<Card> = drawn[..2].iter().map(|&c| c.clone()).collect();
+        let mut board = community_cards.to_vec();
+        board.extend(drawn[2..].iter().map(|&c| c.clone()));
+
+        let ours = evaluate_hand(hole_cards, &board);
+        let theirs = evaluate_hand(&opponent, &board);
+        let comparison = compare_hands(&ours, &theirs);
+        if comparison > 0 {
+            score += 1.0;
+        } else if comparison == 0 {
+            score += 0.5;
+        }
+    }
+    score / iterations as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn create_card(rank: &str, suit: &str, value: i32) -> Card {
+        Card::new(rank, suit, value)
+    }
+
+    #[test]
+    fn test_equity_of_nuts_on_river_is_certain() {
+        let hole = vec![create_card("A", "♠", 14), create_card("K", "♠", 13)];
+        let community = vec![
+            create_card("Q", "♠", 12),
+            create_card("J", "♠", 11),
+            create_card("10", "♠", 10),
+            create_card("2", "♥", 2),
+            create_card("3", "♦", 3),
+        ];
+        let equity = estimate_equity(&hole, &community, 50, &mut thread_rng());
+        assert_eq!(equity, 1.0);
+    }
+}
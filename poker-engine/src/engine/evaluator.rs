@@ -0,0 +1,653 @@
+use super::deck::Card;
+
+/// Poker hand categories, ordered from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandRank {
+    HighCard = 0,
+    Pair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    Straight = 4,
+    Flush = 5,
+    FullHouse = 6,
+    FourOfAKind = 7,
+    StraightFlush = 8,
+}
+
+/// Result of [`evaluate_hand`], comparable with [`compare_hands`].
+#[derive(Clone, Debug)]
+pub struct EvaluatedHand {
+    pub rank: HandRank,
+    pub primary_value: i32,
+    pub secondary_values: Vec<i32>,
+}
+
+/// Evaluates the best hand made from hole cards plus the board.
+pub fn evaluate_hand(hole_cards: &[Card], community_cards: &[Card]) -> EvaluatedHand {
+    best_hand(hole_cards.iter().chain(community_cards).collect()).0
+}
+
+// The best hand out of `cards` and the (up to) five cards making it, best
+// first: the made part of the hand, then the kickers.
+fn best_hand(mut cards: Vec<&Card>) -> (EvaluatedHand, Vec<&Card>) {
+    cards.sort_by_key(|card| std::cmp::Reverse(card.value));
+    let hand = |rank, primary_value, secondary_values| EvaluatedHand {
+        rank,
+        primary_value,
+        secondary_values,
+    };
+    // The highest cards not already in `used`.
+    let kickers = |used: &[&Card], count: usize| -> Vec<&Card> {
+        cards
+            .iter()
+            .filter(|card| !used.iter().any(|held| std::ptr::eq(*held, **card)))
+            .take(count)
+            .copied()
+            .collect()
+    };
+    let values = |five: &[&Card]| five.iter().map(|card| card.value).collect::<Vec<i32>>();
+
+    let suited = ["♠", "♥", "♦", "♣"]
+        .iter()
+        .map(|suit| -> Vec<&Card> { cards.iter().filter(|c| c.suit == *suit).copied().collect() })
+        .find(|suited| suited.len() >= 5);
+    if let Some((high, five)) = suited.as_deref().and_then(straight) {
+        return (hand(HandRank::StraightFlush, high, Vec::new()), five);
+    }
+
+    // Cards of one value together, the biggest groups first, then the
+    // highest.
+    let mut groups: Vec<Vec<&Card>> = Vec::new();
+    for card in &cards {
+        match groups.last_mut() {
+            Some(group) if group[0].value == card.value => group.push(card),
+            _ => groups.push(vec![card]),
+        }
+    }
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then(b[0].value.cmp(&a[0].value)));
+    let size = |index: usize| groups.get(index).map_or(0, Vec::len);
+
+    if size(0) == 4 {
+        let mut five = groups[0].clone();
+        five.extend(kickers(&five, 1));
+        let kicker = values(&five[4..]);
+        return (
+            hand(HandRank::FourOfAKind, groups[0][0].value, kicker),
+            five,
+        );
+    }
+    if size(0) == 3 && size(1) >= 2 {
+        let mut five = groups[0].clone();
+        five.extend(&groups[1][..2]);
+        let pair = vec![groups[1][0].value];
+        return (hand(HandRank::FullHouse, groups[0][0].value, pair), five);
+    }
+    if let Some(suited) = suited {
+        let five = suited[..5].to_vec();
+        let values = values(&five);
+        return (hand(HandRank::Flush, values[0], values[1..].to_vec()), five);
+    }
+    if let Some((high, five)) = straight(&cards) {
+        return (hand(HandRank::Straight, high, Vec::new()), five);
+    }
+    if size(0) == 3 {
+        let mut five = groups[0].clone();
+        five.extend(kickers(&five, 2));
+        let kickers = values(&five[3..]);
+        return (
+            hand(HandRank::ThreeOfAKind, groups[0][0].value, kickers),
+            five,
+        );
+    }
+    if size(0) == 2 && size(1) == 2 {
+        let mut five = [groups[0].as_slice(), groups[1].as_slice()].concat();
+        five.extend(kickers(&five, 1));
+        let mut rest = vec![groups[1][0].value];
+        rest.extend(values(&five[4..]));
+        return (hand(HandRank::TwoPair, groups[0][0].value, rest), five);
+    }
+    if size(0) == 2 {
+        let mut five = groups[0].clone();
+        five.extend(kickers(&five, 3));
+        let kickers = values(&five[2..]);
+        return (hand(HandRank::Pair, groups[0][0].value, kickers), five);
+    }
+    let five = kickers(&[], 5);
+    let values = values(&five);
+    let high = values.first().copied().unwrap_or(0);
+    let rest = values.get(1..).unwrap_or_default().to_vec();
+    (hand(HandRank::HighCard, high, rest), five)
+}
+
+// The highest straight in `cards`, sorted high to low, with its top card's
+// value; the ace plays low in the wheel, which is five high.
+fn straight<'a>(cards: &[&'a Card]) -> Option<(i32, Vec<&'a Card>)> {
+    let find = |value: i32| {
+        cards
+            .iter()
+            .find(|card| card.value == value || (value == 1 && card.value == 14))
+            .copied()
+    };
+    (5..=14).rev().find_map(|high| {
+        let five: Option<Vec<&Card>> = (0..5).map(|step| find(high - step)).collect();
+        five.map(|five| (high, five))
+    })
+}
+
+impl EvaluatedHand {
+    /// The hand as a player would say it, e.g. "Two Pair, Kings and Nines".
+    pub fn describe(&self) -> String {
+        let kicker = |index: usize| self.secondary_values.get(index).copied().unwrap_or(0);
+        match self.rank {
+            HandRank::HighCard => format!("High Card, {}", value_name(self.primary_value)),
+            HandRank::Pair => format!("Pair of {}", plural_name(self.primary_value)),
+            HandRank::TwoPair => format!(
+                "Two Pair, {} and {}",
+                plural_name(self.primary_value),
+                plural_name(kicker(0))
+            ),
+            HandRank::ThreeOfAKind => {
+                format!("Three of a Kind, {}", plural_name(self.primary_value))
+            }
+            HandRank::Straight => format!("Straight, {} high", value_name(self.primary_value)),
+            HandRank::Flush => format!("Flush, {} high", value_name(self.primary_value)),
+            HandRank::FullHouse => format!(
+                "Full House, {} over {}",
+                plural_name(self.primary_value),
+                plural_name(kicker(0))
+            ),
+            HandRank::FourOfAKind => {
+                format!("Four of a Kind, {}", plural_name(self.primary_value))
+            }
+            HandRank::StraightFlush if self.primary_value == 14 => "Royal Flush".to_string(),
+            HandRank::StraightFlush => {
+                format!("Straight Flush, {} high", value_name(self.primary_value))
+            }
+        }
+    }
+}
+
+fn value_name(value: i32) -> &'static str {
+    match value {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace",
+    }
+}
+
+fn plural_name(value: i32) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        _ => format!("{}s", value_name(value)),
+    }
+}
+
+/// The five cards out of hole cards plus the board that make the best hand,
+/// in the order they were given. With five cards or fewer, all of them.
+pub fn best_five(hole_cards: &[Card], community_cards: &[Card]) -> Vec<Card> {
+    let cards: Vec<&Card> = hole_cards.iter().chain(community_cards).collect();
+    if cards.len() <= 5 {
+        return cards.into_iter().cloned().collect();
+    }
+    let (_, five) = best_hand(cards.clone());
+    cards
+        .into_iter()
+        .filter(|card| five.iter().any(|best| std::ptr::eq(*best, *card)))
+        .cloned()
+        .collect()
+}
+
+/// A hand one card short of a flush or straight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Draw {
+    Flush,
+    /// Two ranks complete the straight (open-ended or a double gutshot).
+    OpenEnded,
+    /// Only one rank completes the straight.
+    Gutshot,
+}
+
+impl Draw {
+    pub fn name(self) -> &'static str {
+        match self {
+            Draw::Flush => "flush draw",
+            Draw::OpenEnded => "open-ended straight draw",
+            Draw::Gutshot => "gutshot",
+        }
+    }
+}
+
+/// The draws still live with more board cards to come; none on the river or
+/// once the hand is already that strong.
+pub fn draws(hole_cards: &[Card], community_cards: &[Card]) -> Vec<Draw> {
+    if community_cards.len() < 3 || community_cards.len() >= 5 {
+        return Vec::new();
+    }
+    let made = evaluate_hand(hole_cards, community_cards).rank;
+    let cards: Vec<&Card> = hole_cards.iter().chain(community_cards).collect();
+    let mut found = Vec::new();
+
+    let suited = |suit: &str| cards.iter().filter(|card| card.suit == suit).count();
+    if made < HandRank::Flush && cards.iter().any(|card| suited(&card.suit) == 4) {
+        found.push(Draw::Flush);
+    }
+
+    if made < HandRank::Straight {
+        let ranks = cards
+            .iter()
+            .fold(0u16, |ranks, card| ranks | rank_bit(card.value));
+        let outs = (2..=14)
+            .filter(|&value| ranks & rank_bit(value) == 0 && has_straight(ranks | rank_bit(value)))
+            .count();
+        match outs {
+            0 => {}
+            1 => found.push(Draw::Gutshot),
+            _ => found.push(Draw::OpenEnded),
+        }
+    }
+    found
+}
+
+// Bit `value` for each rank, plus bit 1 for an ace so it can play low.
+fn rank_bit(value: i32) -> u16 {
+    let bit = 1 << value;
+    if value == 14 {
+        bit | 1 << 1
+    } else {
+        bit
+    }
+}
+
+fn has_straight(ranks: u16) -> bool {
+    (1..=10).any(|low| (ranks >> low) & 0b11111 == 0b11111)
+}
+
+/// Returns a positive number if `hand1` wins, negative if `hand2` wins, 0 on a tie.
+pub fn compare_hands(hand1: &EvaluatedHand, hand2: &EvaluatedHand) -> i32 {
+    if hand1.rank != hand2.rank {
+        return hand1.rank as i32 - hand2.rank as i32;
+    }
+    if hand1.primary_value != hand2.primary_value {
+        return hand1.primary_value - hand2.primary_value;
+    }
+    for (v1, v2) in hand1
+        .secondary_values
+        .iter()
+        .zip(hand2.secondary_values.iter())
+    {
+        if v1 != v2 {
+            return v1 - v2;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_card(rank: &str, suit: &str, value: i32) -> Card {
+        Card::new(rank, suit, value)
+    }
+
+    #[test]
+    fn test_high_card_evaluation() {
+        let hole = vec![create_card("2", "♠", 2), create_card("9", "♥", 9)];
+        let community = vec![
+            create_card("4", "♦", 4),
+            create_card("J", "♣", 11),
+            create_card("K", "♠", 13),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::HighCard);
+        assert_eq!(result.primary_value, 13);
+    }
+
+    #[test]
+    fn test_pair_evaluation() {
+        let hole = vec![create_card("A", "♠", 14), create_card("A", "♥", 14)];
+        let community = vec![
+            create_card("2", "♦", 2),
+            create_card("J", "♣", 11),
+            create_card("K", "♠", 13),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::Pair);
+        assert_eq!(result.primary_value, 14);
+    }
+
+    #[test]
+    fn test_two_pair_evaluation() {
+        let hole = vec![create_card("A", "♠", 14), create_card("A", "♥", 14)];
+        let community = vec![
+            create_card("K", "♦", 13),
+            create_card("K", "♣", 13),
+            create_card("Q", "♠", 12),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::TwoPair);
+        assert_eq!(result.primary_value, 14);
+    }
+
+    #[test]
+    fn test_three_of_a_kind_evaluation() {
+        let hole = vec![create_card("A", "♠", 14), create_card("2", "♥", 2)];
+        let community = vec![
+            create_card("A", "♦", 14),
+            create_card("K", "♣", 13),
+            create_card("A", "♠", 14),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::ThreeOfAKind);
+        assert_eq!(result.primary_value, 14);
+    }
+
+    #[test]
+    fn test_straight_evaluation() {
+        let hole = vec![create_card("5", "♠", 5), create_card("6", "♥", 6)];
+        let community = vec![
+            create_card("7", "♦", 7),
+            create_card("8", "♣", 8),
+            create_card("9", "♠", 9),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::Straight);
+        assert_eq!(result.primary_value, 9);
+    }
+
+    #[test]
+    fn test_flush_evaluation() {
+        let hole = vec![create_card("2", "♠", 2), create_card("5", "♠", 5)];
+        let community = vec![
+            create_card("8", "♠", 8),
+            create_card("J", "♠", 11),
+            create_card("K", "♠", 13),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::Flush);
+        assert_eq!(result.primary_value, 13);
+    }
+
+    #[test]
+    fn test_full_house_evaluation() {
+        let hole = vec![create_card("A", "♠", 14), create_card("A", "♥", 14)];
+        let community = vec![
+            create_card("K", "♦", 13),
+            create_card("K", "♣", 13),
+            create_card("A", "♠", 14),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::FullHouse);
+        assert_eq!(result.primary_value, 14);
+    }
+
+    #[test]
+    fn test_four_of_a_kind_evaluation() {
+        let hole = vec![create_card("A", "♠", 14), create_card("A", "♥", 14)];
+        let community = vec![
+            create_card("A", "♦", 14),
+            create_card("K", "♣", 13),
+            create_card("A", "♠", 14),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::FourOfAKind);
+        assert_eq!(result.primary_value, 14);
+    }
+
+    #[test]
+    fn test_straight_flush_evaluation() {
+        let hole = vec![create_card("5", "♠", 5), create_card("6", "♠", 6)];
+        let community = vec![
+            create_card("7", "♠", 7),
+            create_card("8", "♠", 8),
+            create_card("4", "♠", 4),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::StraightFlush);
+        assert_eq!(result.primary_value, 8);
+    }
+
+    #[test]
+    fn test_compare_hands_high_card_wins() {
+        let hand1 = EvaluatedHand {
+            rank: HandRank::HighCard,
+            primary_value: 14,
+            secondary_values: vec![13, 12, 11, 10],
+        };
+        let hand2 = EvaluatedHand {
+            rank: HandRank::HighCard,
+            primary_value: 13,
+            secondary_values: vec![12, 11, 10, 9],
+        };
+        assert!(compare_hands(&hand1, &hand2) > 0);
+        assert!(compare_hands(&hand2, &hand1) < 0);
+    }
+
+    #[test]
+    fn test_compare_hands_pair_beats_high_card() {
+        let pair = EvaluatedHand {
+            rank: HandRank::Pair,
+            primary_value: 10,
+            secondary_values: vec![9, 8],
+        };
+        let high_card = EvaluatedHand {
+            rank: HandRank::HighCard,
+            primary_value: 14,
+            secondary_values: vec![13, 12, 11, 10],
+        };
+        assert!(compare_hands(&pair, &high_card) > 0);
+    }
+
+    #[test]
+    fn test_compare_hands_equal_returns_zero() {
+        let hand1 = EvaluatedHand {
+            rank: HandRank::Pair,
+            primary_value: 10,
+            secondary_values: vec![9, 8],
+        };
+        let hand2 = EvaluatedHand {
+            rank: HandRank::Pair,
+            primary_value: 10,
+            secondary_values: vec![9, 8],
+        };
+        assert_eq!(compare_hands(&hand1, &hand2), 0);
+    }
+
+    #[test]
+    fn test_compare_hands_same_rank_different_primary() {
+        let pair_high = EvaluatedHand {
+            rank: HandRank::Pair,
+            primary_value: 14,
+            secondary_values: vec![13, 12],
+        };
+        let pair_low = EvaluatedHand {
+            rank: HandRank::Pair,
+            primary_value: 10,
+            secondary_values: vec![13, 12],
+        };
+        assert!(compare_hands(&pair_high, &pair_low) > 0);
+    }
+
+    #[test]
+    fn test_wheel_straight_ace_low() {
+        let hole = vec![create_card("A", "♠", 14), create_card("2", "♥", 2)];
+        let community = vec![
+            create_card("3", "♦", 3),
+            create_card("4", "♣", 4),
+            create_card("5", "♠", 5),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::Straight);
+        assert_eq!(result.primary_value, 5);
+        assert_eq!(result.describe(), "Straight, Five high");
+
+        // The wheel is the lowest straight there is.
+        let six_high = evaluate_hand(
+            &[create_card("6", "♥", 6), create_card("2", "♥", 2)],
+            &community,
+        );
+        assert!(compare_hands(&six_high, &result) > 0);
+    }
+
+    #[test]
+    fn test_steel_wheel_is_a_five_high_straight_flush() {
+        let hole = vec![create_card("A", "♠", 14), create_card("2", "♠", 2)];
+        let community = vec![
+            create_card("3", "♠", 3),
+            create_card("4", "♠", 4),
+            create_card("5", "♠", 5),
+            create_card("K", "♥", 13),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.rank, HandRank::StraightFlush);
+        assert_eq!(result.describe(), "Straight Flush, Five high");
+
+        // A straight and a flush apart don't make a straight flush.
+        let split = vec![
+            create_card("3", "♠", 3),
+            create_card("4", "♥", 4),
+            create_card("5", "♠", 5),
+            create_card("9", "♠", 9),
+        ];
+        assert_eq!(evaluate_hand(&hole, &split).rank, HandRank::Flush);
+    }
+
+    #[test]
+    fn test_flushes_compare_from_the_top_card_down() {
+        let board = vec![
+            create_card("2", "♥", 2),
+            create_card("7", "♥", 7),
+            create_card("9", "♥", 9),
+            create_card("J", "♣", 11),
+            create_card("4", "♦", 4),
+        ];
+        let ace = evaluate_hand(
+            &[create_card("A", "♥", 14), create_card("3", "♥", 3)],
+            &board,
+        );
+        let king = evaluate_hand(
+            &[create_card("K", "♥", 13), create_card("Q", "♥", 12)],
+            &board,
+        );
+        assert_eq!(ace.primary_value, 14);
+        assert_eq!(ace.secondary_values, vec![9, 7, 3, 2]);
+        assert!(compare_hands(&ace, &king) > 0);
+    }
+
+    #[test]
+    fn test_high_card_keeps_the_five_highest() {
+        let hole = vec![create_card("A", "♠", 14), create_card("3", "♥", 3)];
+        let community = vec![
+            create_card("K", "♦", 13),
+            create_card("9", "♣", 9),
+            create_card("7", "♠", 7),
+            create_card("2", "♦", 2),
+            create_card("J", "♥", 11),
+        ];
+        let result = evaluate_hand(&hole, &community);
+        assert_eq!(result.primary_value, 14);
+        assert_eq!(result.secondary_values, vec![13, 11, 9, 7]);
+        let weaker = evaluate_hand(
+            &[create_card("A", "♥", 14), create_card("4", "♣", 4)],
+            &[
+                create_card("K", "♠", 13),
+                create_card("9", "♦", 9),
+                create_card("6", "♠", 6),
+                create_card("2", "♣", 2),
+                create_card("J", "♦", 11),
+            ],
+        );
+        assert!(compare_hands(&result, &weaker) > 0);
+    }
+
+    #[test]
+    fn test_best_five_uses_a_straight_on_the_board() {
+        let hole = vec![create_card("K", "♠", 13), create_card("Q", "♥", 12)];
+        let community = vec![
+            create_card("2", "♦", 2),
+            create_card("3", "♣", 3),
+            create_card("4", "♠", 4),
+            create_card("5", "♦", 5),
+            create_card("6", "♥", 6),
+        ];
+        assert_eq!(
+            evaluate_hand(&hole, &community).describe(),
+            "Straight, Six high"
+        );
+        assert_eq!(best_five(&hole, &community), community);
+    }
+
+    #[test]
+    fn test_best_five_picks_the_made_hand() {
+        let hole = vec![create_card("K", "♠", 13), create_card("9", "♥", 9)];
+        let community = vec![
+            create_card("K", "♦", 13),
+            create_card("9", "♣", 9),
+            create_card("2", "♠", 2),
+            create_card("3", "♦", 3),
+            create_card("Q", "♥", 12),
+        ];
+        let five = best_five(&hole, &community);
+        assert_eq!(five.len(), 5);
+        assert!(hole.iter().all(|card| five.contains(card)));
+        assert!(five.contains(&community[4]));
+
+        let hand = evaluate_hand(&five, &[]);
+        assert_eq!(hand.describe(), "Two Pair, Kings and Nines");
+    }
+
+    #[test]
+    fn test_describe_names_each_rank() {
+        let hand = |rank, primary_value, secondary_values: Vec<i32>| EvaluatedHand {
+            rank,
+            primary_value,
+            secondary_values,
+        };
+        assert_eq!(hand(HandRank::Pair, 6, vec![]).describe(), "Pair of Sixes");
+        assert_eq!(
+            hand(HandRank::FullHouse, 10, vec![14]).describe(),
+            "Full House, Tens over Aces"
+        );
+        assert_eq!(
+            hand(HandRank::StraightFlush, 14, vec![]).describe(),
+            "Royal Flush"
+        );
+    }
+
+    #[test]
+    fn test_draws_on_the_flop() {
+        let hole = vec![create_card("9", "♥", 9), create_card("8", "♥", 8)];
+        let flop = vec![
+            create_card("J", "♥", 11),
+            create_card("7", "♥", 7),
+            create_card("2", "♣", 2),
+        ];
+        assert_eq!(draws(&hole, &flop), vec![Draw::Flush, Draw::Gutshot]);
+
+        let open_ended = vec![
+            create_card("10", "♠", 10),
+            create_card("7", "♦", 7),
+            create_card("2", "♣", 2),
+        ];
+        assert_eq!(draws(&hole, &open_ended), vec![Draw::OpenEnded]);
+
+        let wheel = vec![create_card("A", "♠", 14), create_card("2", "♦", 2)];
+        let low_flop = vec![
+            create_card("3", "♣", 3),
+            create_card("4", "♥", 4),
+            create_card("K", "♠", 13),
+        ];
+        assert_eq!(draws(&wheel, &low_flop), vec![Draw::Gutshot]);
+        assert!(draws(&hole, &[]).is_empty());
+    }
+}
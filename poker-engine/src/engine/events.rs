@@ -0,0 +1,41 @@
+use super::action::Action;
+use super::chips::Chips;
+use super::deck::Card;
+use super::game::GamePhase;
+
+/// Something that happened at the table. The engine queues these in order and
+/// frontends drain them with [`crate::PokerGame::drain_events`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    HandStarted {
+        hand_number: u32,
+        dealer: usize,
+    },
+    BlindPosted {
+        seat: usize,
+        amount: Chips,
+    },
+    /// Hole cards for `Some(seat)`, or board cards when `seat` is `None`.
+    CardsDealt {
+        seat: Option<usize>,
+        cards: Vec<Card>,
+    },
+    /// `amount` is the number of chips the player moved into the pot.
+    PlayerActed {
+        seat: usize,
+        action: Action,
+        amount: Chips,
+    },
+    PhaseAdvanced {
+        phase: GamePhase,
+    },
+    /// The whole state was replaced by [`crate::PokerGame::restore`].
+    StateRestored,
+    /// `pot` indexes [`crate::PokerGame::pots`]: 0 for the main pot, then
+    /// the side pots.
+    PotAwarded {
+        seat: usize,
+        amount: Chips,
+        pot: usize,
+    },
+}
@@ -0,0 +1,989 @@
+use super::action::{Action, ActionError};
+use super::betting::{BettingRound, Pot, PotOdds, SeatStatus};
+use super::chips::Chips;
+use super::deck::{Card, DeckSource, LocalDeck};
+use super::evaluator::{compare_hands, evaluate_hand};
+use super::events::GameEvent;
+use super::observer::Observers;
+use super::replay::HandLog;
+use super::stats::PlayerStats;
+use crate::ai::{BotDecision, BotPersonality, BotStyle, StrategyParams, TrapState};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use std::collections::VecDeque;
+use tracing::{debug, info, instrument, trace};
+
+/// Stack each player starts a game with.
+pub const STARTING_CHIPS: Chips = Chips::new(1000);
+/// Small blind posted at the start of every hand.
+pub const SMALL_BLIND: Chips = Chips::new(10);
+/// Big blind posted at the start of every hand.
+pub const BIG_BLIND: Chips = Chips::new(20);
+/// Minimum raise increment over the current bet.
+pub const MIN_RAISE: Chips = Chips::new(20);
+/// Pause between streets so the UI can show the previous one.
+pub const PHASE_TRANSITION_TIME_MS: u64 = 600;
+
+/// A seat at the table.
+#[derive(Clone, Debug)]
+pub struct Player {
+    pub name: String,
+    pub chips: Chips,
+    pub bet: Chips,
+    pub cards: Vec<Card>,
+    /// The hole cards given up by folding this hand. `cards` is emptied on
+    /// a fold, since that's how a seat is known to be out of the hand.
+    pub mucked_cards: Vec<Card>,
+    pub is_user: bool,
+    pub last_action: String,
+}
+
+impl Player {
+    pub fn new(name: &str, is_user: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            chips: STARTING_CHIPS,
+            bet: Chips::ZERO,
+            cards: Vec::new(),
+            mucked_cards: Vec::new(),
+            is_user,
+            last_action: String::new(),
+        }
+    }
+}
+
+/// Betting streets of a hand.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GamePhase {
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+impl GamePhase {
+    /// The street in lowercase, as it is named on the wire.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamePhase::PreFlop => "preflop",
+            GamePhase::Flop => "flop",
+            GamePhase::Turn => "turn",
+            GamePhase::River => "river",
+            GamePhase::Showdown => "showdown",
+        }
+    }
+}
+
+/// Heads-up game state: the user is seat 0 and the bot is seat 1.
+pub struct PokerGame {
+    pub(crate) deck: Box<dyn DeckSource>,
+    pub community_cards: Vec<Card>,
+    pub players: Vec<Player>,
+    pub current_player: usize,
+    pub phase: GamePhase,
+    pub pot: Chips,
+    pub current_bet: Chips,
+    pub dealer_position: usize,
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    pub hand_complete: bool,
+    pub showdown_done: bool,
+    pub bot_params: StrategyParams,
+    pub hand_start_chips: Vec<Chips>,
+    pub bot_style: BotStyle,
+    pub user_stats: PlayerStats,
+    pub bot_stats: PlayerStats,
+    pub last_exploit: Option<String>,
+    pub hand_number: u32,
+    pub decision_log: VecDeque<BotDecision>,
+    pub bot_personality: BotPersonality,
+    pub(crate) trap: TrapState,
+    pub(crate) betting: BettingRound,
+    pub(crate) street_aggressor: Option<usize>,
+    pub(crate) previous_street_aggressor: Option<usize>,
+    pub(crate) check_raise_plan: Option<GamePhase>,
+    pub(crate) bot_rng: StdRng,
+    pub(crate) hand_log: HandLog,
+    pub(crate) events: Vec<GameEvent>,
+    pub(crate) observers: Observers,
+}
+
+impl Default for PokerGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PokerGame {
+    pub fn new() -> Self {
+        let players = vec![Player::new("You", true), Player::new("Bot", false)];
+        let betting = BettingRound::new(players.len());
+
+        Self {
+            deck: Box::new(LocalDeck::default()),
+            community_cards: Vec::new(),
+            players,
+            current_player: 0,
+            phase: GamePhase::PreFlop,
+            pot: Chips::ZERO,
+            current_bet: Chips::ZERO,
+            dealer_position: 0,
+            small_blind: SMALL_BLIND,
+            big_blind: BIG_BLIND,
+            hand_complete: false,
+            showdown_done: false,
+            bot_params: StrategyParams::default(),
+            hand_start_chips: vec![STARTING_CHIPS; 2],
+            bot_style: BotStyle::Standard,
+            user_stats: PlayerStats::default(),
+            bot_stats: PlayerStats::default(),
+            last_exploit: None,
+            hand_number: 0,
+            decision_log: VecDeque::new(),
+            bot_personality: BotPersonality::Balanced,
+            trap: TrapState::Undecided,
+            betting,
+            street_aggressor: None,
+            previous_street_aggressor: None,
+            check_raise_plan: None,
+            bot_rng: StdRng::seed_from_u64(0),
+            hand_log: HandLog::default(),
+            events: Vec::new(),
+            observers: Observers::default(),
+        }
+    }
+
+    fn deal_card(&mut self) -> Option<Card> {
+        self.deck.deal()
+    }
+
+    /// Deals the hands started from now on from `deck`, in place of the
+    /// [`LocalDeck`] every game starts with.
+    pub fn deal_from(&mut self, deck: impl DeckSource + 'static) {
+        self.deck = Box::new(deck);
+    }
+
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    /// Events queued since the last [`PokerGame::drain_events`], oldest first.
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Cards left in the deck.
+    pub fn cards_remaining(&self) -> usize {
+        self.deck.remaining()
+    }
+
+    /// The seed, starting table and actions of the current hand, enough for
+    /// [`crate::replay`] to reproduce it.
+    pub fn hand_log(&self) -> &HandLog {
+        &self.hand_log
+    }
+
+    /// Gives `seat` specific hole cards, for setting up scenarios before the
+    /// flop. The cards are taken out of the deck, or out of another seat's
+    /// hand, which gets a replacement from the deck.
+    pub fn set_hole_cards(&mut self, seat: usize, cards: Vec<Card>) {
+        let returned = std::mem::take(&mut self.players[seat].cards);
+        self.deck.take_out(&cards);
+        for (other, player) in self.players.iter_mut().enumerate() {
+            if other == seat {
+                continue;
+            }
+            for held in player.cards.iter_mut().filter(|held| cards.contains(held)) {
+                if let Some(replacement) = self.deck.deal() {
+                    *held = replacement;
+                }
+            }
+        }
+        self.deck.put_back(
+            returned
+                .into_iter()
+                .filter(|card| !cards.contains(card))
+                .collect(),
+        );
+        self.players[seat].cards = cards.clone();
+        self.emit(GameEvent::CardsDealt {
+            seat: Some(seat),
+            cards,
+        });
+        self.notify_state_changed();
+    }
+
+    /// Puts `cards` on top of the deck so they are dealt next, first card
+    /// first, for scripting the board. Cards already in a hand are skipped.
+    pub fn stack_deck(&mut self, cards: &[Card]) {
+        let in_hand: Vec<&Card> = self.players.iter().flat_map(|p| p.cards.iter()).collect();
+        let stacked: Vec<Card> = cards
+            .iter()
+            .filter(|card| !in_hand.contains(card))
+            .cloned()
+            .collect();
+        self.deck.stack(stacked);
+    }
+
+    /// Takes the events queued since the last call. Events from a previous hand
+    /// that were never drained are discarded when the next hand starts.
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        self.observers.reset_events();
+        std::mem::take(&mut self.events)
+    }
+
+    /// Shuffles, posts blinds and deals hole cards for a new hand.
+    pub fn start_hand(&mut self) {
+        self.start_hand_seeded(thread_rng().gen());
+    }
+
+    /// Like [`PokerGame::start_hand`], but the same seed always deals the
+    /// same cards and gives the bot the same random draws.
+    pub fn start_hand_seeded(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.deck.new_hand(&mut rng);
+        self.bot_rng = StdRng::seed_from_u64(rng.gen());
+        self.community_cards.clear();
+        self.pot = Chips::ZERO;
+        self.current_bet = Chips::ZERO;
+        self.phase = GamePhase::PreFlop;
+        self.hand_complete = false;
+        self.showdown_done = false;
+
+        for player in &mut self.players {
+            player.bet = Chips::ZERO;
+            player.cards.clear();
+            player.mucked_cards.clear();
+            player.last_action = String::new();
+        }
+        self.hand_start_chips = self.players.iter().map(|p| p.chips).collect();
+        self.user_stats.start_hand();
+        self.bot_stats.start_hand();
+        self.last_exploit = None;
+        self.hand_number += 1;
+        self.trap = TrapState::Undecided;
+        self.betting = BettingRound::new(self.players.len());
+        self.street_aggressor = None;
+        self.previous_street_aggressor = None;
+        self.check_raise_plan = None;
+        self.hand_log = HandLog {
+            seed,
+            hand_number: self.hand_number,
+            dealer_position: self.dealer_position,
+            stacks: self.hand_start_chips.clone(),
+            actions: Vec::new(),
+        };
+        self.events.clear();
+        self.observers.reset_events();
+        self.emit(GameEvent::HandStarted {
+            hand_number: self.hand_number,
+            dealer: self.dealer_position,
+        });
+
+        info!(
+            hand = self.hand_number,
+            user_chips = %self.players[0].chips,
+            bot_chips = %self.players[1].chips,
+            "starting hand"
+        );
+
+        debug!(
+            dealer = %self.players[self.dealer_position].name,
+            small_blind = %self.players[self.small_blind_seat()].name,
+            big_blind = %self.players[self.big_blind_seat()].name,
+            "seats assigned"
+        );
+
+        self.post_blinds();
+        self.deal_hole_cards();
+
+        self.current_player = (self.big_blind_seat() + 1) % self.players.len();
+        if self.seat_status(self.current_player) != SeatStatus::Active {
+            self.move_to_next_player();
+        }
+        debug!(
+            player = %self.players[self.current_player].name,
+            pot = %self.pot,
+            current_bet = %self.current_bet,
+            "first to act"
+        );
+        self.notify_state_changed();
+    }
+
+    /// Heads up the dealer posts the small blind, acting first before the
+    /// flop and last after it; with more players it's the seat after the
+    /// dealer.
+    pub fn small_blind_seat(&self) -> usize {
+        if self.players.len() == 2 {
+            self.dealer_position
+        } else {
+            (self.dealer_position + 1) % self.players.len()
+        }
+    }
+
+    pub fn big_blind_seat(&self) -> usize {
+        (self.small_blind_seat() + 1) % self.players.len()
+    }
+
+    fn post_blinds(&mut self) {
+        let sb_player = self.small_blind_seat();
+        let bb_player = self.big_blind_seat();
+
+        let small_blind = self.post_blind(sb_player, self.small_blind, "SB");
+        let big_blind = self.post_blind(bb_player, self.big_blind, "BB");
+        self.current_bet = small_blind.max(big_blind);
+    }
+
+    /// Posts up to `blind` for `seat`; a short stack posts what it has.
+    fn post_blind(&mut self, seat: usize, blind: Chips, label: &str) -> Chips {
+        let player = &mut self.players[seat];
+        let posted = blind.min(player.chips);
+        player.chips -= posted;
+        player.bet = posted;
+        player.last_action = format!("{}: {}", label, posted);
+        debug!(player = %player.name, blind = label, amount = %posted, "posted blind");
+
+        self.pot += posted;
+        self.emit(GameEvent::BlindPosted {
+            seat,
+            amount: posted,
+        });
+        posted
+    }
+
+    fn deal_hole_cards(&mut self) {
+        trace!("dealing hole cards");
+        for i in 0..self.players.len() {
+            if let Some(card) = self.deal_card() {
+                self.players[i].cards.push(card);
+            }
+            if let Some(card) = self.deal_card() {
+                self.players[i].cards.push(card);
+            }
+            self.emit(GameEvent::CardsDealt {
+                seat: Some(i),
+                cards: self.players[i].cards.clone(),
+            });
+            if self.players[i].is_user {
+                debug!(
+                    "user hole cards: {} {} | {} {}",
+                    self.players[i].cards[0].rank,
+                    self.players[i].cards[0].suit,
+                    self.players[i].cards[1].rank,
+                    self.players[i].cards[1].suit
+                );
+            } else {
+                trace!("bot hole cards dealt");
+            }
+        }
+    }
+
+    fn deal_community_cards(&mut self, count: usize) {
+        let mut dealt = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(card) = self.deal_card() {
+                self.community_cards.push(card.clone());
+                dealt.push(card);
+            }
+        }
+        self.emit(GameEvent::CardsDealt {
+            seat: None,
+            cards: dealt,
+        });
+    }
+
+    pub fn next_phase(&mut self) {
+        self.advance_phase();
+        self.notify_state_changed();
+    }
+
+    #[instrument(level = "debug", skip_all, fields(hand = self.hand_number))]
+    fn advance_phase(&mut self) {
+        if self.phase != GamePhase::Showdown && self.live_players() <= 1 {
+            debug!("hand over before showdown");
+            self.phase = GamePhase::Showdown;
+            self.emit(GameEvent::PhaseAdvanced {
+                phase: GamePhase::Showdown,
+            });
+            self.do_showdown();
+            return;
+        }
+        match self.phase {
+            GamePhase::PreFlop => {
+                debug!("dealing the flop");
+                self.deal_community_cards(3);
+                self.phase = GamePhase::Flop;
+            }
+            GamePhase::Flop => {
+                debug!("dealing the turn");
+                self.deal_community_cards(1);
+                self.phase = GamePhase::Turn;
+            }
+            GamePhase::Turn => {
+                debug!("dealing the river");
+                self.deal_community_cards(1);
+                self.phase = GamePhase::River;
+            }
+            GamePhase::River => {
+                debug!("river complete, going to showdown");
+                self.phase = GamePhase::Showdown;
+                self.emit(GameEvent::PhaseAdvanced {
+                    phase: GamePhase::Showdown,
+                });
+                self.do_showdown();
+                return;
+            }
+            GamePhase::Showdown => return,
+        }
+        self.emit(GameEvent::PhaseAdvanced {
+            phase: self.phase.clone(),
+        });
+        self.finish_phase_transition();
+    }
+
+    fn finish_phase_transition(&mut self) {
+        self.user_stats.start_street();
+        self.bot_stats.start_street();
+        self.previous_street_aggressor = self.street_aggressor.take();
+        self.betting = BettingRound::new(self.players.len());
+        self.current_bet = Chips::ZERO;
+        for player in &mut self.players {
+            player.bet = Chips::ZERO;
+        }
+        self.current_player = (self.dealer_position + 1) % self.players.len();
+        if self.seat_status(self.current_player) != SeatStatus::Active {
+            self.move_to_next_player();
+        }
+
+        let community_str: String = self
+            .community_cards
+            .iter()
+            .map(|c| format!("{} {}", c.rank, c.suit))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        debug!(
+            board = %community_str,
+            player = %self.players[self.current_player].name,
+            pot = %self.pot,
+            "street dealt"
+        );
+    }
+
+    pub fn get_phase_name(&self) -> String {
+        match self.phase {
+            GamePhase::PreFlop => "Pre-Flop".to_string(),
+            GamePhase::Flop => "Flop".to_string(),
+            GamePhase::Turn => "Turn".to_string(),
+            GamePhase::River => "River".to_string(),
+            GamePhase::Showdown => "Showdown!".to_string(),
+        }
+    }
+
+    fn get_next_player(&self) -> usize {
+        let seats = self.players.len();
+        (1..=seats)
+            .map(|offset| (self.current_player + offset) % seats)
+            .find(|&seat| self.seat_status(seat) == SeatStatus::Active)
+            .unwrap_or((self.current_player + 1) % seats)
+    }
+
+    pub(crate) fn move_to_next_player(&mut self) {
+        self.current_player = self.get_next_player();
+    }
+
+    pub fn seat_status(&self, seat: usize) -> SeatStatus {
+        let player = &self.players[seat];
+        if player.cards.is_empty() {
+            SeatStatus::Folded
+        } else if player.chips.is_zero() {
+            SeatStatus::AllIn
+        } else {
+            SeatStatus::Active
+        }
+    }
+
+    fn live_players(&self) -> usize {
+        (0..self.players.len())
+            .filter(|&seat| self.seat_status(seat) != SeatStatus::Folded)
+            .count()
+    }
+
+    /// Whether `seat` still has to act before the street can end.
+    pub fn has_option(&self, seat: usize) -> bool {
+        self.seat_status(seat) == SeatStatus::Active
+            && !self.is_betting_round_complete()
+            && (!self.betting.has_acted(seat) || self.players[seat].bet < self.current_bet)
+    }
+
+    /// Applies `action` for the player to act.
+    #[instrument(level = "debug", skip(self), fields(hand = self.hand_number, seat = self.current_player))]
+    pub fn player_action(&mut self, action: Action) -> Result<(), ActionError> {
+        if self.hand_complete || self.phase == GamePhase::Showdown {
+            return Err(ActionError::OutOfTurn);
+        }
+        let actor = self.current_player;
+        let is_user = self.players[actor].is_user;
+        let to_call = self.current_bet.saturating_sub(self.players[actor].bet);
+        let phase = self.phase.clone();
+        let chips_before = self.players[actor].chips;
+        let bet_before = self.current_bet;
+
+        self.apply_player_action(action)?;
+        self.hand_log.actions.push(action);
+        self.emit(GameEvent::PlayerActed {
+            seat: actor,
+            action,
+            amount: chips_before - self.players[actor].chips,
+        });
+        self.betting.record(actor, self.current_bet > bet_before);
+        if matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn) {
+            self.street_aggressor = Some(actor);
+        }
+        let stats = if is_user {
+            &mut self.user_stats
+        } else {
+            &mut self.bot_stats
+        };
+        stats.record_action(action.name(), to_call.as_i32(), &phase);
+        self.check_chip_conservation();
+        self.notify_state_changed();
+        Ok(())
+    }
+
+    /// Applies `action` and deals any streets that are now due, so a headless
+    /// driver only ever submits actions. Interactive frontends that want to
+    /// pause between streets use [`PokerGame::player_action`] instead.
+    pub fn apply(&mut self, action: Action) -> Result<(), ActionError> {
+        self.player_action(action)?;
+        while !self.hand_complete && self.is_betting_round_complete() {
+            self.next_phase();
+        }
+        Ok(())
+    }
+
+    /// Like [`PokerGame::player_action`], but rejects the action unless `seat`
+    /// is the player to act.
+    pub fn seat_action(&mut self, seat: usize, action: Action) -> Result<(), ActionError> {
+        if seat != self.current_player {
+            return Err(ActionError::OutOfTurn);
+        }
+        self.player_action(action)
+    }
+
+    fn apply_player_action(&mut self, action: Action) -> Result<(), ActionError> {
+        let player = &mut self.players[self.current_player];
+
+        match action {
+            Action::Fold => {
+                debug!(player = %player.name, "folds");
+                player.mucked_cards = std::mem::take(&mut player.cards);
+                player.last_action = "Folded".to_string();
+            }
+            Action::Check => {
+                if player.bet < self.current_bet {
+                    return Err(ActionError::IllegalCheck);
+                }
+                debug!(player = %player.name, "checks");
+                player.last_action = "Check".to_string();
+            }
+            Action::Bet(to_bet) | Action::Raise(to_bet) => {
+                if to_bet < self.current_bet + MIN_RAISE {
+                    return Err(ActionError::BelowMinRaise);
+                }
+                let added = to_bet.saturating_sub(player.bet);
+                let Some(remaining) = player.chips.checked_sub(added) else {
+                    return Err(ActionError::InsufficientChips);
+                };
+                player.chips = remaining;
+                player.bet = to_bet;
+                debug!(player = %player.name, to = %to_bet, added = %added, "{}s", action.name());
+                player.last_action = to_bet.to_string();
+                self.current_bet = to_bet;
+                self.pot += added;
+            }
+            Action::Call => {
+                let call_amount = self.current_bet.saturating_sub(player.bet);
+                let Some(remaining) = player.chips.checked_sub(call_amount) else {
+                    return Err(ActionError::InsufficientChips);
+                };
+                player.chips = remaining;
+                player.bet = self.current_bet;
+                debug!(player = %player.name, amount = %call_amount, "calls");
+                player.last_action = format!("Call: {}", call_amount);
+                self.pot += call_amount;
+            }
+            Action::AllIn => {
+                let all_in = player.chips;
+                if all_in.is_zero() {
+                    return Err(ActionError::InsufficientChips);
+                }
+                player.chips = Chips::ZERO;
+                player.bet += all_in;
+                debug!(player = %player.name, amount = %all_in, "goes all-in");
+                player.last_action = format!("All-In: {}", all_in);
+                self.pot += all_in;
+                if player.bet > self.current_bet {
+                    self.current_bet = player.bet;
+                }
+            }
+        }
+        self.move_to_next_player();
+        Ok(())
+    }
+
+    /// Whether the street is over: everyone still able to bet has acted since
+    /// the last raise and matched the current bet. Callers decide how long to
+    /// pause before calling [`PokerGame::next_phase`].
+    pub fn is_betting_round_complete(&self) -> bool {
+        let seats: Vec<(SeatStatus, Chips)> = (0..self.players.len())
+            .map(|seat| {
+                (
+                    self.seat_status(seat),
+                    self.current_bet.saturating_sub(self.players[seat].bet),
+                )
+            })
+            .collect();
+        self.betting.is_complete(&seats)
+    }
+
+    /// Advances to the next street once all bets are matched.
+    pub fn check_phase_complete(&mut self) {
+        if self.is_betting_round_complete() {
+            self.next_phase();
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(hand = self.hand_number))]
+    fn do_showdown(&mut self) {
+        if self.showdown_done {
+            return;
+        }
+        self.showdown_done = true;
+
+        let user = &self.players[0];
+        let bot = &self.players[1];
+
+        if user.cards.len() >= 2 {
+            debug!(
+                "user shows {} {} | {} {}",
+                user.cards[0].rank, user.cards[0].suit, user.cards[1].rank, user.cards[1].suit
+            );
+        } else {
+            debug!("user folded");
+        }
+
+        if !bot.cards.is_empty() && bot.cards.len() >= 2 {
+            debug!(
+                "bot shows {} {} | {} {}",
+                bot.cards[0].rank, bot.cards[0].suit, bot.cards[1].rank, bot.cards[1].suit
+            );
+        } else if bot.cards.is_empty() {
+            debug!("bot folded");
+        } else {
+            debug!("bot hand incomplete");
+        }
+
+        let live: Vec<usize> = (0..self.players.len())
+            .filter(|&seat| !self.players[seat].cards.is_empty())
+            .collect();
+
+        if let [winner] = live[..] {
+            info!(
+                winner = %self.players[winner].name,
+                pot = %self.pot,
+                "pot won uncontested"
+            );
+            self.players[winner].chips += self.pot;
+            self.emit(GameEvent::PotAwarded {
+                seat: winner,
+                amount: self.pot,
+                pot: 0,
+            });
+        } else if live.len() == 2 {
+            let comparison = self.showdown_comparison();
+            debug!(comparison, "hands compared");
+            for (index, pot) in self.pots().into_iter().enumerate() {
+                self.award_pot(index, pot, comparison);
+            }
+        }
+
+        self.pot = Chips::ZERO;
+        self.hand_complete = true;
+        self.check_chip_conservation();
+
+        debug!(
+            user_chips = %self.players[0].chips,
+            bot_chips = %self.players[1].chips,
+            "hand finished"
+        );
+        self.notify_hand_finished();
+    }
+
+    // The user's hand against the bot's: positive if the user's is better.
+    fn showdown_comparison(&self) -> i32 {
+        let user_eval = evaluate_hand(&self.players[0].cards, &self.community_cards);
+        let bot_eval = evaluate_hand(&self.players[1].cards, &self.community_cards);
+        compare_hands(&user_eval, &bot_eval)
+    }
+
+    /// The seats that won the main pot at a contested showdown, both on a
+    /// split; empty if the hand didn't get there.
+    pub fn showdown_winners(&self) -> Vec<usize> {
+        let contested = self.players.iter().all(|player| !player.cards.is_empty());
+        if !self.showdown_done || !contested {
+            return Vec::new();
+        }
+        match self.showdown_comparison() {
+            comparison if comparison > 0 => vec![0],
+            comparison if comparison < 0 => vec![1],
+            _ => vec![0, 1],
+        }
+    }
+
+    /// Gives one pot to its winner at showdown, or splits it. `comparison`
+    /// is the user's hand against the bot's; a pot only one seat is eligible
+    /// for goes back to that seat.
+    fn award_pot(&mut self, index: usize, pot: Pot, comparison: i32) {
+        let winner = match pot.eligible[..] {
+            [only] => Some(only),
+            _ if comparison > 0 => Some(0),
+            _ if comparison < 0 => Some(1),
+            _ => None,
+        };
+        if let Some(seat) = winner {
+            info!(
+                winner = %self.players[seat].name,
+                pot = index,
+                amount = %pot.amount,
+                "pot won at showdown"
+            );
+            self.players[seat].chips += pot.amount;
+            self.emit(GameEvent::PotAwarded {
+                seat,
+                amount: pot.amount,
+                pot: index,
+            });
+            return;
+        }
+
+        let (share, odd_chip) = pot.amount.split(2);
+        info!(pot = index, share = %share, odd_chip = %odd_chip, "pot split");
+        // The odd chip goes to the player out of position.
+        let out_of_position = (self.dealer_position + 1) % self.players.len();
+        for seat in 0..2 {
+            let amount = if seat == out_of_position {
+                share + odd_chip
+            } else {
+                share
+            };
+            self.players[seat].chips += amount;
+            self.emit(GameEvent::PotAwarded {
+                seat,
+                amount,
+                pot: index,
+            });
+        }
+    }
+
+    /// The main pot followed by any side pots, from what each seat has put
+    /// in this hand. A folded seat's chips stay in the pots, but it can't
+    /// win any of them.
+    pub fn pots(&self) -> Vec<Pot> {
+        let seats = 0..self.players.len();
+        let put_in: Vec<Chips> = self
+            .players
+            .iter()
+            .zip(&self.hand_start_chips)
+            .map(|(player, &start)| start.saturating_sub(player.chips))
+            .collect();
+        let live = |seat: &usize| !self.players[*seat].cards.is_empty();
+
+        let mut levels: Vec<Chips> = seats
+            .clone()
+            .filter(live)
+            .map(|seat| put_in[seat])
+            .filter(|amount| !amount.is_zero())
+            .collect();
+        levels.sort();
+        levels.dedup();
+
+        let mut pots = Vec::new();
+        let mut below = Chips::ZERO;
+        for level in levels {
+            pots.push(Pot {
+                amount: put_in
+                    .iter()
+                    .map(|&amount| amount.min(level).saturating_sub(below))
+                    .sum(),
+                eligible: seats
+                    .clone()
+                    .filter(|seat| live(seat) && put_in[*seat] >= level)
+                    .collect(),
+            });
+            below = level;
+        }
+        // A folded seat may have put in more than anyone still live.
+        let above: Chips = put_in
+            .iter()
+            .map(|amount| amount.saturating_sub(below))
+            .sum();
+        if let Some(last) = pots.last_mut() {
+            last.amount += above;
+        }
+
+        if pots.iter().map(|pot| pot.amount).sum::<Chips>() != self.pot {
+            // The stacks were edited mid-hand, as debug scenarios do.
+            return vec![Pot {
+                amount: self.pot,
+                eligible: seats.filter(live).collect(),
+            }];
+        }
+        pots
+    }
+
+    /// What calling costs `seat` and what it can win, or `None` when there
+    /// is nothing to call. A short stack's call is capped at its chips.
+    pub fn pot_odds(&self, seat: usize) -> Option<PotOdds> {
+        let player = &self.players[seat];
+        let call = self
+            .current_bet
+            .saturating_sub(player.bet)
+            .min(player.chips);
+        (!call.is_zero()).then_some(PotOdds {
+            call,
+            pot: self.pot,
+        })
+    }
+
+    pub fn is_user_turn(&self) -> bool {
+        self.players[self.current_player].is_user
+            && !self.hand_complete
+            && self.phase != GamePhase::Showdown
+    }
+
+    pub fn is_bot_turn(&self) -> bool {
+        !self.players[self.current_player].is_user
+            && !self.hand_complete
+            && self.phase != GamePhase::Showdown
+    }
+
+    pub fn get_winner_name(&self) -> String {
+        if self.players[0].chips > self.players[1].chips {
+            "YOU WIN!".to_string()
+        } else if self.players[1].chips > self.players[0].chips {
+            "BOT WINS!".to_string()
+        } else {
+            "TIE GAME!".to_string()
+        }
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.players.iter().any(|p| p.chips.is_zero())
+    }
+
+    /// Chips in the stacks plus the pot. Nothing during a hand should change
+    /// this, so it always matches the stacks the hand started with.
+    pub fn chips_in_play(&self) -> Chips {
+        self.players.iter().map(|p| p.chips).sum::<Chips>() + self.pot
+    }
+
+    fn check_chip_conservation(&self) {
+        let started_with: Chips = self.hand_start_chips.iter().copied().sum();
+        debug_assert_eq!(
+            self.chips_in_play(),
+            started_with,
+            "chips were created or destroyed in hand {}",
+            self.hand_number
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_new_has_correct_initial_state() {
+        let game = PokerGame::new();
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.players[0].chips, STARTING_CHIPS);
+        assert_eq!(game.players[1].chips, STARTING_CHIPS);
+        assert_eq!(game.phase, GamePhase::PreFlop);
+    }
+
+    #[test]
+    fn test_set_hole_cards_keeps_every_card_unique() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        let aces = vec![Card::new("A", "♠", 14), Card::new("A", "♥", 14)];
+        game.set_hole_cards(1, aces.clone());
+        game.set_hole_cards(0, aces.clone());
+
+        assert_eq!(game.players[0].cards, aces);
+        let mut seen: Vec<Card> = std::iter::from_fn(|| game.deal_card()).collect();
+        seen.extend(game.players.iter().flat_map(|p| p.cards.clone()));
+        assert_eq!(seen.len(), 52);
+        assert!(seen
+            .iter()
+            .enumerate()
+            .all(|(i, card)| !seen[i + 1..].contains(card)));
+    }
+
+    #[test]
+    fn test_stack_deck_deals_the_board_in_order() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        game.set_hole_cards(0, vec![Card::new("A", "♠", 14), Card::new("K", "♠", 13)]);
+        game.set_hole_cards(1, vec![Card::new("Q", "♥", 12), Card::new("Q", "♦", 12)]);
+        let board = vec![
+            Card::new("K", "♦", 13),
+            Card::new("7", "♣", 7),
+            Card::new("2", "♥", 2),
+        ];
+        game.stack_deck(&board);
+        assert_eq!(game.cards_remaining(), 48);
+        let dealt: Vec<Card> = (0..3).filter_map(|_| game.deal_card()).collect();
+        assert_eq!(dealt, board);
+    }
+
+    #[test]
+    fn test_showdown_winners_are_the_seats_paid() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        // A wheel for the user loses to the bot's six-high straight.
+        game.set_hole_cards(0, vec![Card::new("A", "♠", 14), Card::new("K", "♠", 13)]);
+        game.set_hole_cards(1, vec![Card::new("6", "♥", 6), Card::new("K", "♦", 13)]);
+        game.stack_deck(&[
+            Card::new("2", "♦", 2),
+            Card::new("3", "♣", 3),
+            Card::new("4", "♥", 4),
+            Card::new("5", "♠", 5),
+            Card::new("9", "♣", 9),
+        ]);
+        assert!(game.showdown_winners().is_empty());
+        while !game.hand_complete {
+            if game.is_betting_round_complete() {
+                game.next_phase();
+                continue;
+            }
+            let seat = game.current_player;
+            let action = if game.players[seat].bet < game.current_bet {
+                Action::Call
+            } else {
+                Action::Check
+            };
+            game.seat_action(seat, action).unwrap();
+        }
+        assert_eq!(game.showdown_winners(), vec![1]);
+        assert!(game.players[1].chips > game.players[0].chips);
+    }
+
+    #[test]
+    fn test_player_new_has_correct_initial_state() {
+        let player = Player::new("Test", true);
+        assert_eq!(player.name, "Test");
+        assert_eq!(player.chips, STARTING_CHIPS);
+        assert_eq!(player.bet, Chips::ZERO);
+        assert!(player.is_user);
+    }
+}
@@ -0,0 +1,141 @@
+use super::events::GameEvent;
+use super::game::PokerGame;
+use super::snapshot::GameSnapshot;
+use std::collections::VecDeque;
+
+/// A game state together with the events that led to it.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub snapshot: GameSnapshot,
+    pub events: Vec<GameEvent>,
+}
+
+/// The most recent game states, for stepping backwards and forwards through
+/// a hand. Recording while stepped back drops the states after the cursor,
+/// the way an undo stack does.
+#[derive(Debug)]
+pub struct History {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+    cursor: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            cursor: 0,
+        }
+    }
+
+    pub fn record(&mut self, game: &PokerGame, events: &[GameEvent]) {
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.cursor + 1);
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(HistoryEntry {
+            snapshot: game.snapshot(),
+            events: events.to_vec(),
+        });
+        self.cursor = self.entries.len() - 1;
+    }
+
+    pub fn back(&mut self) -> Option<&HistoryEntry> {
+        self.cursor = self.cursor.checked_sub(1)?;
+        self.entries.get(self.cursor)
+    }
+
+    pub fn forward(&mut self) -> Option<&HistoryEntry> {
+        if self.cursor + 1 >= self.entries.len() {
+            return None;
+        }
+        self.cursor += 1;
+        self.entries.get(self.cursor)
+    }
+
+    /// Moves the cursor to the oldest state still kept.
+    pub fn first(&mut self) -> Option<&HistoryEntry> {
+        self.cursor = 0;
+        self.entries.front()
+    }
+
+    /// Moves the cursor back to the most recent state.
+    pub fn latest(&mut self) -> Option<&HistoryEntry> {
+        self.cursor = self.entries.len().checked_sub(1)?;
+        self.entries.back()
+    }
+
+    /// True unless the cursor has been stepped back.
+    pub fn is_live(&self) -> bool {
+        self.cursor + 1 >= self.entries.len()
+    }
+
+    /// One-based cursor position.
+    pub fn position(&self) -> usize {
+        if self.entries.is_empty() {
+            0
+        } else {
+            self.cursor + 1
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Action, GamePhase};
+
+    #[test]
+    fn test_history_steps_back_and_forward() {
+        let mut game = PokerGame::new();
+        let mut history = History::new(10);
+        game.start_hand();
+        history.record(&game, &[]);
+        game.apply(Action::Call).unwrap();
+        game.apply(Action::Check).unwrap();
+        history.record(&game, &[]);
+        assert!(history.is_live());
+
+        let preflop = history.back().expect("earlier state").snapshot.clone();
+        assert_eq!(preflop.phase(), &GamePhase::PreFlop);
+        assert!(history.back().is_none());
+        assert!(!history.is_live());
+        assert_eq!(history.position(), 1);
+
+        let flop = history.forward().expect("later state");
+        assert_eq!(flop.snapshot.phase(), &GamePhase::Flop);
+        assert!(history.forward().is_none());
+
+        let first = history.first().expect("oldest state");
+        assert_eq!(first.snapshot.phase(), &GamePhase::PreFlop);
+        assert_eq!(history.position(), 1);
+    }
+
+    #[test]
+    fn test_recording_after_stepping_back_drops_later_states() {
+        let mut game = PokerGame::new();
+        let mut history = History::new(2);
+        for _ in 0..3 {
+            game.start_hand();
+            history.record(&game, &[]);
+        }
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.latest().map(|e| e.snapshot.hand_number()), Some(3));
+
+        history.back();
+        history.record(&game, &[]);
+        assert_eq!(history.len(), 2);
+        assert!(history.is_live());
+    }
+}
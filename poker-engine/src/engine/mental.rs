@@ -0,0 +1,354 @@
+//! Mental poker: a shuffle two players run between them, with no dealer
+//! either has to trust (SRA commutative encryption). Each locks every card
+//! with a secret key and shuffles in turn, so neither knows the order. A
+//! lock is raising to a secret power modulo a shared prime, and locks come
+//! off in any order, so dealing a card is a matter of who takes theirs off:
+//!
+//! - to deal position `k` to one player, the other sends that card with
+//!   their lock taken off ([`MentalKey::unlock`]) and the receiver takes
+//!   off their own, reading the card with [`card_of`];
+//! - a board card is unlocked by both, in the open.
+//!
+//! After the hand both reveal their keys, and each checks the other's
+//! shuffle with [`verify_shuffle`]: a card added, dropped or swapped shows.
+//! Each player's game deals from a [`MentalDeck`], given to
+//! [`crate::PokerGame::deal_from`] in place of the engine's own shuffle.
+//!
+//! Cards stand for squares modulo a safe prime and keys act on that
+//! subgroup only, so a locked card gives away nothing about which card it
+//! is, not even whether it is a square.
+
+use super::deck::{full_deck, Card, DeckSource};
+use num_bigint::BigUint;
+use rand::seq::SliceRandom;
+use rand::{CryptoRng, Rng, RngCore};
+
+// RFC 3526's 2048-bit MODP prime, a safe prime: (P - 1) / 2 is prime too.
+const PRIME_HEX: &str = "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD1\
+    29024E088A67CC74020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B\
+    302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B\
+    0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3D\
+    C2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD96\
+    1C62F356208552BB9ED529077096966D670C354E4ABC9804F1746C08CA18217C\
+    32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9\
+    DE2BCBF6955817183995497CEA956AE515D2261898FA051015728E5A8AACAA68\
+    FFFFFFFFFFFFFFFF";
+
+fn prime() -> BigUint {
+    let digits: String = PRIME_HEX.split_whitespace().collect();
+    BigUint::parse_bytes(digits.as_bytes(), 16).expect("the prime is valid hex")
+}
+
+/// The order of the subgroup the cards live in, (P - 1) / 2.
+fn order() -> BigUint {
+    (prime() - 1u32) >> 1
+}
+
+/// A card under one or both players' locks, or with none left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Locked(BigUint);
+
+impl Locked {
+    /// For sending: lowercase hex.
+    pub fn to_hex(&self) -> String {
+        self.0.to_str_radix(16)
+    }
+
+    /// `None` unless it's in the cards' subgroup: a value outside it
+    /// would leak whether the other player's cards are squares.
+    pub fn from_hex(text: &str) -> Option<Self> {
+        let value = BigUint::parse_bytes(text.trim().as_bytes(), 16)?;
+        let prime = prime();
+        let in_subgroup = value > BigUint::from(1u32)
+            && value < prime
+            && value.modpow(&order(), &prime) == BigUint::from(1u32);
+        in_subgroup.then_some(Locked(value))
+    }
+}
+
+/// One player's secret key: the power a card is raised to in locking it,
+/// and the one that undoes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MentalKey {
+    lock: BigUint,
+    unlock: BigUint,
+}
+
+impl MentalKey {
+    /// A fresh key; anyone who could guess the random numbers could read
+    /// every card, so `rng` has to be fit for cryptography.
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        let order = order();
+        loop {
+            let mut bytes = [0u8; 256];
+            rng.fill(&mut bytes[..]);
+            let lock = BigUint::from_bytes_be(&bytes) % &order;
+            if let Some(key) = Self::from_lock(lock) {
+                return key;
+            }
+        }
+    }
+
+    fn from_lock(lock: BigUint) -> Option<Self> {
+        if lock < BigUint::from(2u32) {
+            return None;
+        }
+        let unlock = lock.modinv(&order())?;
+        Some(MentalKey { lock, unlock })
+    }
+
+    pub fn lock(&self, card: &Locked) -> Locked {
+        Locked(card.0.modpow(&self.lock, &prime()))
+    }
+
+    pub fn unlock(&self, card: &Locked) -> Locked {
+        Locked(card.0.modpow(&self.unlock, &prime()))
+    }
+
+    /// The key as revealed after the hand, for the other player to check
+    /// the shuffle with.
+    pub fn reveal(&self) -> String {
+        self.lock.to_str_radix(16)
+    }
+
+    /// A key the other player revealed; `None` if it can't be one.
+    pub fn from_revealed(text: &str) -> Option<Self> {
+        let lock = BigUint::parse_bytes(text.trim().as_bytes(), 16)?;
+        if lock >= order() {
+            return None;
+        }
+        Self::from_lock(lock)
+    }
+}
+
+/// Every card with no lock on it, in [`full_deck`] order: where the first
+/// player's shuffle starts.
+pub fn open_deck() -> Vec<Locked> {
+    let prime = prime();
+    (2u32..54)
+        .map(|n| Locked(BigUint::from(n).modpow(&BigUint::from(2u32), &prime)))
+        .collect()
+}
+
+/// The card `unlocked` stands for once every lock is off it; `None` if it
+/// stands for none, meaning a lock is still on or someone cheated.
+pub fn card_of(unlocked: &Locked) -> Option<Card> {
+    let index = open_deck().iter().position(|card| card == unlocked)?;
+    full_deck().into_iter().nth(index)
+}
+
+/// One player's turn at the shuffle: locks every card in `deck` with
+/// `key`, then puts them in an order only that player knows.
+pub fn shuffle<R: Rng>(deck: &[Locked], key: &MentalKey, rng: &mut R) -> Vec<Locked> {
+    let mut locked: Vec<Locked> = deck.iter().map(|card| key.lock(card)).collect();
+    locked.shuffle(rng);
+    locked
+}
+
+/// Whether `after` is `before` locked with the revealed `key` and
+/// reordered, with nothing added, dropped or swapped.
+pub fn verify_shuffle(before: &[Locked], after: &[Locked], key: &MentalKey) -> bool {
+    let mut expected = before.to_vec();
+    let mut found: Vec<Locked> = after.iter().map(|card| key.unlock(card)).collect();
+    expected.sort();
+    found.sort();
+    expected == found
+}
+
+/// One hand's deck as one player's game deals it: the shuffled deck under
+/// both locks, and this player's key. A card can be read once the other
+/// player has taken their lock off it, sent in with
+/// [`MentalDeck::unlocked`]. One that can't, the other player's hole card,
+/// deals as a stand-in for [`crate::PokerGame::set_hole_cards`] to replace
+/// once it is shown.
+///
+/// The deck goes, top down, to the first player's two hole cards, the
+/// second's two, then the board. Every game deals its own seat 0 first,
+/// so `first` says which of the two this player is.
+#[derive(Clone, Debug)]
+pub struct MentalDeck {
+    cards: Vec<Locked>,
+    key: MentalKey,
+    first: bool,
+    // The other player's unlocks, by position in the deck.
+    theirs: Vec<Option<Locked>>,
+    dealt: usize,
+    // Cards read or stood in so far, which a stand-in mustn't be.
+    used: Vec<Card>,
+}
+
+impl MentalDeck {
+    pub fn new(cards: Vec<Locked>, key: MentalKey, first: bool) -> Self {
+        let theirs = vec![None; cards.len()];
+        MentalDeck {
+            cards,
+            key,
+            first,
+            theirs,
+            dealt: 0,
+            used: Vec::new(),
+        }
+    }
+
+    /// The card at `position` with this player's lock taken off, for the
+    /// other player.
+    pub fn share(&self, position: usize) -> Option<Locked> {
+        self.cards.get(position).map(|card| self.key.unlock(card))
+    }
+
+    /// The card at `position` with the other player's lock taken off.
+    pub fn unlocked(&mut self, position: usize, card: Locked) {
+        if let Some(slot) = self.theirs.get_mut(position) {
+            *slot = Some(card);
+        }
+    }
+
+    /// Where in the deck the `dealt`th card dealt comes from.
+    fn position(&self, dealt: usize) -> usize {
+        match dealt {
+            0 | 1 if !self.first => dealt + 2,
+            2 | 3 if !self.first => dealt - 2,
+            _ => dealt,
+        }
+    }
+}
+
+impl DeckSource for MentalDeck {
+    // The shuffle is the players' own, run before the hand; each hand
+    // needs a fresh one.
+    fn new_hand(&mut self, _rng: &mut dyn RngCore) {}
+
+    fn deal(&mut self) -> Option<Card> {
+        if self.dealt >= self.cards.len() {
+            return None;
+        }
+        let position = self.position(self.dealt);
+        self.dealt += 1;
+        let card = match &self.theirs[position] {
+            Some(theirs) => card_of(&self.key.unlock(theirs))?,
+            None => full_deck()
+                .into_iter()
+                .find(|card| !self.used.contains(card))?,
+        };
+        self.used.push(card.clone());
+        Some(card)
+    }
+
+    fn remaining(&self) -> usize {
+        self.cards.len() - self.dealt
+    }
+
+    fn clone_box(&self) -> Box<dyn DeckSource> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_two_players_deal_from_a_deck_neither_shuffled_alone() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (alice, bob) = (MentalKey::generate(&mut rng), MentalKey::generate(&mut rng));
+        let open = open_deck();
+        let after_alice = shuffle(&open, &alice, &mut rng);
+        let deck = shuffle(&after_alice, &bob, &mut rng);
+        // Nothing in the shuffled deck reads as a card yet.
+        assert!(deck.iter().all(|card| card_of(card).is_none()));
+
+        // Alice's hole cards: Bob takes his lock off, then she takes hers.
+        let alice_cards: Vec<Card> = deck[..2]
+            .iter()
+            .map(|card| card_of(&alice.unlock(&bob.unlock(card))).unwrap())
+            .collect();
+        // Bob's, the other way round; the order locks come off in is free.
+        let bob_cards: Vec<Card> = deck[2..4]
+            .iter()
+            .map(|card| card_of(&bob.unlock(&alice.unlock(card))).unwrap())
+            .collect();
+        // Alice alone can't read Bob's cards.
+        assert!(deck[2..4]
+            .iter()
+            .all(|card| card_of(&alice.unlock(card)).is_none()));
+        let dealt = [alice_cards, bob_cards].concat();
+        assert!(dealt
+            .iter()
+            .enumerate()
+            .all(|(index, card)| !dealt[..index].contains(card)));
+
+        // The keys come out after the hand, and both shuffles check.
+        let (alice_shown, bob_shown) = (
+            MentalKey::from_revealed(&alice.reveal()).unwrap(),
+            MentalKey::from_revealed(&bob.reveal()).unwrap(),
+        );
+        assert_eq!(alice_shown, alice);
+        assert!(verify_shuffle(&open, &after_alice, &alice_shown));
+        assert!(verify_shuffle(&after_alice, &deck, &bob_shown));
+
+        // A shuffle that slips in a second copy of a card is caught.
+        let mut stacked = deck.clone();
+        stacked[1] = stacked[0].clone();
+        assert!(!verify_shuffle(&after_alice, &stacked, &bob_shown));
+        assert_eq!(Locked::from_hex(&deck[0].to_hex()), Some(deck[0].clone()));
+        // A non-square is outside the cards' subgroup: P - 1 is -1, and -1
+        // isn't a square modulo a safe prime.
+        let minus_one = (prime() - 1u32).to_str_radix(16);
+        assert_eq!(Locked::from_hex(&minus_one), None);
+    }
+
+    #[test]
+    fn test_both_games_deal_the_same_board_from_the_shared_deck() {
+        use crate::engine::{Action, PokerGame};
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let (alice, bob) = (MentalKey::generate(&mut rng), MentalKey::generate(&mut rng));
+        let after_alice = shuffle(&open_deck(), &alice, &mut rng);
+        let deck = shuffle(&after_alice, &bob, &mut rng);
+        let mut alice_deck = MentalDeck::new(deck.clone(), alice, true);
+        let mut bob_deck = MentalDeck::new(deck, bob, false);
+        // Each takes their lock off the other's hole cards and the board.
+        for position in (0..2).chain(4..9) {
+            alice_deck.unlocked(position, bob_deck.share(position).unwrap());
+        }
+        for position in 2..9 {
+            bob_deck.unlocked(position, alice_deck.share(position).unwrap());
+        }
+
+        let (mut alice_game, mut bob_game) = (PokerGame::new(), PokerGame::new());
+        alice_game.deal_from(alice_deck);
+        bob_game.deal_from(bob_deck);
+        for game in [&mut alice_game, &mut bob_game] {
+            game.start_hand_seeded(1);
+            assert_eq!(game.players[0].cards.len(), 2);
+            // The other player's cards are stand-ins.
+            assert_eq!(game.players[1].cards.len(), 2);
+        }
+        assert!(alice_game.players[0]
+            .cards
+            .iter()
+            .all(|card| !bob_game.players[0].cards.contains(card)));
+
+        // Cards placed by hand leave the shared deck as it was.
+        let alice_cards = alice_game.players[0].cards.clone();
+        bob_game.set_hole_cards(1, alice_cards);
+        for game in [&mut alice_game, &mut bob_game] {
+            while game.community_cards.len() < 5 {
+                if game.is_betting_round_complete() {
+                    game.next_phase();
+                    continue;
+                }
+                let seat = game.current_player;
+                let action = if game.players[seat].bet < game.current_bet {
+                    Action::Call
+                } else {
+                    Action::Check
+                };
+                game.seat_action(seat, action).unwrap();
+            }
+        }
+        assert_eq!(alice_game.community_cards, bob_game.community_cards);
+    }
+}
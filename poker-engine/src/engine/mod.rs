@@ -0,0 +1,38 @@
+//! Table mechanics: cards, hand evaluation, statistics and the betting flow.
+
+mod action;
+mod betting;
+mod chips;
+mod deck;
+mod equity;
+mod evaluator;
+mod events;
+mod game;
+mod history;
+#[cfg(feature = "mental-poker")]
+pub mod mental;
+mod observer;
+mod replay;
+mod snapshot;
+mod stats;
+mod visibility;
+
+pub use action::{Action, ActionError};
+pub use betting::{BettingRound, Pot, PotOdds, SeatStatus};
+pub use chips::Chips;
+pub use deck::{full_deck, starting_hand_code, Card, DeckSource, LocalDeck};
+pub use equity::estimate_equity;
+pub use evaluator::{
+    best_five, compare_hands, draws, evaluate_hand, Draw, EvaluatedHand, HandRank,
+};
+pub use events::GameEvent;
+pub use game::{
+    GamePhase, Player, PokerGame, BIG_BLIND, MIN_RAISE, PHASE_TRANSITION_TIME_MS, SMALL_BLIND,
+    STARTING_CHIPS,
+};
+pub use history::{History, HistoryEntry};
+pub use observer::{HandObserver, StateObserver};
+pub use replay::{replay, HandLog, ReplayError};
+pub use snapshot::GameSnapshot;
+pub use stats::PlayerStats;
+pub use visibility::{PrivateState, PublicSeat, PublicState};
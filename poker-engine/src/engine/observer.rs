@@ -0,0 +1,88 @@
+use super::events::GameEvent;
+use super::game::PokerGame;
+
+/// Called after every state change with the events emitted since the previous
+/// call.
+pub type StateObserver = Box<dyn FnMut(&PokerGame, &[GameEvent])>;
+/// Called once the pot has been awarded.
+pub type HandObserver = Box<dyn FnMut(&PokerGame)>;
+
+#[derive(Default)]
+pub(crate) struct Observers {
+    state_changed: Vec<StateObserver>,
+    hand_finished: Vec<HandObserver>,
+    notified_events: usize,
+}
+
+impl Observers {
+    pub(crate) fn reset_events(&mut self) {
+        self.notified_events = 0;
+    }
+}
+
+impl PokerGame {
+    pub fn on_state_changed(&mut self, observer: impl FnMut(&PokerGame, &[GameEvent]) + 'static) {
+        self.observers.state_changed.push(Box::new(observer));
+    }
+
+    pub fn on_hand_finished(&mut self, observer: impl FnMut(&PokerGame) + 'static) {
+        self.observers.hand_finished.push(Box::new(observer));
+    }
+
+    pub(crate) fn notify_state_changed(&mut self) {
+        let mut observers = std::mem::take(&mut self.observers.state_changed);
+        let start = self.observers.notified_events.min(self.events.len());
+        for observer in &mut observers {
+            observer(self, &self.events[start..]);
+        }
+        self.observers.notified_events = self.events.len();
+        observers.append(&mut self.observers.state_changed);
+        self.observers.state_changed = observers;
+    }
+
+    pub(crate) fn notify_hand_finished(&mut self) {
+        let mut observers = std::mem::take(&mut self.observers.hand_finished);
+        for observer in &mut observers {
+            observer(self);
+        }
+        observers.append(&mut self.observers.hand_finished);
+        self.observers.hand_finished = observers;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Action, GameEvent, PokerGame};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_observers_see_each_event_once() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let finished = Rc::new(RefCell::new(0));
+        let mut game = PokerGame::new();
+        let sink = seen.clone();
+        game.on_state_changed(move |_, events| sink.borrow_mut().extend_from_slice(events));
+        let counter = finished.clone();
+        game.on_hand_finished(move |game| {
+            assert!(game.hand_complete);
+            *counter.borrow_mut() += 1;
+        });
+
+        game.start_hand();
+        let after_deal = seen.borrow().len();
+        assert!(matches!(seen.borrow()[0], GameEvent::HandStarted { .. }));
+
+        game.player_action(Action::Fold).unwrap();
+        game.next_phase();
+        let seen = seen.borrow();
+        assert!(matches!(seen[after_deal], GameEvent::PlayerActed { .. }));
+        assert_eq!(
+            seen.iter()
+                .filter(|e| matches!(e, GameEvent::HandStarted { .. }))
+                .count(),
+            1
+        );
+        assert_eq!(*finished.borrow(), 1);
+    }
+}
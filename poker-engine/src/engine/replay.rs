@@ -0,0 +1,100 @@
+use super::action::{Action, ActionError};
+use super::chips::Chips;
+use super::game::PokerGame;
+use std::fmt;
+
+/// What it takes to reproduce a hand exactly: the seed its deck and the bot's
+/// random draws came from, the table as it was dealt, and every accepted
+/// action in order. [`PokerGame`] keeps one for the hand in progress.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HandLog {
+    pub seed: u64,
+    pub hand_number: u32,
+    pub dealer_position: usize,
+    pub stacks: Vec<Chips>,
+    pub actions: Vec<Action>,
+}
+
+/// A logged action the replayed game would not accept, which means the log
+/// does not belong to this engine version or was edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayError {
+    pub index: usize,
+    pub action: Action,
+    pub error: ActionError,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "action {} ({:?}) rejected: {}",
+            self.index + 1,
+            self.action,
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Replays `log` on a fresh table, dealing streets as they come due, and
+/// returns the game as it stood after the last action.
+pub fn replay(log: &HandLog) -> Result<PokerGame, ReplayError> {
+    let mut game = PokerGame::new();
+    for (player, &stack) in game.players.iter_mut().zip(&log.stacks) {
+        player.chips = stack;
+    }
+    game.dealer_position = log.dealer_position;
+    game.hand_number = log.hand_number.saturating_sub(1);
+    game.start_hand_seeded(log.seed);
+
+    for (index, &action) in log.actions.iter().enumerate() {
+        game.apply(action).map_err(|error| ReplayError {
+            index,
+            action,
+            error,
+        })?;
+    }
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_played_hand_replays_bit_for_bit() {
+        let mut game = PokerGame::new();
+        game.dealer_position = 1;
+        game.start_hand_seeded(7);
+        while !game.hand_complete {
+            game.make_bot_move();
+            while !game.hand_complete && game.is_betting_round_complete() {
+                game.next_phase();
+            }
+        }
+
+        let replayed = replay(game.hand_log()).unwrap();
+        assert_eq!(replayed.events(), game.events());
+        assert_eq!(replayed.hand_log(), game.hand_log());
+        assert_eq!(replayed.community_cards, game.community_cards);
+        for (replayed, played) in replayed.players.iter().zip(&game.players) {
+            assert_eq!(replayed.chips, played.chips);
+        }
+    }
+
+    #[test]
+    fn test_rejected_action_reports_its_position() {
+        let log = HandLog {
+            seed: 1,
+            hand_number: 1,
+            dealer_position: 0,
+            stacks: Vec::new(),
+            actions: vec![Action::Call, Action::Raise(Chips::new(25))],
+        };
+        let error = replay(&log).err().unwrap();
+        assert_eq!(error.index, 1);
+        assert_eq!(error.error, ActionError::BelowMinRaise);
+    }
+}
@@ -0,0 +1,129 @@
+use super::betting::BettingRound;
+use super::chips::Chips;
+use super::deck::{Card, DeckSource};
+use super::events::GameEvent;
+use super::game::{GamePhase, Player, PokerGame};
+use super::replay::HandLog;
+use super::stats::PlayerStats;
+use crate::ai::{BotDecision, BotPersonality, BotStyle, StrategyParams, TrapState};
+use rand::rngs::StdRng;
+use std::collections::VecDeque;
+
+// Every field of `PokerGame` except the event queue and the observers, which
+// belong to whoever is watching the game rather than to the game itself.
+macro_rules! game_snapshot {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// A full copy of the game state at one decision point, including the
+        /// undealt deck, so restoring it replays the same cards.
+        #[derive(Clone, Debug)]
+        pub struct GameSnapshot {
+            $($field: $ty,)*
+        }
+
+        impl PokerGame {
+            pub fn snapshot(&self) -> GameSnapshot {
+                GameSnapshot {
+                    $($field: self.$field.clone(),)*
+                }
+            }
+
+            /// Rewinds the game to `snapshot`. Pending events are discarded and
+            /// observers are told to redraw from scratch.
+            pub fn restore(&mut self, snapshot: &GameSnapshot) {
+                $(self.$field = snapshot.$field.clone();)*
+                self.events.clear();
+                self.observers.reset_events();
+                self.emit(GameEvent::StateRestored);
+                self.notify_state_changed();
+            }
+        }
+    };
+}
+
+game_snapshot! {
+    deck: Box<dyn DeckSource>,
+    community_cards: Vec<Card>,
+    players: Vec<Player>,
+    current_player: usize,
+    phase: GamePhase,
+    pot: Chips,
+    current_bet: Chips,
+    dealer_position: usize,
+    small_blind: Chips,
+    big_blind: Chips,
+    hand_complete: bool,
+    showdown_done: bool,
+    bot_params: StrategyParams,
+    hand_start_chips: Vec<Chips>,
+    bot_style: BotStyle,
+    user_stats: PlayerStats,
+    bot_stats: PlayerStats,
+    last_exploit: Option<String>,
+    hand_number: u32,
+    decision_log: VecDeque<BotDecision>,
+    bot_personality: BotPersonality,
+    trap: TrapState,
+    betting: BettingRound,
+    street_aggressor: Option<usize>,
+    previous_street_aggressor: Option<usize>,
+    check_raise_plan: Option<GamePhase>,
+    bot_rng: StdRng,
+    hand_log: HandLog,
+}
+
+impl GameSnapshot {
+    pub fn hand_number(&self) -> u32 {
+        self.hand_number
+    }
+
+    pub fn phase(&self) -> &GamePhase {
+        &self.phase
+    }
+
+    pub fn pot(&self) -> Chips {
+        self.pot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Action, GameEvent, GamePhase, PokerGame};
+
+    #[test]
+    fn test_restore_rewinds_and_replays_the_same_cards() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        let before_call = game.snapshot();
+
+        game.apply(Action::Call).unwrap();
+        game.apply(Action::Check).unwrap();
+        let flop = game.community_cards.clone();
+        assert_eq!(game.phase, GamePhase::Flop);
+
+        game.restore(&before_call);
+        assert_eq!(game.phase, GamePhase::PreFlop);
+        assert_eq!(game.pot, before_call.pot());
+        assert!(game.community_cards.is_empty());
+        assert_eq!(game.drain_events(), vec![GameEvent::StateRestored]);
+
+        game.apply(Action::Call).unwrap();
+        game.apply(Action::Check).unwrap();
+        assert_eq!(game.community_cards, flop);
+    }
+
+    #[test]
+    fn test_snapshot_explores_alternative_lines() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        let decision_point = game.snapshot();
+
+        game.apply(Action::Fold).unwrap();
+        assert!(game.hand_complete);
+        let folded_chips = game.players[0].chips;
+
+        game.restore(&decision_point);
+        game.apply(Action::AllIn).unwrap();
+        assert!(!game.hand_complete);
+        assert_ne!(game.players[0].chips, folded_chips);
+    }
+}
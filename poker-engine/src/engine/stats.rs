@@ -0,0 +1,133 @@
+use super::game::GamePhase;
+
+const EXPLOIT_MIN_SAMPLES: u32 = 5;
+
+/// Running tendencies of one player, fed by every action they take.
+#[derive(Clone, Debug, Default)]
+pub struct PlayerStats {
+    pub hands: u32,
+    pub vpip_hands: u32,
+    pub pfr_hands: u32,
+    pub faced_bets: u32,
+    pub folds_to_bet: u32,
+    pub faced_three_bets: u32,
+    pub folds_to_three_bet: u32,
+    pub aggressive_actions: u32,
+    pub calls: u32,
+    vpip_this_hand: bool,
+    pfr_this_hand: bool,
+    pub(crate) raised_this_street: bool,
+}
+
+impl PlayerStats {
+    pub(crate) fn start_hand(&mut self) {
+        self.hands += 1;
+        self.vpip_this_hand = false;
+        self.pfr_this_hand = false;
+        self.raised_this_street = false;
+    }
+
+    pub(crate) fn start_street(&mut self) {
+        self.raised_this_street = false;
+    }
+
+    pub(crate) fn record_action(&mut self, action: &str, to_call: i32, phase: &GamePhase) {
+        let facing_bet = to_call > 0;
+        if facing_bet {
+            self.faced_bets += 1;
+            if self.raised_this_street {
+                self.faced_three_bets += 1;
+            }
+        }
+
+        match action {
+            "fold" if facing_bet => {
+                self.folds_to_bet += 1;
+                if self.raised_this_street {
+                    self.folds_to_three_bet += 1;
+                }
+            }
+            "call" => self.calls += 1,
+            "bet" | "raise" | "all-in" => {
+                self.aggressive_actions += 1;
+                self.raised_this_street = true;
+                if *phase == GamePhase::PreFlop && !self.pfr_this_hand {
+                    self.pfr_this_hand = true;
+                    self.pfr_hands += 1;
+                }
+            }
+            _ => {}
+        }
+
+        if *phase == GamePhase::PreFlop
+            && matches!(action, "call" | "bet" | "raise" | "all-in")
+            && !self.vpip_this_hand
+        {
+            self.vpip_this_hand = true;
+            self.vpip_hands += 1;
+        }
+    }
+
+    fn percent(count: u32, total: u32) -> Option<u32> {
+        if total < EXPLOIT_MIN_SAMPLES {
+            None
+        } else {
+            Some(count * 100 / total)
+        }
+    }
+
+    pub fn vpip(&self) -> Option<u32> {
+        Self::percent(self.vpip_hands, self.hands)
+    }
+
+    pub fn pfr(&self) -> Option<u32> {
+        Self::percent(self.pfr_hands, self.hands)
+    }
+
+    pub fn fold_to_bet(&self) -> Option<u32> {
+        Self::percent(self.folds_to_bet, self.faced_bets)
+    }
+
+    pub fn fold_to_three_bet(&self) -> Option<u32> {
+        Self::percent(self.folds_to_three_bet, self.faced_three_bets)
+    }
+
+    pub fn aggression_factor(&self) -> Option<u32> {
+        if self.aggressive_actions + self.calls < EXPLOIT_MIN_SAMPLES {
+            None
+        } else {
+            Some(self.aggressive_actions / self.calls.max(1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_track_vpip_and_folds() {
+        let mut stats = PlayerStats::default();
+        for _ in 0..5 {
+            stats.start_hand();
+            stats.record_action("call", 10, &GamePhase::PreFlop);
+            stats.record_action("fold", 40, &GamePhase::Flop);
+        }
+        assert_eq!(stats.vpip(), Some(100));
+        assert_eq!(stats.fold_to_bet(), Some(50));
+        assert_eq!(stats.fold_to_three_bet(), None);
+    }
+
+    #[test]
+    fn test_stats_count_folds_to_three_bet() {
+        let mut stats = PlayerStats::default();
+        for _ in 0..5 {
+            stats.start_hand();
+            stats.record_action("raise", 10, &GamePhase::PreFlop);
+            stats.record_action("fold", 60, &GamePhase::PreFlop);
+        }
+        assert_eq!(stats.fold_to_three_bet(), Some(100));
+        assert_eq!(stats.pfr_hands, 5);
+        assert_eq!(stats.pfr(), Some(100));
+    }
+}
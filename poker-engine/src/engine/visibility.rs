@@ -0,0 +1,139 @@
+//! What each side of a game may know. The instance running a
+//! [`PokerGame`] holds the deck and every hole card; anyone else at the
+//! table is told its [`PublicState`], and a player also its own
+//! [`PrivateState`]. Neither carries the deck, so the cards still to come
+//! never leave the game.
+
+use super::betting::SeatStatus;
+use super::chips::Chips;
+use super::deck::Card;
+use super::game::{GamePhase, PokerGame};
+
+/// The table as everyone sees it, spectators included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicState {
+    pub hand_number: u32,
+    pub phase: GamePhase,
+    pub dealer: usize,
+    /// The seat to act, if anyone is.
+    pub to_act: Option<usize>,
+    pub pot: Chips,
+    pub current_bet: Chips,
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    pub board: Vec<Card>,
+    pub seats: Vec<PublicSeat>,
+    pub hand_complete: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicSeat {
+    pub name: String,
+    pub chips: Chips,
+    pub bet: Chips,
+    /// The stack before this hand's blinds.
+    pub hand_start_chips: Chips,
+    pub status: SeatStatus,
+    pub last_action: String,
+    /// The hole cards, once a showdown has turned them up.
+    pub shown: Option<Vec<Card>>,
+}
+
+/// What only the player in `seat` may know.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrivateState {
+    pub seat: usize,
+    pub hole_cards: Vec<Card>,
+}
+
+impl PokerGame {
+    pub fn public_state(&self) -> PublicState {
+        // Cards are turned up only when a showdown is contested; a hand
+        // won by a fold shows nothing.
+        let shown =
+            self.hand_complete && self.players.iter().all(|player| !player.cards.is_empty());
+        let waiting = self.hand_complete || self.phase == GamePhase::Showdown;
+        PublicState {
+            hand_number: self.hand_number,
+            phase: self.phase.clone(),
+            dealer: self.dealer_position,
+            to_act: (!waiting && !self.is_betting_round_complete()).then_some(self.current_player),
+            pot: self.pot,
+            current_bet: self.current_bet,
+            small_blind: self.small_blind,
+            big_blind: self.big_blind,
+            board: self.community_cards.clone(),
+            seats: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(seat, player)| PublicSeat {
+                    name: player.name.clone(),
+                    chips: player.chips,
+                    bet: player.bet,
+                    hand_start_chips: self.hand_start_chips[seat],
+                    status: self.seat_status(seat),
+                    last_action: player.last_action.clone(),
+                    shown: shown.then(|| player.cards.clone()),
+                })
+                .collect(),
+            hand_complete: self.hand_complete,
+        }
+    }
+
+    pub fn private_state(&self, seat: usize) -> PrivateState {
+        PrivateState {
+            seat,
+            hole_cards: self.players[seat].cards.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{Action, PokerGame};
+
+    #[test]
+    fn test_hole_cards_stay_private_until_a_contested_showdown() {
+        let mut game = PokerGame::new();
+        game.start_hand_seeded(11);
+        let public = game.public_state();
+        assert!(public.seats.iter().all(|seat| seat.shown.is_none()));
+        assert_eq!(public.to_act, Some(game.current_player));
+        assert_eq!(game.private_state(1).hole_cards, game.players[1].cards);
+
+        while !game.hand_complete {
+            if game.is_betting_round_complete() {
+                game.next_phase();
+            } else {
+                let seat = game.current_player;
+                let action = if game.players[seat].bet < game.current_bet {
+                    Action::Call
+                } else {
+                    Action::Check
+                };
+                game.seat_action(seat, action).unwrap();
+            }
+            if !game.hand_complete {
+                let public = game.public_state();
+                assert!(public.seats.iter().all(|seat| seat.shown.is_none()));
+            }
+        }
+        let public = game.public_state();
+        assert_eq!(public.to_act, None);
+        for (seat, player) in public.seats.iter().zip(&game.players) {
+            assert_eq!(seat.shown.as_ref(), Some(&player.cards));
+        }
+
+        // A hand won by a fold turns nothing up.
+        game.start_hand_seeded(12);
+        game.seat_action(game.current_player, Action::Fold).unwrap();
+        game.next_phase();
+        assert!(game.hand_complete);
+        assert!(game
+            .public_state()
+            .seats
+            .iter()
+            .all(|seat| seat.shown.is_none()));
+    }
+}
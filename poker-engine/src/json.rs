@@ -0,0 +1,38 @@
+//! The game as JSON, for the `rest-api` and `json-rpc` features.
+
+use crate::{Card, GamePhase, PokerGame};
+use serde_json::{json, Value};
+
+pub(crate) fn cards(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(Card::to_string).collect()
+}
+
+/// The table as JSON, every seat's cards included.
+pub(crate) fn table(game: &PokerGame) -> Value {
+    let seats: Vec<Value> = game
+        .players
+        .iter()
+        .map(|player| {
+            json!({
+                "name": player.name,
+                "chips": player.chips.amount(),
+                "bet": player.bet.amount(),
+                "cards": cards(&player.cards),
+                "last_action": player.last_action,
+            })
+        })
+        .collect();
+    let waiting = game.hand_complete || game.phase == GamePhase::Showdown;
+    json!({
+        "hand": game.hand_number,
+        "phase": game.phase.name(),
+        "button": game.dealer_position,
+        "to_act": (!waiting && !game.is_betting_round_complete()).then_some(game.current_player),
+        "pot": game.pot.amount(),
+        "current_bet": game.current_bet.amount(),
+        "board": cards(&game.community_cards),
+        "seats": seats,
+        "hand_complete": game.hand_complete,
+        "game_over": game.hand_complete && game.is_game_over(),
+    })
+}
@@ -0,0 +1,237 @@
+//! JSON-RPC 2.0 over a socket, for tests and scripts that drive games end
+//! to end. Each request and response is one line; each connection plays a
+//! game of its own. The methods:
+//!
+//! - `deal {seed?}`: deals the next hand, the same cards for the same seed.
+//! - `act {seat, action, amount?}`: `action` is `fold`, `check`, `call`,
+//!   `bet`, `raise` or `all-in`; bets and raises are to `amount`. Streets
+//!   are dealt as they're due.
+//! - `bot`: the built-in bot plays seat 1's turn.
+//! - `state`: the table, every seat's cards included.
+//! - `new_game`: starts over with fresh stacks.
+//!
+//! All but `new_game` answer with the table. A rejected action is error
+//! [`REJECTED`], with the reason as its message.
+
+use crate::json::table;
+use crate::{Action, Chips, PokerGame};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+pub const DEFAULT_PORT: u16 = 47483;
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The engine turned the request down: an illegal action, or a hand
+/// dealt while one is still being played.
+pub const REJECTED: i64 = -32000;
+// The built-in bot only ever plays seat 1.
+const BOT_SEAT: usize = 1;
+
+struct Error {
+    code: i64,
+    message: String,
+}
+
+impl Error {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Error {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Answers every connection to `listener`, each on a thread of its own.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    info!(address = %listener.local_addr()?, "JSON-RPC listening");
+    for stream in listener.incoming() {
+        let stream = stream?;
+        std::thread::spawn(move || {
+            if let Err(error) = session(stream) {
+                debug!(%error, "JSON-RPC connection closed");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn session(stream: TcpStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut game = PokerGame::new();
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = answer(&mut game, &line) {
+            writeln!(writer, "{}", response)?;
+        }
+    }
+    Ok(())
+}
+
+/// The response to one request line, or `None` for a notification.
+fn answer(game: &mut PokerGame, line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(error) => {
+            return Some(failure(
+                Value::Null,
+                Error::new(PARSE_ERROR, error.to_string()),
+            ))
+        }
+    };
+    let id = request.get("id").cloned();
+    let (Some("2.0"), Some(method)) = (request["jsonrpc"].as_str(), request["method"].as_str())
+    else {
+        let error = Error::new(INVALID_REQUEST, "not a JSON-RPC 2.0 request");
+        return Some(failure(id.unwrap_or_default(), error));
+    };
+    let result = call(game, method, &request["params"]);
+    // Notifications, with no id, are run but not answered.
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => failure(id, error),
+    })
+}
+
+fn failure(id: Value, error: Error) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn call(game: &mut PokerGame, method: &str, params: &Value) -> Result<Value, Error> {
+    match method {
+        "deal" => {
+            if game.hand_number > 0 && !game.hand_complete {
+                return Err(Error::new(REJECTED, "the hand isn't over"));
+            }
+            if game.hand_complete && game.is_game_over() {
+                return Err(Error::new(REJECTED, "the game is over"));
+            }
+            match &params["seed"] {
+                Value::Null => game.start_hand(),
+                seed => {
+                    let seed = seed
+                        .as_u64()
+                        .ok_or_else(|| Error::new(INVALID_PARAMS, "`seed` is not a u64"))?;
+                    game.start_hand_seeded(seed);
+                }
+            }
+        }
+        "act" => {
+            let seat = params["seat"]
+                .as_u64()
+                .ok_or_else(|| Error::new(INVALID_PARAMS, "`seat` is missing"))?;
+            let amount = match &params["amount"] {
+                Value::Null => Chips::ZERO,
+                amount => amount
+                    .as_u64()
+                    .and_then(|amount| u32::try_from(amount).ok())
+                    .map(Chips::new)
+                    .ok_or_else(|| Error::new(INVALID_PARAMS, "`amount` is not a chip count"))?,
+            };
+            let action = params["action"]
+                .as_str()
+                .and_then(|name| Action::from_name(name, amount))
+                .ok_or_else(|| Error::new(INVALID_PARAMS, "unknown `action`"))?;
+            in_hand(game)?;
+            game.seat_action(seat as usize, action)
+                .map_err(|error| Error::new(REJECTED, error.to_string()))?;
+            deal_due_streets(game);
+        }
+        "bot" => {
+            in_hand(game)?;
+            if game.current_player != BOT_SEAT || !game.is_bot_turn() {
+                return Err(Error::new(REJECTED, "it isn't the bot's turn"));
+            }
+            game.make_bot_move();
+            deal_due_streets(game);
+        }
+        "state" => {}
+        "new_game" => {
+            *game = PokerGame::new();
+            return Ok(Value::Null);
+        }
+        _ => {
+            return Err(Error::new(
+                METHOD_NOT_FOUND,
+                format!("no method `{}`", method),
+            ))
+        }
+    }
+    Ok(table(game))
+}
+
+fn in_hand(game: &PokerGame) -> Result<(), Error> {
+    if game.hand_number == 0 || game.hand_complete {
+        return Err(Error::new(REJECTED, "no hand is being played"));
+    }
+    Ok(())
+}
+
+fn deal_due_streets(game: &mut PokerGame) {
+    while !game.hand_complete && game.is_betting_round_complete() {
+        game.next_phase();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask(game: &mut PokerGame, line: &str) -> Value {
+        answer(game, line).unwrap()
+    }
+
+    #[test]
+    fn test_script_deals_a_seeded_hand_and_plays_it() {
+        let mut game = PokerGame::new();
+        let dealt = ask(
+            &mut game,
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "deal", "params": {"seed": 42}}"#,
+        );
+        let table = &dealt["result"];
+        assert_eq!(table["pot"], 30);
+        let mut replayed = PokerGame::new();
+        replayed.start_hand_seeded(42);
+        assert_eq!(
+            table["seats"][0]["cards"],
+            json!(crate::json::cards(&replayed.players[0].cards))
+        );
+
+        let to_act = table["to_act"].as_u64().unwrap();
+        let wrong_seat = format!(
+            r#"{{"jsonrpc": "2.0", "id": 2, "method": "act", "params": {{"seat": {}, "action": "check"}}}}"#,
+            1 - to_act
+        );
+        assert_eq!(ask(&mut game, &wrong_seat)["error"]["code"], REJECTED);
+        let fold = format!(
+            r#"{{"jsonrpc": "2.0", "id": 3, "method": "act", "params": {{"seat": {}, "action": "fold"}}}}"#,
+            to_act
+        );
+        assert_eq!(ask(&mut game, &fold)["result"]["hand_complete"], true);
+
+        assert_eq!(ask(&mut game, "{oops")["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            ask(
+                &mut game,
+                r#"{"jsonrpc": "2.0", "id": "x", "method": "shuffle"}"#
+            )["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            ask(&mut game, r#"{"method": "state"}"#)["error"]["code"],
+            INVALID_REQUEST
+        );
+        assert!(answer(&mut game, r#"{"jsonrpc": "2.0", "method": "state"}"#).is_none());
+    }
+}
@@ -0,0 +1,54 @@
+//! Heads-up Texas Hold'em engine: deck, hand evaluator, betting flow and the
+//! bot opponent, with no UI dependencies. A frontend (or a test, or a
+//! simulation) drives it by submitting actions:
+//!
+//! ```
+//! use poker_engine::{Action, Chips, GamePhase, PokerGame};
+//!
+//! # fn main() -> Result<(), poker_engine::ActionError> {
+//! let mut game = PokerGame::new();
+//! game.start_hand();
+//! assert_eq!(game.pot, Chips::new(30));
+//! assert_eq!(game.players[0].cards.len(), 2);
+//!
+//! game.apply(Action::Call)?;
+//! game.apply(Action::Check)?;
+//! assert_eq!(game.phase, GamePhase::Flop);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The engine logs through [`tracing`]; install a subscriber to see hands,
+//! actions and bot decisions as they happen.
+//!
+//! The `exploit` and `push-fold` cargo features (both on by default) compile
+//! the exploitative bot lines and the Nash push/fold charts. Without them the
+//! bot plays its standard lines in those spots. The `mental-poker` feature
+//! (off by default) adds [`mental`], a shuffle two players run without a
+//! trusted dealer. Also off by default, `rest-api` adds [`rest`], an HTTP
+//! server for watching a game from a dashboard, and `json-rpc` adds
+//! [`json_rpc`], a socket that scripts drive games through.
+
+pub mod ai;
+pub mod engine;
+#[cfg(any(feature = "rest-api", feature = "json-rpc"))]
+mod json;
+#[cfg(feature = "json-rpc")]
+pub mod json_rpc;
+#[cfg(feature = "rest-api")]
+pub mod rest;
+
+pub use ai::{
+    BotDecision, BotMove, BotPersonality, BotStyle, DifficultyController, Exploit, StrategyParams,
+    DEFAULT_BOT_LEVEL, MAX_BOT_LEVEL, MIN_BOT_LEVEL, PUSH_FOLD_THRESHOLD_BB,
+};
+#[cfg(feature = "mental-poker")]
+pub use engine::mental;
+pub use engine::{
+    best_five, compare_hands, draws, estimate_equity, evaluate_hand, full_deck, replay,
+    starting_hand_code, Action, ActionError, BettingRound, Card, Chips, DeckSource, Draw,
+    EvaluatedHand, GameEvent, GamePhase, GameSnapshot, HandLog, HandObserver, HandRank, History,
+    HistoryEntry, LocalDeck, Player, PlayerStats, PokerGame, Pot, PotOdds, PrivateState,
+    PublicSeat, PublicState, ReplayError, SeatStatus, StateObserver, BIG_BLIND, MIN_RAISE,
+    PHASE_TRANSITION_TIME_MS, SMALL_BLIND, STARTING_CHIPS,
+};
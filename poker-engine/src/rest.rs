@@ -0,0 +1,244 @@
+//! A read-only HTTP server publishing a game as JSON, for dashboards and for
+//! keeping an eye on long bot simulations. [`Monitor::attach`] it to a game
+//! and poll:
+//!
+//! - `GET /state`: the table, every seat's cards included.
+//! - `GET /stats`: hands played and each seat's tendencies.
+//! - `GET /hands`: the last [`MAX_HANDS`] finished hands, oldest first;
+//!   `GET /hands/<number>` for one of them.
+//!
+//! Each hand carries its seed and actions, so [`crate::replay`] can deal it
+//! again.
+
+use crate::json::{cards, table};
+use crate::{Action, HandLog, PlayerStats, PokerGame};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info};
+
+pub const DEFAULT_PORT: u16 = 47482;
+pub const MAX_HANDS: usize = 1000;
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+struct Published {
+    state: Value,
+    stats: Value,
+    hands: VecDeque<Value>,
+}
+
+/// The server and what it last heard from the game; clones share both.
+#[derive(Clone)]
+pub struct Monitor {
+    published: Arc<Mutex<Published>>,
+    address: SocketAddr,
+}
+
+impl Monitor {
+    /// Listens at `address` on a thread of its own until the process exits.
+    pub fn serve(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let published = Arc::new(Mutex::new(Published::default()));
+        let serving = published.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(error) = respond(stream, &serving) {
+                    debug!(%error, "REST request failed");
+                }
+            }
+        });
+        info!(%address, "REST API listening");
+        Ok(Monitor { published, address })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Publishes `game` after every change and each hand once it's over.
+    pub fn attach(&self, game: &mut PokerGame) {
+        self.publish(game);
+        let monitor = self.clone();
+        game.on_state_changed(move |game, _| monitor.publish(game));
+        let monitor = self.clone();
+        game.on_hand_finished(move |game| monitor.finish_hand(game));
+    }
+
+    pub fn publish(&self, game: &PokerGame) {
+        let mut published = self.published.lock().unwrap();
+        published.state = table(game);
+        published.stats = stats(game);
+    }
+
+    pub fn finish_hand(&self, game: &PokerGame) {
+        let mut published = self.published.lock().unwrap();
+        if published.hands.len() == MAX_HANDS {
+            published.hands.pop_front();
+        }
+        published.hands.push_back(hand(game));
+        published.stats = stats(game);
+    }
+}
+
+fn tendencies(name: &str, stats: &PlayerStats) -> Value {
+    json!({
+        "name": name,
+        "hands": stats.hands,
+        "vpip": stats.vpip(),
+        "pfr": stats.pfr(),
+        "fold_to_bet": stats.fold_to_bet(),
+        "fold_to_three_bet": stats.fold_to_three_bet(),
+        "aggression_factor": stats.aggression_factor(),
+    })
+}
+
+fn stats(game: &PokerGame) -> Value {
+    json!({
+        "hands": game.hand_number,
+        "seats": [
+            tendencies(&game.players[0].name, &game.user_stats),
+            tendencies(&game.players[1].name, &game.bot_stats),
+        ],
+    })
+}
+
+fn hand(game: &PokerGame) -> Value {
+    let HandLog {
+        seed,
+        hand_number,
+        dealer_position,
+        stacks,
+        actions,
+    } = game.hand_log();
+    let actions: Vec<Value> = actions
+        .iter()
+        .map(|action| {
+            let to = match action {
+                Action::Bet(to) | Action::Raise(to) => Some(to.amount()),
+                _ => None,
+            };
+            json!({ "action": action.name(), "to": to })
+        })
+        .collect();
+    let results: Vec<i64> = game
+        .players
+        .iter()
+        .zip(stacks)
+        .map(|(player, start)| i64::from(player.chips.amount()) - i64::from(start.amount()))
+        .collect();
+    json!({
+        "hand": hand_number,
+        "seed": seed,
+        "button": dealer_position,
+        "stacks": stacks.iter().map(|stack| stack.amount()).collect::<Vec<_>>(),
+        "actions": actions,
+        "board": cards(&game.community_cards),
+        "hole_cards": game
+            .players
+            .iter()
+            .map(|player| cards(if player.cards.is_empty() { &player.mucked_cards } else { &player.cards }))
+            .collect::<Vec<_>>(),
+        "results": results,
+    })
+}
+
+fn respond(stream: TcpStream, published: &Mutex<Published>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // The headers are read so closing doesn't reset the connection.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let mut words = request.split_whitespace();
+    let method = words.next().unwrap_or_default();
+    let path = words.next().unwrap_or("/").split('?').next().unwrap_or("/");
+    let (status, body) = {
+        let published = published.lock().unwrap();
+        match (method, path.trim_end_matches('/')) {
+            ("GET", "/state") => ("200 OK", published.state.clone()),
+            ("GET", "/stats") => ("200 OK", published.stats.clone()),
+            ("GET", "/hands") => ("200 OK", Value::from_iter(published.hands.clone())),
+            ("GET", path) => match path.strip_prefix("/hands/").map(str::parse::<u32>) {
+                Some(Ok(number)) => published
+                    .hands
+                    .iter()
+                    .find(|hand| hand["hand"] == number)
+                    .map_or(
+                        ("404 Not Found", json!({ "error": "no such hand" })),
+                        |hand| ("200 OK", hand.clone()),
+                    ),
+                _ => ("404 Not Found", json!({ "error": "not found" })),
+            },
+            _ => ("405 Method Not Allowed", json!({ "error": "read-only" })),
+        }
+    };
+    let body = body.to_string();
+    write!(
+        &stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nAccess-Control-Allow-Origin: *\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(address: SocketAddr, path: &str) -> (String, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap().to_string();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_monitor_serves_state_stats_and_hands() {
+        let monitor = Monitor::serve("127.0.0.1:0").unwrap();
+        let address = monitor.local_addr();
+        let mut game = PokerGame::new();
+        monitor.attach(&mut game);
+        for _ in 0..3 {
+            game.start_hand();
+            while !game.hand_complete {
+                if game.is_betting_round_complete() {
+                    game.next_phase();
+                } else {
+                    game.make_bot_move();
+                }
+            }
+        }
+
+        let (status, state) = get(address, "/state");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(state["hand"], 3);
+        assert_eq!(get(address, "/stats").1["seats"][1]["hands"], 3);
+        let (_, hands) = get(address, "/hands");
+        let hands = hands.as_array().unwrap();
+        assert_eq!(hands.len(), 3);
+        let net: i64 = hands[1]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| result.as_i64().unwrap())
+            .sum();
+        assert_eq!(net, 0);
+        assert_eq!(get(address, "/hands/2").1, hands[1]);
+        assert_eq!(get(address, "/hands/9").0, "HTTP/1.1 404 Not Found");
+    }
+}
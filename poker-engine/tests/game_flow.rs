@@ -0,0 +1,352 @@
+use poker_engine::{
+    compare_hands, evaluate_hand, Action, ActionError, Card, Chips, GameEvent, GamePhase, HandRank,
+    PokerGame, BIG_BLIND, SMALL_BLIND, STARTING_CHIPS,
+};
+
+fn card(rank: &str, suit: &str, value: i32) -> Card {
+    Card::new(rank, suit, value)
+}
+
+#[test]
+fn start_hand_posts_blinds_and_deals() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+
+    assert_eq!(game.phase, GamePhase::PreFlop);
+    assert_eq!(game.pot, SMALL_BLIND + BIG_BLIND);
+    assert_eq!(game.current_bet, BIG_BLIND);
+    assert!(game.players.iter().all(|p| p.cards.len() == 2));
+    assert!(game.community_cards.is_empty());
+
+    let total: Chips = game.players.iter().map(|p| p.chips).sum::<Chips>() + game.pot;
+    assert_eq!(total, STARTING_CHIPS * 2);
+}
+
+#[test]
+fn short_stacks_post_what_they_have() {
+    let mut game = PokerGame::new();
+    let big_blind_seat = game.big_blind_seat();
+    game.players[big_blind_seat].chips = Chips::new(5);
+    game.start_hand();
+
+    assert_eq!(game.players[big_blind_seat].chips, Chips::ZERO);
+    assert_eq!(game.players[big_blind_seat].bet, Chips::new(5));
+    assert_eq!(game.pot, SMALL_BLIND + Chips::new(5));
+}
+
+#[test]
+fn all_in_small_blind_does_not_get_the_action() {
+    let mut game = PokerGame::new();
+    let small_blind_seat = game.small_blind_seat();
+    game.players[small_blind_seat].chips = Chips::new(5);
+    game.start_hand();
+
+    assert_ne!(game.current_player, small_blind_seat);
+    assert!(game.apply(Action::Check).is_ok());
+    assert!(game.hand_complete);
+}
+
+#[test]
+fn small_blind_acts_first_preflop() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    let small_blind_seat = game.small_blind_seat();
+    assert_eq!(small_blind_seat, game.dealer_position);
+    assert_eq!(game.current_player, small_blind_seat);
+    assert_eq!(game.players[small_blind_seat].bet, SMALL_BLIND);
+    assert_eq!(game.players[game.big_blind_seat()].bet, BIG_BLIND);
+}
+
+#[test]
+fn dealer_acts_last_after_the_flop() {
+    let mut game = PokerGame::new();
+    game.dealer_position = 1;
+    game.start_hand();
+    assert_eq!(game.current_player, 1);
+
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.player_action(Action::Check).is_ok());
+    game.check_phase_complete();
+    assert_eq!(game.phase, GamePhase::Flop);
+    assert_eq!(game.current_player, 0);
+}
+
+#[test]
+fn check_is_rejected_when_facing_a_bet() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    assert!(game.current_bet > game.players[game.current_player].bet);
+    assert_eq!(
+        game.player_action(Action::Check),
+        Err(ActionError::IllegalCheck)
+    );
+}
+
+#[test]
+fn call_then_check_deals_the_flop() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.player_action(Action::Check).is_ok());
+    game.next_phase();
+
+    assert_eq!(game.phase, GamePhase::Flop);
+    assert_eq!(game.community_cards.len(), 3);
+    assert_eq!(game.current_bet, Chips::ZERO);
+}
+
+#[test]
+fn bot_move_passes_the_turn() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    if game.is_user_turn() {
+        assert!(game.player_action(Action::Call).is_ok());
+    }
+    assert!(game.is_bot_turn());
+
+    game.make_bot_move();
+    assert!(!game.is_bot_turn() || game.hand_complete);
+    assert_eq!(game.decision_log.len(), 1);
+}
+
+#[test]
+fn evaluator_ranks_flush_over_straight() {
+    let board = vec![
+        card("Q", "♠", 12),
+        card("J", "♠", 11),
+        card("10", "♥", 10),
+        card("4", "♠", 4),
+        card("2", "♦", 2),
+    ];
+    let flush = evaluate_hand(&[card("A", "♠", 14), card("7", "♠", 7)], &board);
+    let straight = evaluate_hand(&[card("K", "♣", 13), card("9", "♥", 9)], &board);
+
+    assert_eq!(flush.rank, HandRank::Flush);
+    assert_eq!(straight.rank, HandRank::Straight);
+    assert!(compare_hands(&flush, &straight) > 0);
+}
+
+#[test]
+fn check_phase_complete_advances_without_blocking() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    assert!(!game.is_betting_round_complete());
+
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.player_action(Action::Check).is_ok());
+    assert!(game.is_betting_round_complete());
+
+    let started = std::time::Instant::now();
+    game.check_phase_complete();
+    assert!(started.elapsed() < std::time::Duration::from_millis(100));
+    assert_eq!(game.phase, GamePhase::Flop);
+}
+
+#[test]
+fn events_describe_the_hand_in_order() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    let events = game.drain_events();
+
+    assert!(matches!(
+        events[0],
+        GameEvent::HandStarted { hand_number: 1, .. }
+    ));
+    assert_eq!(
+        events[1],
+        GameEvent::BlindPosted {
+            seat: 0,
+            amount: SMALL_BLIND
+        }
+    );
+    assert_eq!(
+        events[2],
+        GameEvent::BlindPosted {
+            seat: 1,
+            amount: BIG_BLIND
+        }
+    );
+    let dealt = events
+        .iter()
+        .filter(|e| matches!(e, GameEvent::CardsDealt { seat: Some(_), cards } if cards.len() == 2))
+        .count();
+    assert_eq!(dealt, 2);
+    assert!(game.drain_events().is_empty());
+
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.player_action(Action::Check).is_ok());
+    game.check_phase_complete();
+    let events = game.drain_events();
+    assert_eq!(
+        events[0],
+        GameEvent::PlayerActed {
+            seat: 0,
+            action: Action::Call,
+            amount: BIG_BLIND - SMALL_BLIND
+        }
+    );
+    assert!(matches!(&events[2], GameEvent::CardsDealt { seat: None, cards } if cards.len() == 3));
+    assert_eq!(
+        events[3],
+        GameEvent::PhaseAdvanced {
+            phase: GamePhase::Flop
+        }
+    );
+}
+
+#[test]
+fn rejected_actions_report_the_reason() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    let actor = game.current_player;
+
+    assert_eq!(
+        game.player_action(Action::Raise(BIG_BLIND + Chips::new(1))),
+        Err(ActionError::BelowMinRaise)
+    );
+    assert_eq!(
+        game.player_action(Action::Raise(STARTING_CHIPS * 2)),
+        Err(ActionError::InsufficientChips)
+    );
+    assert_eq!(
+        game.seat_action(1 - actor, Action::Call),
+        Err(ActionError::OutOfTurn)
+    );
+    assert_eq!(game.current_player, actor);
+    assert!(game
+        .drain_events()
+        .iter()
+        .all(|e| !matches!(e, GameEvent::PlayerActed { .. })));
+}
+
+#[test]
+fn big_blind_gets_its_option_after_a_limp() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    let big_blind = game.big_blind_seat();
+
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(!game.is_betting_round_complete());
+    assert!(game.has_option(big_blind));
+    assert_eq!(game.current_player, big_blind);
+
+    assert!(game.player_action(Action::Raise(BIG_BLIND * 3)).is_ok());
+    assert!(!game.is_betting_round_complete());
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.is_betting_round_complete());
+}
+
+#[test]
+fn postflop_check_does_not_end_the_street() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    assert!(game.player_action(Action::Call).is_ok());
+    assert!(game.player_action(Action::Check).is_ok());
+    game.check_phase_complete();
+
+    assert!(game.player_action(Action::Check).is_ok());
+    assert!(!game.is_betting_round_complete());
+    assert!(game.player_action(Action::Check).is_ok());
+    assert!(game.is_betting_round_complete());
+}
+
+#[test]
+fn fold_ends_the_hand() {
+    let mut game = PokerGame::new();
+    game.start_hand();
+    let folder = game.current_player;
+    let hole_cards = game.players[folder].cards.clone();
+
+    assert!(game.player_action(Action::Fold).is_ok());
+    assert!(game.players[folder].cards.is_empty());
+    assert_eq!(game.players[folder].mucked_cards, hole_cards);
+    assert!(game.is_betting_round_complete());
+    game.check_phase_complete();
+
+    assert!(game.hand_complete);
+    assert_eq!(game.players[1 - folder].chips, STARTING_CHIPS + SMALL_BLIND);
+    assert!(!game.is_game_over());
+}
+
+#[test]
+fn apply_drives_a_hand_to_showdown() -> Result<(), ActionError> {
+    let mut game = PokerGame::new();
+    game.start_hand();
+
+    game.apply(Action::Call)?;
+    game.apply(Action::Check)?;
+    assert_eq!(game.phase, GamePhase::Flop);
+    for _ in 0..3 {
+        game.apply(Action::Check)?;
+        game.apply(Action::Check)?;
+    }
+
+    assert!(game.hand_complete);
+    assert_eq!(game.community_cards.len(), 5);
+    assert_eq!(game.apply(Action::Check), Err(ActionError::OutOfTurn));
+    Ok(())
+}
+
+#[test]
+fn bot_play_conserves_chips() {
+    let mut game = PokerGame::new();
+    let total = game.chips_in_play();
+    for _ in 0..50 {
+        if game.is_game_over() {
+            break;
+        }
+        game.start_hand();
+        while !game.hand_complete {
+            if game.is_betting_round_complete() {
+                game.next_phase();
+            } else {
+                game.make_bot_move();
+            }
+            assert_eq!(game.chips_in_play(), total);
+        }
+        game.dealer_position = (game.dealer_position + 1) % game.players.len();
+    }
+}
+
+#[test]
+fn a_short_all_in_call_leaves_the_excess_in_a_side_pot() -> Result<(), ActionError> {
+    let mut game = PokerGame::new();
+    game.players[0].chips = Chips::new(200);
+    game.dealer_position = 1;
+    game.start_hand();
+    game.set_hole_cards(0, vec![card("A", "♠", 14), card("A", "♥", 14)]);
+    game.set_hole_cards(1, vec![card("7", "♣", 7), card("2", "♦", 2)]);
+    game.stack_deck(&[
+        card("K", "♠", 13),
+        card("9", "♥", 9),
+        card("5", "♣", 5),
+        card("3", "♦", 3),
+        card("J", "♠", 11),
+    ]);
+
+    game.player_action(Action::AllIn)?;
+    game.player_action(Action::AllIn)?;
+    let pots = game.pots();
+    assert_eq!(pots.len(), 2);
+    assert_eq!(
+        (pots[0].amount, &pots[0].eligible[..]),
+        (Chips::new(400), &[0, 1][..])
+    );
+    assert_eq!(
+        (pots[1].amount, &pots[1].eligible[..]),
+        (Chips::new(800), &[1][..])
+    );
+
+    while !game.hand_complete {
+        game.next_phase();
+    }
+    assert_eq!(game.players[0].chips, Chips::new(400));
+    assert_eq!(game.players[1].chips, Chips::new(800));
+    assert!(game.drain_events().contains(&GameEvent::PotAwarded {
+        seat: 1,
+        amount: Chips::new(800),
+        pot: 1,
+    }));
+    Ok(())
+}
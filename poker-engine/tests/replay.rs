@@ -0,0 +1,144 @@
+//! Replays the hand histories in `tests/hands` and compares the final table
+//! with the matching file in `tests/golden`. After an intended behaviour
+//! change, regenerate the golden files with
+//!
+//! `UPDATE_GOLDEN=1 cargo test -p poker-engine --test replay`
+//!
+//! A hand history is a seed, the dealer seat and the actions in order:
+//!
+//! ```text
+//! seed 42
+//! dealer 0
+//! actions call check bet:40 call check check check check
+//! ```
+
+use poker_engine::{replay, Action, Card, Chips, HandLog, STARTING_CHIPS};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn parse_history(text: &str) -> Result<HandLog, String> {
+    let mut history = HandLog {
+        hand_number: 1,
+        stacks: vec![STARTING_CHIPS; 2],
+        ..HandLog::default()
+    };
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "seed" => history.seed = value.parse().map_err(|_| format!("bad seed: {value}"))?,
+            "dealer" => {
+                history.dealer_position =
+                    value.parse().map_err(|_| format!("bad dealer: {value}"))?
+            }
+            "actions" => {
+                for token in value.split_whitespace() {
+                    history.actions.push(parse_action(token)?);
+                }
+            }
+            _ => return Err(format!("unknown line: {line}")),
+        }
+    }
+    Ok(history)
+}
+
+fn parse_action(token: &str) -> Result<Action, String> {
+    let (name, amount) = match token.split_once(':') {
+        Some((name, amount)) => {
+            let amount = amount.parse().map_err(|_| format!("bad amount: {token}"))?;
+            (name, Chips::new(amount))
+        }
+        None => (token, Chips::ZERO),
+    };
+    Action::from_name(name, amount).ok_or_else(|| format!("unknown action: {token}"))
+}
+
+fn cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| format!("{}{}", card.rank, card.suit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn replay_to_text(history: &HandLog) -> Result<String, String> {
+    let game = replay(history).map_err(|error| error.to_string())?;
+
+    let mut out = String::new();
+    writeln!(out, "phase: {}", game.get_phase_name()).unwrap();
+    writeln!(out, "hand complete: {}", game.hand_complete).unwrap();
+    writeln!(out, "board: {}", cards(&game.community_cards)).unwrap();
+    writeln!(out, "pot: {}", game.pot).unwrap();
+    for (seat, player) in game.players.iter().enumerate() {
+        writeln!(
+            out,
+            "seat {}: {} bet {} [{}]",
+            seat,
+            player.chips,
+            player.bet,
+            cards(&player.cards)
+        )
+        .unwrap();
+    }
+    writeln!(out, "events:").unwrap();
+    for event in game.events() {
+        writeln!(out, "  {:?}", event).unwrap();
+    }
+    Ok(out)
+}
+
+fn golden_path(hand: &Path) -> PathBuf {
+    let name = hand.file_stem().expect("hand file name");
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+        .with_extension("golden")
+}
+
+#[test]
+fn recorded_hands_match_golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let hands_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/hands");
+    let mut hands: Vec<PathBuf> = fs::read_dir(&hands_dir)
+        .expect("tests/hands directory")
+        .map(|entry| entry.expect("hand file").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "hand"))
+        .collect();
+    hands.sort();
+    assert!(!hands.is_empty(), "no hand histories in {:?}", hands_dir);
+
+    let mut failures = Vec::new();
+    for hand in &hands {
+        let text = fs::read_to_string(hand).expect("readable hand file");
+        let actual = match parse_history(&text).and_then(|history| replay_to_text(&history)) {
+            Ok(actual) => actual,
+            Err(error) => {
+                failures.push(format!("{}: {}", hand.display(), error));
+                continue;
+            }
+        };
+        let golden = golden_path(hand);
+        if update {
+            fs::write(&golden, &actual).expect("writable golden file");
+            continue;
+        }
+        match fs::read_to_string(&golden) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{} differs from {}\n--- expected\n{}--- actual\n{}",
+                hand.display(),
+                golden.display(),
+                expected,
+                actual
+            )),
+            Err(_) => failures.push(format!(
+                "{} has no golden file; run with UPDATE_GOLDEN=1",
+                hand.display()
+            )),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,161 @@
+#ifndef POKER_FFI_H
+#define POKER_FFI_H
+
+/* Generated by cbindgen from poker-ffi/src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum PkActionKind {
+  PK_ACTION_KIND_FOLD = 0,
+  PK_ACTION_KIND_CHECK = 1,
+  PK_ACTION_KIND_CALL = 2,
+  PK_ACTION_KIND_BET = 3,
+  PK_ACTION_KIND_RAISE = 4,
+  PK_ACTION_KIND_ALL_IN = 5,
+} PkActionKind;
+
+typedef enum PkStatus {
+  PK_STATUS_OK = 0,
+  PK_STATUS_ILLEGAL_CHECK = 1,
+  PK_STATUS_INSUFFICIENT_CHIPS = 2,
+  PK_STATUS_BELOW_MIN_RAISE = 3,
+  PK_STATUS_OUT_OF_TURN = 4,
+  PK_STATUS_INVALID_ARGUMENT = 5,
+} PkStatus;
+
+typedef struct PokerGame PokerGame;
+
+/**
+ * Hand category (0 = high card .. 8 = straight flush) and its primary value,
+ * or `rank == -1` if the input was invalid.
+ */
+typedef struct PkHandValue {
+  int32_t rank;
+  int32_t primary_value;
+} PkHandValue;
+
+/**
+ * A card: `value` 2..=14 (ace high), `suit` 0..=3 for spades, hearts,
+ * diamonds, clubs.
+ */
+typedef struct PkCard {
+  uint8_t value;
+  uint8_t suit;
+} PkCard;
+
+/**
+ * Evaluates the best hand from hole cards plus board.
+ *
+ * # Safety
+ * `hole` and `board` must point to `hole_len` and `board_len` cards.
+ */
+struct PkHandValue pk_evaluate_hand(const struct PkCard *hole,
+                                    size_t hole_len,
+                                    const struct PkCard *board,
+                                    size_t board_len);
+
+/**
+ * Compares two-card hands on a shared board: positive if `hole_a` wins,
+ * negative if `hole_b` wins, 0 for a split. Returns 0 on invalid input.
+ *
+ * # Safety
+ * `hole_a` and `hole_b` must point to 2 cards, `board` to `board_len` cards.
+ */
+int32_t pk_compare_hands(const struct PkCard *hole_a,
+                         const struct PkCard *hole_b,
+                         const struct PkCard *board,
+                         size_t board_len);
+
+/**
+ * Monte Carlo equity of `hole` (2 cards) against a random hand, in 0..=1,
+ * or a negative value on invalid input.
+ *
+ * # Safety
+ * `hole` must point to 2 cards and `board` to `board_len` cards.
+ */
+double pk_estimate_equity(const struct PkCard *hole,
+                          const struct PkCard *board,
+                          size_t board_len,
+                          uint32_t iterations);
+
+PokerGame *pk_game_new(void);
+
+/**
+ * # Safety
+ * `game` must come from [`pk_game_new`] and not be used afterwards.
+ */
+void pk_game_free(PokerGame *game);
+
+/**
+ * # Safety
+ * `game` must be a live pointer from [`pk_game_new`].
+ */
+enum PkStatus pk_game_start_hand(PokerGame *game);
+
+/**
+ * Applies an action for the player to act and deals any streets that are
+ * due. `amount` is the total to bet or raise to and is ignored otherwise.
+ *
+ * # Safety
+ * `game` must be a live pointer from [`pk_game_new`].
+ */
+enum PkStatus pk_game_apply(PokerGame *game, enum PkActionKind kind, int32_t amount);
+
+/**
+ * Lets the built-in bot act for the player to act, then deals any streets
+ * that are due.
+ *
+ * # Safety
+ * `game` must be a live pointer from [`pk_game_new`].
+ */
+enum PkStatus pk_game_bot_move(PokerGame *game);
+
+/**
+ * 0 = pre-flop, 1 = flop, 2 = turn, 3 = river, 4 = showdown; -1 for null.
+ *
+ * # Safety
+ * `game` must be null or a live pointer from [`pk_game_new`].
+ */
+int32_t pk_game_phase(const PokerGame *game);
+
+/**
+ * # Safety
+ * `game` must be null or a live pointer from [`pk_game_new`].
+ */
+int32_t pk_game_pot(const PokerGame *game);
+
+/**
+ * # Safety
+ * `game` must be null or a live pointer from [`pk_game_new`].
+ */
+int32_t pk_game_current_player(const PokerGame *game);
+
+/**
+ * Stack behind `seat`, or -1 for an unknown seat.
+ *
+ * # Safety
+ * `game` must be null or a live pointer from [`pk_game_new`].
+ */
+int32_t pk_game_chips(const PokerGame *game, size_t seat);
+
+/**
+ * # Safety
+ * `game` must be null or a live pointer from [`pk_game_new`].
+ */
+bool pk_game_hand_complete(const PokerGame *game);
+
+/**
+ * Copies up to `capacity` board cards into `out` and returns how many the
+ * board holds.
+ *
+ * # Safety
+ * `game` must be a live pointer from [`pk_game_new`] and `out` must have
+ * room for `capacity` cards.
+ */
+size_t pk_game_board(const PokerGame *game, struct PkCard *out, size_t capacity);
+
+#endif /* POKER_FFI_H */
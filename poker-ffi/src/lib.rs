@@ -0,0 +1,366 @@
+//! C ABI over `poker-engine`. Cards cross the boundary as [`PkCard`] values,
+//! games as opaque pointers owned by the caller until [`pk_game_free`].
+//! The header in `include/poker_ffi.h` is generated with cbindgen.
+
+use poker_engine::{
+    compare_hands, estimate_equity, evaluate_hand, Action, ActionError, Card, Chips, GamePhase,
+    PokerGame,
+};
+use std::slice;
+
+const RANKS: [&str; 13] = [
+    "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+];
+const SUITS: [&str; 4] = ["♠", "♥", "♦", "♣"];
+
+/// A card: `value` 2..=14 (ace high), `suit` 0..=3 for spades, hearts,
+/// diamonds, clubs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct PkCard {
+    pub value: u8,
+    pub suit: u8,
+}
+
+/// Hand category (0 = high card .. 8 = straight flush) and its primary value,
+/// or `rank == -1` if the input was invalid.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct PkHandValue {
+    pub rank: i32,
+    pub primary_value: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PkStatus {
+    Ok = 0,
+    IllegalCheck = 1,
+    InsufficientChips = 2,
+    BelowMinRaise = 3,
+    OutOfTurn = 4,
+    InvalidArgument = 5,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PkActionKind {
+    Fold = 0,
+    Check = 1,
+    Call = 2,
+    Bet = 3,
+    Raise = 4,
+    AllIn = 5,
+}
+
+impl From<ActionError> for PkStatus {
+    fn from(error: ActionError) -> Self {
+        match error {
+            ActionError::IllegalCheck => PkStatus::IllegalCheck,
+            ActionError::InsufficientChips => PkStatus::InsufficientChips,
+            ActionError::BelowMinRaise => PkStatus::BelowMinRaise,
+            ActionError::OutOfTurn => PkStatus::OutOfTurn,
+        }
+    }
+}
+
+fn to_card(card: &PkCard) -> Option<Card> {
+    let rank = RANKS.get(usize::from(card.value).checked_sub(2)?)?;
+    let suit = SUITS.get(usize::from(card.suit))?;
+    Some(Card::new(rank, suit, i32::from(card.value)))
+}
+
+fn from_card(card: &Card) -> PkCard {
+    PkCard {
+        value: card.value as u8,
+        suit: SUITS.iter().position(|s| *s == card.suit).unwrap_or(0) as u8,
+    }
+}
+
+/// # Safety
+/// `cards` must point to `len` readable `PkCard`s (or be null when `len` is 0).
+unsafe fn read_cards(cards: *const PkCard, len: usize) -> Option<Vec<Card>> {
+    if len == 0 {
+        return Some(Vec::new());
+    }
+    if cards.is_null() {
+        return None;
+    }
+    slice::from_raw_parts(cards, len)
+        .iter()
+        .map(to_card)
+        .collect()
+}
+
+/// Evaluates the best hand from hole cards plus board.
+///
+/// # Safety
+/// `hole` and `board` must point to `hole_len` and `board_len` cards.
+#[no_mangle]
+pub unsafe extern "C" fn pk_evaluate_hand(
+    hole: *const PkCard,
+    hole_len: usize,
+    board: *const PkCard,
+    board_len: usize,
+) -> PkHandValue {
+    match (read_cards(hole, hole_len), read_cards(board, board_len)) {
+        (Some(hole), Some(board)) => {
+            let hand = evaluate_hand(&hole, &board);
+            PkHandValue {
+                rank: hand.rank as i32,
+                primary_value: hand.primary_value,
+            }
+        }
+        _ => PkHandValue {
+            rank: -1,
+            primary_value: 0,
+        },
+    }
+}
+
+/// Compares two-card hands on a shared board: positive if `hole_a` wins,
+/// negative if `hole_b` wins, 0 for a split. Returns 0 on invalid input.
+///
+/// # Safety
+/// `hole_a` and `hole_b` must point to 2 cards, `board` to `board_len` cards.
+#[no_mangle]
+pub unsafe extern "C" fn pk_compare_hands(
+    hole_a: *const PkCard,
+    hole_b: *const PkCard,
+    board: *const PkCard,
+    board_len: usize,
+) -> i32 {
+    let (Some(a), Some(b), Some(board)) = (
+        read_cards(hole_a, 2),
+        read_cards(hole_b, 2),
+        read_cards(board, board_len),
+    ) else {
+        return 0;
+    };
+    compare_hands(&evaluate_hand(&a, &board), &evaluate_hand(&b, &board)).signum()
+}
+
+/// Monte Carlo equity of `hole` (2 cards) against a random hand, in 0..=1,
+/// or a negative value on invalid input.
+///
+/// # Safety
+/// `hole` must point to 2 cards and `board` to `board_len` cards.
+#[no_mangle]
+pub unsafe extern "C" fn pk_estimate_equity(
+    hole: *const PkCard,
+    board: *const PkCard,
+    board_len: usize,
+    iterations: u32,
+) -> f64 {
+    match (read_cards(hole, 2), read_cards(board, board_len)) {
+        (Some(hole), Some(board)) if iterations > 0 => {
+            estimate_equity(&hole, &board, iterations, &mut rand::thread_rng())
+        }
+        _ => -1.0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn pk_game_new() -> *mut PokerGame {
+    Box::into_raw(Box::new(PokerGame::new()))
+}
+
+/// # Safety
+/// `game` must come from [`pk_game_new`] and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_free(game: *mut PokerGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// # Safety
+/// `game` must be a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_start_hand(game: *mut PokerGame) -> PkStatus {
+    let Some(game) = game.as_mut() else {
+        return PkStatus::InvalidArgument;
+    };
+    game.start_hand();
+    PkStatus::Ok
+}
+
+/// Applies an action for the player to act and deals any streets that are
+/// due. `amount` is the total to bet or raise to and is ignored otherwise.
+///
+/// # Safety
+/// `game` must be a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_apply(
+    game: *mut PokerGame,
+    kind: PkActionKind,
+    amount: i32,
+) -> PkStatus {
+    let Some(game) = game.as_mut() else {
+        return PkStatus::InvalidArgument;
+    };
+    let action = match kind {
+        PkActionKind::Fold => Action::Fold,
+        PkActionKind::Check => Action::Check,
+        PkActionKind::Call => Action::Call,
+        PkActionKind::Bet => Action::Bet(Chips::saturating_from(amount)),
+        PkActionKind::Raise => Action::Raise(Chips::saturating_from(amount)),
+        PkActionKind::AllIn => Action::AllIn,
+    };
+    match game.apply(action) {
+        Ok(()) => PkStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
+
+/// Lets the built-in bot act for the player to act, then deals any streets
+/// that are due.
+///
+/// # Safety
+/// `game` must be a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_bot_move(game: *mut PokerGame) -> PkStatus {
+    let Some(game) = game.as_mut() else {
+        return PkStatus::InvalidArgument;
+    };
+    if game.hand_complete {
+        return PkStatus::OutOfTurn;
+    }
+    game.make_bot_move();
+    while !game.hand_complete && game.is_betting_round_complete() {
+        game.next_phase();
+    }
+    PkStatus::Ok
+}
+
+/// 0 = pre-flop, 1 = flop, 2 = turn, 3 = river, 4 = showdown; -1 for null.
+///
+/// # Safety
+/// `game` must be null or a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_phase(game: *const PokerGame) -> i32 {
+    match game.as_ref().map(|g| &g.phase) {
+        Some(GamePhase::PreFlop) => 0,
+        Some(GamePhase::Flop) => 1,
+        Some(GamePhase::Turn) => 2,
+        Some(GamePhase::River) => 3,
+        Some(GamePhase::Showdown) => 4,
+        None => -1,
+    }
+}
+
+/// # Safety
+/// `game` must be null or a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_pot(game: *const PokerGame) -> i32 {
+    game.as_ref().map_or(0, |g| g.pot.as_i32())
+}
+
+/// # Safety
+/// `game` must be null or a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_current_player(game: *const PokerGame) -> i32 {
+    game.as_ref().map_or(-1, |g| g.current_player as i32)
+}
+
+/// Stack behind `seat`, or -1 for an unknown seat.
+///
+/// # Safety
+/// `game` must be null or a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_chips(game: *const PokerGame, seat: usize) -> i32 {
+    game.as_ref()
+        .and_then(|g| g.players.get(seat))
+        .map_or(-1, |p| p.chips.as_i32())
+}
+
+/// # Safety
+/// `game` must be null or a live pointer from [`pk_game_new`].
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_hand_complete(game: *const PokerGame) -> bool {
+    game.as_ref().is_some_and(|g| g.hand_complete)
+}
+
+/// Copies up to `capacity` board cards into `out` and returns how many the
+/// board holds.
+///
+/// # Safety
+/// `game` must be a live pointer from [`pk_game_new`] and `out` must have
+/// room for `capacity` cards.
+#[no_mangle]
+pub unsafe extern "C" fn pk_game_board(
+    game: *const PokerGame,
+    out: *mut PkCard,
+    capacity: usize,
+) -> usize {
+    let Some(game) = game.as_ref() else {
+        return 0;
+    };
+    if !out.is_null() {
+        for (i, card) in game.community_cards.iter().take(capacity).enumerate() {
+            *out.add(i) = from_card(card);
+        }
+    }
+    game.community_cards.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(value: u8, suit: u8) -> PkCard {
+        PkCard { value, suit }
+    }
+
+    #[test]
+    fn test_evaluate_and_compare_through_the_abi() {
+        let aces = [card(14, 0), card(14, 1)];
+        let kings = [card(13, 0), card(13, 1)];
+        let board = [card(2, 2), card(7, 3), card(9, 0)];
+        unsafe {
+            let value = pk_evaluate_hand(aces.as_ptr(), 2, board.as_ptr(), 3);
+            assert_eq!(value.rank, 1);
+            assert_eq!(value.primary_value, 14);
+            assert_eq!(
+                pk_compare_hands(aces.as_ptr(), kings.as_ptr(), board.as_ptr(), 3),
+                1
+            );
+            assert_eq!(
+                pk_evaluate_hand([card(1, 0)].as_ptr(), 1, board.as_ptr(), 0).rank,
+                -1
+            );
+            let equity = pk_estimate_equity(aces.as_ptr(), std::ptr::null(), 0, 200);
+            assert!(equity > 0.6);
+        }
+    }
+
+    #[test]
+    fn test_game_lifecycle() {
+        unsafe {
+            let game = pk_game_new();
+            assert_eq!(pk_game_start_hand(game), PkStatus::Ok);
+            assert_eq!(pk_game_pot(game), 30);
+            assert_eq!(
+                pk_game_apply(game, PkActionKind::Check, 0),
+                PkStatus::IllegalCheck
+            );
+            assert_eq!(pk_game_apply(game, PkActionKind::Call, 0), PkStatus::Ok);
+            assert_eq!(pk_game_apply(game, PkActionKind::Check, 0), PkStatus::Ok);
+            assert_eq!(pk_game_phase(game), 1);
+
+            let mut board = [card(0, 0); 5];
+            assert_eq!(pk_game_board(game, board.as_mut_ptr(), board.len()), 3);
+            assert!((2..=14).contains(&board[0].value));
+
+            while !pk_game_hand_complete(game) {
+                assert_eq!(pk_game_bot_move(game), PkStatus::Ok);
+            }
+            assert_eq!(pk_game_chips(game, 0) + pk_game_chips(game, 1), 2000);
+            pk_game_free(game);
+            assert_eq!(
+                pk_game_start_hand(std::ptr::null_mut()),
+                PkStatus::InvalidArgument
+            );
+        }
+    }
+}
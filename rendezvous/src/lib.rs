@@ -0,0 +1,330 @@
+//! A small rendezvous service for playing over the internet. A host opens a
+//! table and is given a short code; a guest asks for that code and the
+//! service splices the two connections together. Names aren't unique, so a
+//! table is only ever found by its code. Both ends only ever connect outwards, so neither needs an open port behind its
+//! router.
+//!
+//! Each side opens with one JSON line, a [`Request`], and is answered with
+//! [`Reply`] lines. After `paired`, every byte is passed straight through.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+pub const DEFAULT_PORT: u16 = 47480;
+// No 0/O or 1/I, which are easy to mix up when read out.
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 6;
+const MAX_LINE: usize = 512;
+// How long a new connection has to say what it wants.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// Every connection takes a thread (a paired one two) and every waiting
+// table a socket, so both are capped.
+const MAX_CONNECTIONS: usize = 256;
+const MAX_TABLES: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Opens a table and waits for a guest.
+    Host { name: String },
+    /// Joins the table with this code.
+    Join { code: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Reply {
+    /// The table is open; guests join it with `code`.
+    Hosting {
+        code: String,
+    },
+    /// The other side is through; what follows is theirs.
+    Paired,
+    Error {
+        message: String,
+    },
+}
+
+/// Reads one line without reading past it, so whatever follows is left
+/// in the stream for the game.
+pub fn read_line(stream: &mut impl Read) -> io::Result<String> {
+    let mut line = Vec::new();
+    let mut byte = [0];
+    loop {
+        if stream.read(&mut byte)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        match byte[0] {
+            b'\n' => break,
+            _ if line.len() >= MAX_LINE => return Err(io::ErrorKind::InvalidData.into()),
+            byte => line.push(byte),
+        }
+    }
+    String::from_utf8(line).map_err(|_| io::ErrorKind::InvalidData.into())
+}
+
+pub fn write_line(stream: &mut impl Write, message: &impl Serialize) -> io::Result<()> {
+    let text = serde_json::to_string(message).map_err(io::Error::other)?;
+    writeln!(stream, "{}", text)
+}
+
+struct Waiting {
+    name: String,
+    // None while the code is being sent to the host, which can't be
+    // paired before it has it.
+    stream: Option<TcpStream>,
+}
+
+/// The tables waiting for a guest, by code.
+#[derive(Clone, Default)]
+pub struct Rendezvous {
+    tables: Arc<Mutex<HashMap<String, Waiting>>>,
+    connections: Arc<AtomicUsize>,
+}
+
+// One of the connections being handled, given back when dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Rendezvous {
+    /// Takes connections on `listener` until it fails, each on a thread.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let mut stream = stream?;
+            if self.connections.fetch_add(1, Ordering::Relaxed) >= MAX_CONNECTIONS {
+                self.connections.fetch_sub(1, Ordering::Relaxed);
+                warn!("too many connections");
+                let message = "The service is busy; try again later".to_string();
+                let _ = stream.set_write_timeout(Some(REQUEST_TIMEOUT));
+                let _ = write_line(&mut stream, &Reply::Error { message });
+                continue;
+            }
+            let slot = Slot(self.connections.clone());
+            let rendezvous = self.clone();
+            std::thread::spawn(move || {
+                let _slot = slot;
+                if let Err(error) = rendezvous.handle(stream) {
+                    debug!(%error, "connection ended");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        let request = read_line(&mut stream)?;
+        let Ok(request) = serde_json::from_str::<Request>(&request) else {
+            let message = "Not a rendezvous request".to_string();
+            return write_line(&mut stream, &Reply::Error { message });
+        };
+        stream.set_read_timeout(None)?;
+        match request {
+            Request::Host { name } => self.open(name, stream),
+            Request::Join { code } => self.pair(&code, stream),
+        }
+    }
+
+    fn open(&self, name: String, mut stream: TcpStream) -> io::Result<()> {
+        let code = {
+            let mut tables = self.lock();
+            tables.retain(|_, waiting| waiting.stream.as_ref().is_none_or(is_open));
+            if tables.len() >= MAX_TABLES {
+                None
+            } else {
+                let code = loop {
+                    let code = new_code();
+                    if !tables.contains_key(&code) {
+                        break code;
+                    }
+                };
+                let waiting = Waiting {
+                    name: name.clone(),
+                    stream: None,
+                };
+                tables.insert(code.clone(), waiting);
+                Some(code)
+            }
+        };
+        let Some(code) = code else {
+            warn!(%name, "too many tables waiting");
+            let message = "Too many tables are waiting; try again later".to_string();
+            return write_line(&mut stream, &Reply::Error { message });
+        };
+        if let Err(error) = write_line(&mut stream, &Reply::Hosting { code: code.clone() }) {
+            self.lock().remove(&code);
+            return Err(error);
+        }
+        info!(%code, %name, "table opened");
+        if let Some(waiting) = self.lock().get_mut(&code) {
+            waiting.stream = Some(stream);
+        }
+        Ok(())
+    }
+
+    fn pair(&self, code: &str, mut guest: TcpStream) -> io::Result<()> {
+        let code = code.trim().to_ascii_uppercase();
+        let host = {
+            let mut tables = self.lock();
+            tables.retain(|_, waiting| waiting.stream.as_ref().is_none_or(is_open));
+            match tables.remove(&code) {
+                Some(Waiting {
+                    name,
+                    stream: Some(stream),
+                }) => Some((name, stream)),
+                // Still being sent its code, so left waiting.
+                Some(waiting) => {
+                    tables.insert(code.clone(), waiting);
+                    None
+                }
+                None => None,
+            }
+        };
+        let Some((name, mut stream)) = host else {
+            let message = format!("No table with the code {}", code);
+            return write_line(&mut guest, &Reply::Error { message });
+        };
+        if let Err(error) = write_line(&mut stream, &Reply::Paired) {
+            warn!(%error, "host left before a guest came");
+            let message = "That table has closed".to_string();
+            return write_line(&mut guest, &Reply::Error { message });
+        }
+        write_line(&mut guest, &Reply::Paired)?;
+        info!(%code, %name, "paired");
+        splice(stream, guest)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Waiting>> {
+        self.tables
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn new_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LEN)
+        .map(|_| CODE_LETTERS[rng.gen_range(0..CODE_LETTERS.len())] as char)
+        .collect()
+}
+
+/// Whether a waiting host is still there: it sends nothing, so a read that
+/// would block means connected and one that ends means gone.
+fn is_open(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let open = match stream.peek(&mut [0]) {
+        Ok(read) => read > 0,
+        Err(error) => error.kind() == io::ErrorKind::WouldBlock,
+    };
+    open && stream.set_nonblocking(false).is_ok()
+}
+
+/// Passes bytes both ways until either side closes.
+fn splice(host: TcpStream, guest: TcpStream) -> io::Result<()> {
+    let (mut host_reader, mut guest_writer) = (host.try_clone()?, guest.try_clone()?);
+    let to_guest = std::thread::spawn(move || {
+        let _ = io::copy(&mut host_reader, &mut guest_writer);
+        let _ = guest_writer.shutdown(Shutdown::Both);
+    });
+    let (mut guest_reader, mut host_writer) = (guest, host);
+    let _ = io::copy(&mut guest_reader, &mut host_writer);
+    let _ = host_writer.shutdown(Shutdown::Both);
+    let _ = to_guest.join();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    fn ask(port: u16, request: &Request) -> (TcpStream, Reply) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write_line(&mut stream, request).unwrap();
+        let reply = serde_json::from_str(&read_line(&mut stream).unwrap()).unwrap();
+        (stream, reply)
+    }
+
+    #[test]
+    fn test_guest_finds_the_host_by_code_and_they_talk_through() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || Rendezvous::default().serve(listener));
+
+        let name = "Amy's table".to_string();
+        let (mut host, Reply::Hosting { code }) = ask(port, &Request::Host { name }) else {
+            panic!("table not opened");
+        };
+        assert_eq!(code.len(), CODE_LEN);
+        let wrong = Request::Join {
+            code: "NOPE".to_string(),
+        };
+        assert!(matches!(ask(port, &wrong).1, Reply::Error { .. }));
+
+        // Names aren't unique, so they find nothing.
+        let by_name = Request::Join {
+            code: "Amy's table".to_string(),
+        };
+        assert!(matches!(ask(port, &by_name).1, Reply::Error { .. }));
+
+        let join = Request::Join {
+            code: code.to_ascii_lowercase(),
+        };
+        let (mut guest, reply) = ask(port, &join);
+        assert_eq!(reply, Reply::Paired);
+        assert_eq!(read_line(&mut host).unwrap(), r#"{"type":"paired"}"#);
+        writeln!(guest, "hello from the guest").unwrap();
+        let mut line = String::new();
+        io::BufReader::new(&host).read_line(&mut line).unwrap();
+        assert_eq!(line, "hello from the guest\n");
+        writeln!(host, "hi").unwrap();
+        assert_eq!(read_line(&mut guest).unwrap(), "hi");
+
+        // The table is taken now.
+        let again = Request::Join { code };
+        assert!(matches!(ask(port, &again).1, Reply::Error { .. }));
+    }
+
+    #[test]
+    fn test_hosts_are_turned_away_once_enough_tables_wait() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let rendezvous = Rendezvous::default();
+        for table in 0..MAX_TABLES {
+            let waiting = Waiting {
+                name: table.to_string(),
+                stream: None,
+            };
+            rendezvous.lock().insert(table.to_string(), waiting);
+        }
+        let serving = rendezvous.clone();
+        std::thread::spawn(move || serving.serve(listener));
+
+        let host = Request::Host {
+            name: "One too many".to_string(),
+        };
+        assert!(matches!(ask(port, &host).1, Reply::Error { .. }));
+        // A table still having its code sent can't be joined yet.
+        let join = Request::Join {
+            code: "0".to_string(),
+        };
+        assert!(matches!(ask(port, &join).1, Reply::Error { .. }));
+
+        rendezvous.lock().remove("0");
+        assert!(matches!(ask(port, &host).1, Reply::Hosting { .. }));
+    }
+}
@@ -0,0 +1,27 @@
+//! Runs the rendezvous service: `poker-rendezvous [address]`, by default on
+//! every interface at port 47480. `POKER_LOG` sets the log filter.
+
+use poker_rendezvous::{Rendezvous, DEFAULT_PORT};
+use std::net::TcpListener;
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+fn main() {
+    let filter = EnvFilter::try_from_env("POKER_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| format!("0.0.0.0:{}", DEFAULT_PORT));
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            error!(%address, %error, "cannot listen");
+            std::process::exit(1);
+        }
+    };
+    info!(%address, "rendezvous listening");
+    if let Err(error) = Rendezvous::default().serve(listener) {
+        error!(%error, "rendezvous stopped");
+        std::process::exit(1);
+    }
+}
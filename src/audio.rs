@@ -0,0 +1,146 @@
+//! Background music. The tracks are short synthesized loops, so no audio files
+//! ship with the game. Built without the `audio` feature, or run on a machine
+//! without a sound device, the player stays silent.
+
+use tracing::warn;
+
+/// Names of the selectable tracks, in the order the settings panel lists them.
+pub const TRACKS: [&str; 3] = ["Lounge", "Saloon", "Midnight"];
+
+// Frequencies in Hz, one per beat; 0.0 is a rest.
+const LOUNGE: &[f32] = &[261.6, 329.6, 392.0, 329.6, 293.7, 349.2, 440.0, 349.2];
+const SALOON: &[f32] = &[392.0, 392.0, 440.0, 392.0, 523.3, 493.9, 0.0, 392.0];
+const MIDNIGHT: &[f32] = &[220.0, 0.0, 261.6, 246.9, 196.0, 0.0, 220.0, 164.8];
+
+/// What the settings panel asks the music to do.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicSettings {
+    pub enabled: bool,
+    pub track: usize,
+    /// 0.0 (silent) to 1.0 (full volume).
+    pub volume: f32,
+}
+
+impl MusicSettings {
+    /// Builds settings from the raw UI values, clamping anything out of range.
+    pub fn from_ui(enabled: bool, track: i32, volume_percent: f32) -> Self {
+        MusicSettings {
+            enabled,
+            track: usize::try_from(track).unwrap_or(0).min(TRACKS.len() - 1),
+            volume: (volume_percent / 100.0).clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+fn track_notes(track: usize) -> &'static [f32] {
+    match track {
+        1 => SALOON,
+        2 => MIDNIGHT,
+        _ => LOUNGE,
+    }
+}
+
+#[cfg(feature = "audio")]
+mod output {
+    use super::{track_notes, MusicSettings};
+    use rodio::source::{self, SineWave, Source};
+    use rodio::{OutputStream, OutputStreamHandle, Sink};
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    const NOTE_MS: u64 = 320;
+    const NOTE_AMPLITUDE: f32 = 0.15;
+
+    pub struct Output {
+        // Dropping the stream stops all sound, so it lives as long as the player.
+        _stream: OutputStream,
+        handle: OutputStreamHandle,
+        playing: RefCell<Option<(usize, Sink)>>,
+    }
+
+    impl Output {
+        pub fn open() -> Result<Self, String> {
+            let (stream, handle) = OutputStream::try_default().map_err(|e| e.to_string())?;
+            Ok(Output {
+                _stream: stream,
+                handle,
+                playing: RefCell::new(None),
+            })
+        }
+
+        pub fn apply(&self, settings: &MusicSettings) -> Result<(), String> {
+            let mut playing = self.playing.borrow_mut();
+            if !settings.enabled {
+                *playing = None;
+                return Ok(());
+            }
+            if playing.as_ref().map(|(track, _)| *track) != Some(settings.track) {
+                let sink = Sink::try_new(&self.handle).map_err(|e| e.to_string())?;
+                sink.append(looped_track(settings.track));
+                *playing = Some((settings.track, sink));
+            }
+            if let Some((_, sink)) = playing.as_ref() {
+                sink.set_volume(settings.volume);
+            }
+            Ok(())
+        }
+    }
+
+    fn looped_track(track: usize) -> impl Source<Item = f32> + Send {
+        let notes: Vec<_> = track_notes(track)
+            .iter()
+            .map(|&frequency| {
+                let amplitude = if frequency > 0.0 { NOTE_AMPLITUDE } else { 0.0 };
+                SineWave::new(frequency.max(1.0))
+                    .take_duration(Duration::from_millis(NOTE_MS))
+                    .amplify(amplitude)
+            })
+            .collect();
+        source::from_iter(notes).buffered().repeat_infinite()
+    }
+}
+
+/// Plays the selected track on a loop at the music volume.
+pub struct MusicPlayer {
+    #[cfg(feature = "audio")]
+    output: Option<output::Output>,
+}
+
+impl MusicPlayer {
+    pub fn open() -> Self {
+        MusicPlayer {
+            #[cfg(feature = "audio")]
+            output: output::Output::open()
+                .map_err(|error| warn!(%error, "no audio output, music disabled"))
+                .ok(),
+        }
+    }
+
+    pub fn apply(&self, settings: &MusicSettings) {
+        #[cfg(feature = "audio")]
+        if let Some(output) = &self.output {
+            if let Err(error) = output.apply(settings) {
+                warn!(%error, "cannot play music");
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        if settings.enabled {
+            warn!("built without the `audio` feature, music disabled");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_ui_are_clamped() {
+        let settings = MusicSettings::from_ui(true, 7, 150.0);
+        assert_eq!(settings.track, TRACKS.len() - 1);
+        assert_eq!(settings.volume, 1.0);
+        assert_eq!(MusicSettings::from_ui(false, -1, 40.0).track, 0);
+        assert!((0..TRACKS.len()).all(|track| !track_notes(track).is_empty()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Hands starred during play, kept in the profile's directory as the logs
+//! that replay them, one per line: hand number, seed, button, stacks and
+//! actions, e.g. `12 8812734 0 1000/1000 call,check,raise:40,fold`.
+
+use crate::profiles;
+use poker_engine::{Action, Chips, HandLog};
+use std::io;
+use tracing::warn;
+
+const BOOKMARKS_FILE: &str = "bookmarks.txt";
+
+/// `profile`'s bookmarked hands, oldest first. Unreadable lines are skipped.
+pub fn load(profile: &str) -> Vec<HandLog> {
+    let Some(path) = profiles::path(profile, BOOKMARKS_FILE) else {
+        return Vec::new();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => text.lines().filter_map(parse_line).collect(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => {
+            warn!(path = %path.display(), %error, "cannot read bookmarks");
+            Vec::new()
+        }
+    }
+}
+
+pub fn save(profile: &str, logs: &[HandLog]) -> io::Result<()> {
+    let path = profiles::path(profile, BOOKMARKS_FILE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text: String = logs.iter().map(|log| to_line(log) + "\n").collect();
+    std::fs::write(&path, text)
+}
+
+/// True if `a` and `b` are logs of the same dealt hand.
+pub fn same_hand(a: &HandLog, b: &HandLog) -> bool {
+    a.hand_number == b.hand_number && a.seed == b.seed
+}
+
+pub fn to_line(log: &HandLog) -> String {
+    let stacks: Vec<String> = log
+        .stacks
+        .iter()
+        .map(|stack| stack.amount().to_string())
+        .collect();
+    let actions: Vec<String> = log
+        .actions
+        .iter()
+        .map(|action| match action {
+            Action::Bet(amount) | Action::Raise(amount) => {
+                format!("{}:{}", action.name(), amount.amount())
+            }
+            _ => action.name().to_string(),
+        })
+        .collect();
+    format!(
+        "{} {} {} {} {}",
+        log.hand_number,
+        log.seed,
+        log.dealer_position,
+        stacks.join("/"),
+        actions.join(",")
+    )
+}
+
+pub fn parse_line(line: &str) -> Option<HandLog> {
+    let mut fields = line.split_whitespace();
+    let hand_number = fields.next()?.parse().ok()?;
+    let seed = fields.next()?.parse().ok()?;
+    let dealer_position = fields.next()?.parse().ok()?;
+    let stacks = fields
+        .next()?
+        .split('/')
+        .map(|stack| stack.parse().ok().map(Chips::new))
+        .collect::<Option<Vec<_>>>()?;
+    // A hand folded before anyone acted has no actions at all.
+    let actions = match fields.next() {
+        Some(actions) => actions
+            .split(',')
+            .map(|action| {
+                let (name, amount) = action.split_once(':').unwrap_or((action, "0"));
+                Action::from_name(name, Chips::new(amount.parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()?,
+        None => Vec::new(),
+    };
+    Some(HandLog {
+        seed,
+        hand_number,
+        dealer_position,
+        stacks,
+        actions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bookmark_lines_round_trip() {
+        let log = HandLog {
+            seed: 8812734,
+            hand_number: 12,
+            dealer_position: 1,
+            stacks: vec![Chips::new(1000), Chips::new(980)],
+            actions: vec![Action::Call, Action::Raise(Chips::new(40)), Action::Fold],
+        };
+        let line = to_line(&log);
+        assert_eq!(line, "12 8812734 1 1000/980 call,raise:40,fold");
+        assert_eq!(parse_line(&line), Some(log));
+
+        let unplayed = parse_line("3 5 0 1000/1000").unwrap();
+        assert!(unplayed.actions.is_empty());
+        assert_eq!(parse_line("3 5 0 1000/1000 call,shove"), None);
+        assert_eq!(parse_line("three 5 0 1000/1000"), None);
+    }
+}
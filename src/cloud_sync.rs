@@ -0,0 +1,371 @@
+//! Opt-in cloud sync, so a profile's hands, stats, bookmarks and opponent
+//! notes follow the user from machine to machine. The server is any HTTPS
+//! endpoint keeping one JSON document per profile behind a bearer token:
+//!
+//! - `GET <server>/profiles/<name>` answers the document and its `ETag`, or
+//!   404 before the first upload.
+//! - `PUT <server>/profiles/<name>` replaces it if its `If-Match` still
+//!   holds (`If-None-Match: *` for the first upload), answering the new
+//!   `ETag`, and 412 if another machine got there first.
+//!
+//! Changes on both sides are kept. Each side is compared with the copy
+//! both had after the last sync, kept in the profile's `sync.json`: hands
+//! are only ever added, a bookmark removed on either side stays removed,
+//! and a note changed on both keeps this machine's. A 412 means download,
+//! merge and try again. Built without the `cloud-sync` feature, a sync
+//! fails as unsupported.
+
+use crate::bookmarks;
+use crate::hand_db::{HandDb, StoredHand};
+use crate::notes::{Notes, OpponentNote};
+use crate::profiles;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use tracing::warn;
+
+const STATE_FILE: &str = "sync.json";
+
+/// What's synced of one profile.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    pub hands: Vec<StoredHand>,
+    /// Bookmarked hands as [`bookmarks`] writes them.
+    pub bookmarks: Vec<String>,
+    pub notes: BTreeMap<String, OpponentNote>,
+}
+
+impl Bundle {
+    /// `profile`'s data on this machine.
+    pub fn local(profile: &str, db: &HandDb) -> Self {
+        Bundle {
+            hands: db.export(profile),
+            bookmarks: bookmarks::load(profile)
+                .iter()
+                .map(bookmarks::to_line)
+                .collect(),
+            notes: Notes::load(profile)
+                .iter()
+                .map(|(opponent, note)| (opponent.to_string(), note.clone()))
+                .collect(),
+        }
+    }
+
+    /// Makes this `profile`'s data on this machine.
+    pub fn apply(&self, profile: &str, db: &HandDb) -> io::Result<()> {
+        db.import(profile, &self.hands);
+        let logs: Vec<_> = self
+            .bookmarks
+            .iter()
+            .filter_map(|line| bookmarks::parse_line(line))
+            .collect();
+        bookmarks::save(profile, &logs)?;
+        let mut notes = Notes::default();
+        for (opponent, note) in &self.notes {
+            notes.set(opponent, &note.color, &note.text);
+        }
+        notes.save(profile)
+    }
+}
+
+/// Where the last sync left off: the server's version and what both sides
+/// held then.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    pub etag: Option<String>,
+    pub base: Bundle,
+}
+
+impl SyncState {
+    /// `profile`'s state; a fresh one if it has never synced.
+    pub fn load(profile: &str) -> Self {
+        let Some(path) = profiles::path(profile, STATE_FILE) else {
+            return SyncState::default();
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|error| {
+                warn!(path = %path.display(), %error, "cannot read the sync state");
+                SyncState::default()
+            }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => SyncState::default(),
+            Err(error) => {
+                warn!(path = %path.display(), %error, "cannot read the sync state");
+                SyncState::default()
+            }
+        }
+    }
+
+    pub fn save(&self, profile: &str) -> io::Result<()> {
+        let path = profiles::path(profile, STATE_FILE)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+    }
+}
+
+/// Both sides' changes since `base`; see the module docs for who wins.
+pub fn merge(base: &Bundle, local: &Bundle, remote: &Bundle) -> Bundle {
+    let mut hands = local.hands.clone();
+    for hand in &remote.hands {
+        let known = hands
+            .iter()
+            .any(|kept| kept.played_at == hand.played_at && kept.log == hand.log);
+        if !known {
+            hands.push(hand.clone());
+        }
+    }
+    hands.sort_by_key(|hand| hand.played_at);
+
+    let kept = |line: &String, other: &Bundle| {
+        other.bookmarks.contains(line) || !base.bookmarks.contains(line)
+    };
+    let mut bookmarks: Vec<String> = local
+        .bookmarks
+        .iter()
+        .filter(|line| kept(line, remote))
+        .cloned()
+        .collect();
+    bookmarks.extend(
+        remote
+            .bookmarks
+            .iter()
+            .filter(|line| !local.bookmarks.contains(line) && !base.bookmarks.contains(line))
+            .cloned(),
+    );
+
+    let mut notes = BTreeMap::new();
+    for opponent in local.notes.keys().chain(remote.notes.keys()) {
+        let ours = local.notes.get(opponent);
+        let theirs = remote.notes.get(opponent);
+        let note = if ours == base.notes.get(opponent) {
+            theirs
+        } else {
+            ours
+        };
+        if let Some(note) = note {
+            notes.insert(opponent.clone(), note.clone());
+        }
+    }
+
+    Bundle {
+        hands,
+        bookmarks,
+        notes,
+    }
+}
+
+/// Merges `local` with what `server` holds for `profile` and uploads the
+/// result, which is returned with its new `ETag`.
+#[cfg(feature = "cloud-sync")]
+pub fn exchange(
+    server: &str,
+    token: &str,
+    profile: &str,
+    state: &SyncState,
+    local: &Bundle,
+) -> io::Result<(Bundle, Option<String>)> {
+    http::exchange(server, token, profile, state, local)
+}
+
+#[cfg(not(feature = "cloud-sync"))]
+pub fn exchange(
+    _server: &str,
+    _token: &str,
+    _profile: &str,
+    _state: &SyncState,
+    _local: &Bundle,
+) -> io::Result<(Bundle, Option<String>)> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(feature = "cloud-sync")]
+mod http {
+    use super::{merge, Bundle, SyncState};
+    use std::io;
+    use std::time::Duration;
+    use tracing::{debug, info};
+
+    const TIMEOUT: Duration = Duration::from_secs(20);
+    // Uploads lost to another machine before giving up.
+    const MAX_ATTEMPTS: usize = 3;
+
+    enum Upload {
+        Saved(Option<String>),
+        Conflict,
+    }
+
+    pub fn exchange(
+        server: &str,
+        token: &str,
+        profile: &str,
+        state: &SyncState,
+        local: &Bundle,
+    ) -> io::Result<(Bundle, Option<String>)> {
+        let server = server.trim().trim_end_matches('/');
+        // The token must not cross the network in the clear.
+        let local_only = ["http://localhost", "http://127.0.0.1"]
+            .iter()
+            .any(|prefix| server.starts_with(prefix));
+        if !server.starts_with("https://") && !local_only {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the sync server must be an https:// address",
+            ));
+        }
+        let url = format!("{}/profiles/{}", server, encode(profile));
+        for attempt in 1..=MAX_ATTEMPTS {
+            let (remote, etag) = match download(&url, token)? {
+                Some((remote, etag)) => (remote, etag),
+                // Nothing on the server, even if there was: nothing there
+                // counts as removed.
+                None => (Bundle::default(), None),
+            };
+            let base = if etag.is_some() {
+                &state.base
+            } else {
+                &Bundle::default()
+            };
+            let merged = merge(base, local, &remote);
+            match upload(&url, token, &merged, etag.as_deref())? {
+                Upload::Saved(etag) => {
+                    info!(profile, hands = merged.hands.len(), "profile synced");
+                    return Ok((merged, etag));
+                }
+                Upload::Conflict => debug!(attempt, "the profile changed on the server"),
+            }
+        }
+        Err(io::Error::other(
+            "the profile kept changing on the server; try again",
+        ))
+    }
+
+    fn download(url: &str, token: &str) -> io::Result<Option<(Bundle, Option<String>)>> {
+        let response = ureq::get(url)
+            .timeout(TIMEOUT)
+            .set("Authorization", &format!("Bearer {}", token))
+            .call();
+        match response {
+            Ok(response) => {
+                let etag = response.header("ETag").map(String::from);
+                let bundle = serde_json::from_reader(response.into_reader())?;
+                Ok(Some((bundle, etag)))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(error) => Err(failed(error)),
+        }
+    }
+
+    fn upload(url: &str, token: &str, bundle: &Bundle, etag: Option<&str>) -> io::Result<Upload> {
+        let request = ureq::put(url)
+            .timeout(TIMEOUT)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", "application/json");
+        let request = match etag {
+            Some(etag) => request.set("If-Match", etag),
+            None => request.set("If-None-Match", "*"),
+        };
+        match request.send_string(&serde_json::to_string(bundle)?) {
+            Ok(response) => Ok(Upload::Saved(response.header("ETag").map(String::from))),
+            Err(ureq::Error::Status(412, _)) => Ok(Upload::Conflict),
+            Err(error) => Err(failed(error)),
+        }
+    }
+
+    fn failed(error: ureq::Error) -> io::Error {
+        match error {
+            ureq::Error::Status(401 | 403, _) => io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the server turned the token down",
+            ),
+            ureq::Error::Status(status, _) => {
+                io::Error::other(format!("the server answered {}", status))
+            }
+            error => io::Error::other(error),
+        }
+    }
+
+    // Profile names are safe as directory names, not always in a path.
+    fn encode(name: &str) -> String {
+        name.bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    (byte as char).to_string()
+                }
+                byte => format!("%{:02X}", byte),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(text: &str) -> OpponentNote {
+        OpponentNote {
+            color: "None".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn hand(played_at: i64) -> StoredHand {
+        StoredHand {
+            opponent: "Shark".to_string(),
+            mode: "Cash".to_string(),
+            played_at,
+            hand_number: 1,
+            log: format!("1 {} 0 1000/1000 fold", played_at),
+            board: String::new(),
+            big_blind: 20,
+            net: -10,
+            seats: [[1, 0, 0, 0, 0, 0, 0, 0, 0]; 2],
+        }
+    }
+
+    fn bundle(hands: &[i64], bookmarks: &[&str], notes: &[(&str, &str)]) -> Bundle {
+        Bundle {
+            hands: hands.iter().map(|&at| hand(at)).collect(),
+            bookmarks: bookmarks.iter().map(|line| line.to_string()).collect(),
+            notes: notes
+                .iter()
+                .map(|(opponent, text)| (opponent.to_string(), note(text)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_both_sides_changes() {
+        let base = bundle(
+            &[1],
+            &["a", "b"],
+            &[("Rock", "tight"), ("Shark", "sharp"), ("Maniac", "wild")],
+        );
+        // Here: a hand played, bookmark b removed and c added, Rock's note
+        // edited and Maniac's removed.
+        let local = bundle(
+            &[1, 3],
+            &["a", "c"],
+            &[("Rock", "very tight"), ("Shark", "sharp")],
+        );
+        // There: another hand, bookmark d added, Rock's and Shark's notes
+        // edited.
+        let remote = bundle(
+            &[1, 2],
+            &["a", "b", "d"],
+            &[("Rock", "loose"), ("Shark", "sharper"), ("Maniac", "wild")],
+        );
+
+        let merged = merge(&base, &local, &remote);
+        let played: Vec<i64> = merged.hands.iter().map(|hand| hand.played_at).collect();
+        assert_eq!(played, [1, 2, 3]);
+        assert_eq!(merged.bookmarks, ["a", "c", "d"]);
+        assert_eq!(
+            merged.notes,
+            bundle(&[], &[], &[("Rock", "very tight"), ("Shark", "sharper")]).notes
+        );
+        // Nothing new on either side leaves everything as it was.
+        assert_eq!(merge(&merged, &merged, &merged), merged);
+    }
+}
@@ -0,0 +1,164 @@
+//! Top-level error boundary. A panic anywhere in the UI (a `RefCell` borrow
+//! conflict in a callback, a broken engine invariant) or a failing event loop
+//! ends in an error dialog instead of the window silently vanishing, and the
+//! dialog can save the session to a file for a bug report.
+
+use crate::ui::ErrorDialog;
+use poker_engine::PokerGame;
+use slint::ComponentHandle;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Mutex, PoisonError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Why the app stopped, as shown in the error dialog.
+#[derive(Debug)]
+pub struct Failure {
+    pub context: String,
+    pub details: String,
+}
+
+impl Failure {
+    pub fn new(context: impl Into<String>, details: impl Into<String>) -> Self {
+        Failure {
+            context: context.into(),
+            details: details.into(),
+        }
+    }
+
+    /// A failure for a panic caught by the boundary, using the message the
+    /// panic hook recorded.
+    pub fn from_panic() -> Self {
+        let details = LAST_PANIC
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Failure::new("The game hit an internal error", details)
+    }
+}
+
+/// Logs every panic and keeps its message for [`Failure::from_panic`]; the
+/// default hook still prints it to stderr.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = info.to_string();
+        error!("{}", message);
+        *LAST_PANIC.lock().unwrap_or_else(PoisonError::into_inner) = Some(message);
+        default_hook(info);
+    }));
+}
+
+/// Shows the error dialog and waits for the player to quit. Falls back to
+/// saving the session straight away when no window can be opened.
+pub fn report(failure: &Failure, game: Option<Rc<RefCell<PokerGame>>>) {
+    error!(context = %failure.context, details = %failure.details, "fatal error");
+
+    let dialog = match ErrorDialog::new() {
+        Ok(dialog) => dialog,
+        Err(e) => {
+            error!("Failed to open error dialog: {}", e);
+            eprintln!("{}: {}", failure.context, failure.details);
+            if let Some(game) = &game {
+                match save_session(failure, game) {
+                    Ok(path) => eprintln!("Session saved to {}", path.display()),
+                    Err(e) => eprintln!("Could not save session: {}", e),
+                }
+            }
+            return;
+        }
+    };
+
+    dialog.set_context(failure.context.clone().into());
+    dialog.set_details(failure.details.clone().into());
+    dialog.set_can_save(game.is_some());
+
+    let weak_dialog = dialog.as_weak();
+    let save_context = Failure::new(failure.context.clone(), failure.details.clone());
+    dialog.on_save_session(move || {
+        let Some(game) = &game else { return };
+        let status = match save_session(&save_context, game) {
+            Ok(path) => {
+                info!(path = %path.display(), "session saved");
+                format!("Saved to {}", path.display())
+            }
+            Err(e) => format!("Could not save session: {}", e),
+        };
+        if let Some(dialog) = weak_dialog.upgrade() {
+            dialog.set_save_status(status.into());
+        }
+    });
+
+    let weak_dialog = dialog.as_weak();
+    dialog.on_quit(move || {
+        if let Some(dialog) = weak_dialog.upgrade() {
+            let _ = dialog.hide();
+        }
+    });
+
+    if let Err(e) = dialog.run() {
+        error!("Error dialog failed: {}", e);
+        eprintln!("{}: {}", failure.context, failure.details);
+    }
+}
+
+/// Writes the failure, the current game state and the hand's events to a
+/// file in the temp directory and returns its path.
+pub fn save_session(failure: &Failure, game: &RefCell<PokerGame>) -> io::Result<PathBuf> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let path = std::env::temp_dir().join(format!("slint-poker-session-{}.txt", stamp));
+    std::fs::write(&path, session_report(failure, game))?;
+    Ok(path)
+}
+
+fn session_report(failure: &Failure, game: &RefCell<PokerGame>) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "{}\n\n{}\n", failure.context, failure.details);
+    match game.try_borrow() {
+        Ok(game) => {
+            let _ = writeln!(report, "{:#?}\n", game.snapshot());
+            let _ = writeln!(report, "Events this hand:");
+            for event in game.events() {
+                let _ = writeln!(report, "  {:?}", event);
+            }
+        }
+        Err(_) => {
+            let _ = writeln!(report, "(game state unavailable: still borrowed)");
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_report_includes_failure_and_state() {
+        let game = RefCell::new(PokerGame::new());
+        game.borrow_mut().start_hand();
+        let failure = Failure::new("Something broke", "already borrowed");
+
+        let report = session_report(&failure, &game);
+        assert!(report.starts_with("Something broke\n\nalready borrowed"));
+        assert!(report.contains("GameSnapshot"));
+        assert!(report.contains("CardsDealt"));
+
+        let _borrow = game.borrow_mut();
+        assert!(session_report(&failure, &game).contains("still borrowed"));
+    }
+}
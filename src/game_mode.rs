@@ -0,0 +1,150 @@
+//! The ways to play a match, picked from the main menu. Every mode is heads
+//! up against the bot; they differ in stacks, blinds and what happens when
+//! someone busts.
+
+use poker_engine::{Chips, BIG_BLIND, SMALL_BLIND, STARTING_CHIPS};
+
+// Blinds stop doubling after this many levels.
+const MAX_BLIND_LEVEL: u32 = 6;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Cash,
+    Tournament,
+    SitAndGo,
+    Practice,
+    Tutorial,
+    Replayer,
+    Spectate,
+    /// Against someone on another computer; started from the main menu's
+    /// LAN row rather than the list of modes.
+    Lan,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 7] = [
+        GameMode::Cash,
+        GameMode::Tournament,
+        GameMode::SitAndGo,
+        GameMode::Practice,
+        GameMode::Tutorial,
+        GameMode::Replayer,
+        GameMode::Spectate,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Cash => "Cash Game",
+            GameMode::Tournament => "Tournament",
+            GameMode::SitAndGo => "Sit & Go",
+            GameMode::Practice => "Practice",
+            GameMode::Tutorial => "Tutorial",
+            GameMode::Replayer => "Replayer",
+            GameMode::Spectate => "Watch Bots",
+            GameMode::Lan => "LAN Match",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            GameMode::Cash => "Fixed blinds. Bust and you reload.",
+            GameMode::Tournament => "Deep stacks, blinds double every 15 hands. Last one standing.",
+            GameMode::SitAndGo => "Blinds double every 8 hands. Fast and final.",
+            GameMode::Practice => "Fixed blinds with the bot's cards face up.",
+            GameMode::Tutorial => "New to poker? A guided hand, one step at a time.",
+            GameMode::Replayer => {
+                "Step through this session's hands, or import some played elsewhere."
+            }
+            GameMode::Spectate => "Two bots play each other with every card face up.",
+            GameMode::Lan => "Heads up against a friend on your network.",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        GameMode::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn starting_chips(self) -> Chips {
+        match self {
+            GameMode::Tournament => STARTING_CHIPS * 3,
+            _ => STARTING_CHIPS,
+        }
+    }
+
+    /// The blinds for the `hand_number`th hand of a match.
+    pub fn blinds(self, hand_number: u32) -> (Chips, Chips) {
+        let factor = 1 << self.blind_level(hand_number);
+        (SMALL_BLIND * factor, BIG_BLIND * factor)
+    }
+
+    /// How many times the blinds have doubled by the `hand_number`th hand.
+    pub fn blind_level(self, hand_number: u32) -> u32 {
+        self.hands_per_level().map_or(0, |hands_per_level| {
+            (hand_number.saturating_sub(1) / hands_per_level).min(MAX_BLIND_LEVEL)
+        })
+    }
+
+    /// Hands left at the current level, counting the `hand_number`th, or
+    /// `None` once the blinds have stopped going up.
+    pub fn hands_to_next_level(self, hand_number: u32) -> Option<u32> {
+        let hands_per_level = self.hands_per_level()?;
+        let level = self.blind_level(hand_number);
+        (level < MAX_BLIND_LEVEL).then(|| (level + 1) * hands_per_level + 1 - hand_number.max(1))
+    }
+
+    pub fn has_blind_levels(self) -> bool {
+        self.hands_per_level().is_some()
+    }
+
+    fn hands_per_level(self) -> Option<u32> {
+        match self {
+            GameMode::Tournament => Some(15),
+            GameMode::SitAndGo => Some(8),
+            _ => None,
+        }
+    }
+
+    /// Whether a bust ends the match instead of reloading both stacks.
+    pub fn is_freezeout(self) -> bool {
+        matches!(self, GameMode::Tournament | GameMode::SitAndGo)
+    }
+
+    pub fn shows_bot_cards(self) -> bool {
+        matches!(self, GameMode::Practice | GameMode::Spectate)
+    }
+
+    /// Whether "Save & Quit" keeps the match for next time.
+    pub fn is_saved(self) -> bool {
+        self.is_playing() && !matches!(self, GameMode::Tutorial | GameMode::Lan)
+    }
+
+    /// Whether the user holds a seat, rather than watching.
+    pub fn is_playing(self) -> bool {
+        !matches!(self, GameMode::Replayer | GameMode::Spectate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinds_go_up_only_in_tournament_modes() {
+        assert_eq!(GameMode::Cash.blinds(100), (SMALL_BLIND, BIG_BLIND));
+        assert_eq!(GameMode::SitAndGo.blinds(8), (SMALL_BLIND, BIG_BLIND));
+        assert_eq!(
+            GameMode::SitAndGo.blinds(9),
+            (SMALL_BLIND * 2, BIG_BLIND * 2)
+        );
+        assert_eq!(
+            GameMode::Tournament.blinds(1000),
+            (SMALL_BLIND * 64, BIG_BLIND * 64)
+        );
+        assert_eq!(GameMode::from_name("Sit & Go"), Some(GameMode::SitAndGo));
+        assert_eq!(GameMode::SitAndGo.hands_to_next_level(1), Some(8));
+        assert_eq!(GameMode::SitAndGo.hands_to_next_level(9), Some(8));
+        assert_eq!(GameMode::Tournament.hands_to_next_level(1000), None);
+        assert_eq!(GameMode::Cash.hands_to_next_level(1), None);
+    }
+}
@@ -0,0 +1,523 @@
+//! Every hand played, and the tendencies seen in it, in an SQLite database
+//! all profiles share: `slint-poker/hands.sqlite3` in the config directory.
+//! The stats screen, the leaderboard and the opponent HUD are queries on it.
+//! Built without the `stats-db` feature nothing is kept, and the HUD falls
+//! back to the current match.
+
+#[cfg(feature = "stats-db")]
+use crate::profiles;
+use poker_engine::{Card, Chips, HandLog, PlayerStats};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "stats-db")]
+use tracing::{debug, warn};
+
+#[cfg(feature = "stats-db")]
+const DB_FILE: &str = "hands.sqlite3";
+
+/// A finished hand, as recorded.
+#[cfg_attr(not(feature = "stats-db"), allow(dead_code))]
+pub struct HandRecord<'a> {
+    pub profile: &'a str,
+    pub opponent: &'a str,
+    pub mode: &'a str,
+    pub log: &'a HandLog,
+    pub board: &'a [Card],
+    pub big_blind: Chips,
+    /// What the user won or lost.
+    pub net: i64,
+    /// What each seat did this hand, as counts.
+    pub seats: [PlayerStats; 2],
+}
+
+/// A recorded hand as it is copied between machines; `seats` holds each
+/// seat's stat counters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredHand {
+    pub opponent: String,
+    pub mode: String,
+    pub played_at: i64,
+    pub hand_number: u32,
+    pub log: String,
+    pub board: String,
+    pub big_blind: u32,
+    pub net: i64,
+    pub seats: [[u32; 9]; 2],
+}
+
+/// A profile's results over every hand recorded.
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    pub hands: u32,
+    pub net: i64,
+    pub bb_per_100: f64,
+    pub biggest_win: i64,
+    pub stats: PlayerStats,
+}
+
+/// A profile's row on the leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub profile: String,
+    pub hands: u32,
+    pub net: i64,
+    pub bb_per_100: f64,
+}
+
+/// What `now` has counted since `before`. Stats reset in between count
+/// from zero.
+pub fn stats_since(now: &PlayerStats, before: &PlayerStats) -> PlayerStats {
+    let before = if now.hands < before.hands {
+        [0; 9]
+    } else {
+        counters(before)
+    };
+    let mut since = counters(now);
+    for (count, earlier) in since.iter_mut().zip(before) {
+        *count = count.saturating_sub(earlier);
+    }
+    from_counters(since)
+}
+
+// In the column order of `seat_stats`.
+fn counters(stats: &PlayerStats) -> [u32; 9] {
+    [
+        stats.hands,
+        stats.vpip_hands,
+        stats.pfr_hands,
+        stats.faced_bets,
+        stats.folds_to_bet,
+        stats.faced_three_bets,
+        stats.folds_to_three_bet,
+        stats.aggressive_actions,
+        stats.calls,
+    ]
+}
+
+fn from_counters(counts: [u32; 9]) -> PlayerStats {
+    let mut stats = PlayerStats::default();
+    [
+        stats.hands,
+        stats.vpip_hands,
+        stats.pfr_hands,
+        stats.faced_bets,
+        stats.folds_to_bet,
+        stats.faced_three_bets,
+        stats.folds_to_three_bet,
+        stats.aggressive_actions,
+        stats.calls,
+    ] = counts;
+    stats
+}
+
+#[cfg(feature = "stats-db")]
+mod store {
+    use super::{counters, from_counters, HandRecord, Standing, StoredHand, Summary};
+    use crate::bookmarks;
+    use poker_engine::PlayerStats;
+    use rusqlite::{params, Connection, OptionalExtension};
+    use std::path::Path;
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS hands (
+            id INTEGER PRIMARY KEY,
+            profile TEXT NOT NULL,
+            opponent TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            played_at INTEGER NOT NULL,
+            hand_number INTEGER NOT NULL,
+            log TEXT NOT NULL,
+            board TEXT NOT NULL,
+            big_blind INTEGER NOT NULL,
+            net INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS hands_by_opponent ON hands (profile, opponent);
+        CREATE UNIQUE INDEX IF NOT EXISTS hands_once ON hands (profile, played_at, log);
+        CREATE TABLE IF NOT EXISTS seat_stats (
+            hand_id INTEGER NOT NULL REFERENCES hands (id),
+            seat INTEGER NOT NULL,
+            hands INTEGER NOT NULL,
+            vpip_hands INTEGER NOT NULL,
+            pfr_hands INTEGER NOT NULL,
+            faced_bets INTEGER NOT NULL,
+            folds_to_bet INTEGER NOT NULL,
+            faced_three_bets INTEGER NOT NULL,
+            folds_to_three_bet INTEGER NOT NULL,
+            aggressive_actions INTEGER NOT NULL,
+            calls INTEGER NOT NULL,
+            PRIMARY KEY (hand_id, seat)
+        );
+    ";
+    const SUM_STATS: &str = "
+        SUM(s.hands), SUM(s.vpip_hands), SUM(s.pfr_hands), SUM(s.faced_bets),
+        SUM(s.folds_to_bet), SUM(s.faced_three_bets), SUM(s.folds_to_three_bet),
+        SUM(s.aggressive_actions), SUM(s.calls)";
+
+    pub struct Store {
+        connection: Connection,
+    }
+
+    impl Store {
+        pub fn open(path: &Path) -> rusqlite::Result<Self> {
+            Self::with(Connection::open(path)?)
+        }
+
+        #[cfg(test)]
+        pub fn in_memory() -> rusqlite::Result<Self> {
+            Self::with(Connection::open_in_memory()?)
+        }
+
+        fn with(connection: Connection) -> rusqlite::Result<Self> {
+            connection.execute_batch(SCHEMA)?;
+            Ok(Store { connection })
+        }
+
+        pub fn record(&mut self, hand: &HandRecord) -> rusqlite::Result<()> {
+            let played_at = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs() as i64);
+            let board: Vec<String> = hand.board.iter().map(|card| card.to_string()).collect();
+            let stored = StoredHand {
+                opponent: hand.opponent.to_string(),
+                mode: hand.mode.to_string(),
+                played_at,
+                hand_number: hand.log.hand_number,
+                log: bookmarks::to_line(hand.log),
+                board: board.join(" "),
+                big_blind: hand.big_blind.amount(),
+                net: hand.net,
+                seats: hand.seats.each_ref().map(counters),
+            };
+            self.import(hand.profile, std::slice::from_ref(&stored))
+                .map(|_| ())
+        }
+
+        /// Adds the hands `profile` doesn't have yet; returns how many.
+        pub fn import(&mut self, profile: &str, hands: &[StoredHand]) -> rusqlite::Result<usize> {
+            let transaction = self.connection.transaction()?;
+            let mut added = 0;
+            for hand in hands {
+                let inserted = transaction.execute(
+                    "INSERT OR IGNORE INTO hands (profile, opponent, mode, played_at, hand_number,
+                        log, board, big_blind, net)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![
+                        profile,
+                        hand.opponent,
+                        hand.mode,
+                        hand.played_at,
+                        hand.hand_number,
+                        hand.log,
+                        hand.board,
+                        hand.big_blind,
+                        hand.net,
+                    ],
+                )?;
+                if inserted == 0 {
+                    continue;
+                }
+                let hand_id = transaction.last_insert_rowid();
+                for (seat, &[a, b, c, d, e, f, g, h, i]) in hand.seats.iter().enumerate() {
+                    transaction.execute(
+                        "INSERT INTO seat_stats VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                        params![hand_id, seat, a, b, c, d, e, f, g, h, i],
+                    )?;
+                }
+                added += 1;
+            }
+            transaction.commit()?;
+            Ok(added)
+        }
+
+        /// Every hand `profile` has played, oldest first.
+        pub fn export(&self, profile: &str) -> rusqlite::Result<Vec<StoredHand>> {
+            let mut statement = self.connection.prepare(
+                "SELECT h.opponent, h.mode, h.played_at, h.hand_number, h.log, h.board,
+                    h.big_blind, h.net, s.seat, s.hands, s.vpip_hands, s.pfr_hands, s.faced_bets,
+                    s.folds_to_bet, s.faced_three_bets, s.folds_to_three_bet,
+                    s.aggressive_actions, s.calls
+                 FROM hands h JOIN seat_stats s ON s.hand_id = h.id
+                 WHERE h.profile = ?1 ORDER BY h.id, s.seat",
+            )?;
+            let mut rows = statement.query([profile])?;
+            let mut hands: Vec<StoredHand> = Vec::new();
+            while let Some(row) = rows.next()? {
+                let mut counts = [0; 9];
+                for (index, count) in counts.iter_mut().enumerate() {
+                    *count = row.get(9 + index)?;
+                }
+                let seat: usize = row.get(8)?;
+                // Seat 0 starts a hand's rows and seat 1 finishes it.
+                if seat == 0 {
+                    hands.push(StoredHand {
+                        opponent: row.get(0)?,
+                        mode: row.get(1)?,
+                        played_at: row.get(2)?,
+                        hand_number: row.get(3)?,
+                        log: row.get(4)?,
+                        board: row.get(5)?,
+                        big_blind: row.get(6)?,
+                        net: row.get(7)?,
+                        seats: [counts, [0; 9]],
+                    });
+                } else if let Some(hand) = hands.last_mut() {
+                    hand.seats[1] = counts;
+                }
+            }
+            Ok(hands)
+        }
+
+        pub fn summary(&self, profile: &str) -> rusqlite::Result<Summary> {
+            let (hands, net, bb_per_100, biggest_win) = self.connection.query_row(
+                "SELECT COUNT(*), COALESCE(SUM(net), 0),
+                    COALESCE(100.0 * SUM(CAST(net AS REAL) / big_blind) / COUNT(*), 0.0),
+                    COALESCE(MAX(net), 0)
+                 FROM hands WHERE profile = ?1",
+                [profile],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )?;
+            let stats = self
+                .seat_stats("WHERE h.profile = ?1 AND s.seat = 0", params![profile])?
+                .unwrap_or_default();
+            Ok(Summary {
+                hands,
+                net,
+                bb_per_100,
+                biggest_win,
+                stats,
+            })
+        }
+
+        pub fn leaderboard(&self) -> rusqlite::Result<Vec<Standing>> {
+            let mut statement = self.connection.prepare(
+                "SELECT profile, COUNT(*), SUM(net),
+                    100.0 * SUM(CAST(net AS REAL) / big_blind) / COUNT(*) AS bb_per_100
+                 FROM hands GROUP BY profile ORDER BY bb_per_100 DESC, profile",
+            )?;
+            let standings = statement.query_map([], |row| {
+                Ok(Standing {
+                    profile: row.get(0)?,
+                    hands: row.get(1)?,
+                    net: row.get(2)?,
+                    bb_per_100: row.get(3)?,
+                })
+            })?;
+            standings.collect()
+        }
+
+        pub fn opponent_stats(
+            &self,
+            profile: &str,
+            opponent: &str,
+        ) -> rusqlite::Result<Option<PlayerStats>> {
+            self.seat_stats(
+                "WHERE h.profile = ?1 AND h.opponent = ?2 AND s.seat = 1",
+                params![profile, opponent],
+            )
+        }
+
+        fn seat_stats(
+            &self,
+            filter: &str,
+            params: impl rusqlite::Params,
+        ) -> rusqlite::Result<Option<PlayerStats>> {
+            let query = format!(
+                "SELECT {} FROM seat_stats s JOIN hands h ON h.id = s.hand_id {}",
+                SUM_STATS, filter
+            );
+            let counts = self
+                .connection
+                .query_row(&query, params, |row| {
+                    let mut counts = [0; 9];
+                    for (index, count) in counts.iter_mut().enumerate() {
+                        // Sums over no rows are NULL.
+                        match row.get::<_, Option<u32>>(index)? {
+                            Some(sum) => *count = sum,
+                            None => return Ok(None),
+                        }
+                    }
+                    Ok(Some(counts))
+                })
+                .optional()?
+                .flatten();
+            Ok(counts.map(from_counters))
+        }
+    }
+}
+
+/// The hand database, or nothing if it can't be opened.
+pub struct HandDb {
+    #[cfg(feature = "stats-db")]
+    store: Option<std::cell::RefCell<store::Store>>,
+}
+
+impl HandDb {
+    pub fn open() -> Self {
+        #[cfg(feature = "stats-db")]
+        {
+            let store = profiles::shared_path(DB_FILE).and_then(|path| {
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                store::Store::open(&path)
+                    .map_err(|error| {
+                        warn!(path = %path.display(), %error, "cannot open the hand database");
+                    })
+                    .ok()
+            });
+            HandDb {
+                store: store.map(std::cell::RefCell::new),
+            }
+        }
+        #[cfg(not(feature = "stats-db"))]
+        HandDb {}
+    }
+
+    pub fn record(&self, hand: &HandRecord) {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            if let Err(error) = store.borrow_mut().record(hand) {
+                warn!(%error, "cannot record the hand");
+            }
+        }
+        #[cfg(not(feature = "stats-db"))]
+        let _ = hand;
+    }
+
+    pub fn summary(&self, profile: &str) -> Summary {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            return store
+                .borrow()
+                .summary(profile)
+                .map_err(|error| warn!(%error, "cannot read the stats"))
+                .unwrap_or_default();
+        }
+        let _ = profile;
+        Summary::default()
+    }
+
+    /// Every hand `profile` has played, for copying to another machine.
+    pub fn export(&self, profile: &str) -> Vec<StoredHand> {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            return store
+                .borrow()
+                .export(profile)
+                .map_err(|error| warn!(%error, "cannot read the hands"))
+                .unwrap_or_default();
+        }
+        let _ = profile;
+        Vec::new()
+    }
+
+    /// Adds the hands in `hands` that `profile` doesn't have yet.
+    pub fn import(&self, profile: &str, hands: &[StoredHand]) {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            match store.borrow_mut().import(profile, hands) {
+                Ok(added) => debug!(added, "hands imported"),
+                Err(error) => warn!(%error, "cannot add the hands"),
+            }
+        }
+        #[cfg(not(feature = "stats-db"))]
+        let _ = (profile, hands);
+    }
+
+    /// Every profile's results, best first.
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            return store
+                .borrow()
+                .leaderboard()
+                .map_err(|error| warn!(%error, "cannot read the leaderboard"))
+                .unwrap_or_default();
+        }
+        Vec::new()
+    }
+
+    /// How `opponent` has played against `profile` over every recorded
+    /// hand, or `None` before the first.
+    pub fn opponent_stats(&self, profile: &str, opponent: &str) -> Option<PlayerStats> {
+        #[cfg(feature = "stats-db")]
+        if let Some(store) = &self.store {
+            return store
+                .borrow()
+                .opponent_stats(profile, opponent)
+                .map_err(|error| warn!(%error, "cannot read the opponent's stats"))
+                .ok()
+                .flatten();
+        }
+        let _ = (profile, opponent);
+        None
+    }
+}
+
+#[cfg(all(test, feature = "stats-db"))]
+mod tests {
+    use super::*;
+    use poker_engine::Action;
+
+    #[test]
+    fn test_recorded_hands_feed_the_summary_leaderboard_and_hud() {
+        let mut store = store::Store::in_memory().unwrap();
+        let logs: Vec<HandLog> = (1..=3)
+            .map(|hand_number| HandLog {
+                seed: 7,
+                hand_number,
+                dealer_position: 0,
+                stacks: vec![Chips::new(1000), Chips::new(1000)],
+                actions: vec![Action::Call, Action::Check],
+            })
+            .collect();
+        let mut raiser = PlayerStats::default();
+        raiser.hands = 1;
+        raiser.vpip_hands = 1;
+        raiser.aggressive_actions = 1;
+        let hand = |profile, opponent, net, log| HandRecord {
+            profile,
+            opponent,
+            mode: "Cash",
+            log,
+            board: &[],
+            big_blind: Chips::new(20),
+            net,
+            seats: [PlayerStats::default(), raiser.clone()],
+        };
+        store.record(&hand("amy", "Shark", 40, &logs[0])).unwrap();
+        store.record(&hand("amy", "Rock", -10, &logs[1])).unwrap();
+        store.record(&hand("zed", "Shark", 100, &logs[2])).unwrap();
+
+        let summary = store.summary("amy").unwrap();
+        assert_eq!(
+            (summary.hands, summary.net, summary.biggest_win),
+            (2, 30, 40)
+        );
+        assert_eq!(summary.bb_per_100, 75.0);
+        let profiles: Vec<String> = store
+            .leaderboard()
+            .unwrap()
+            .into_iter()
+            .map(|standing| standing.profile)
+            .collect();
+        assert_eq!(profiles, ["zed", "amy"]);
+        let shark = store.opponent_stats("amy", "Shark").unwrap().unwrap();
+        assert_eq!((shark.hands, shark.vpip_hands), (1, 1));
+        assert!(store.opponent_stats("amy", "Maniac").unwrap().is_none());
+
+        let exported = store.export("amy").unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0].seats[1], counters(&raiser));
+        let mut copy = store::Store::in_memory().unwrap();
+        assert_eq!(copy.import("amy", &exported).unwrap(), 2);
+        assert_eq!(copy.import("amy", &exported).unwrap(), 0);
+        assert_eq!(copy.export("amy").unwrap(), exported);
+
+        let mut later = raiser.clone();
+        later.hands = 3;
+        later.calls = 2;
+        let since = stats_since(&later, &raiser);
+        assert_eq!((since.hands, since.vpip_hands, since.calls), (2, 0, 2));
+        assert_eq!(stats_since(&raiser, &later).hands, 1);
+    }
+}
@@ -0,0 +1,359 @@
+//! Hands played elsewhere, read from the text hand histories poker sites
+//! export so the replayer can step through them. The format read is
+//! PokerStars', which most sites and trackers also write:
+//!
+//! ```text
+//! PokerStars Hand #2471: Hold'em No Limit ($0.50/$1.00 USD) - 2024/01/05
+//! Table 'Hydra' 2-max Seat #1 is the button
+//! Seat 1: amy ($100 in chips)
+//! Seat 2: zed ($80.50 in chips)
+//! amy: posts small blind $0.50
+//! zed: posts big blind $1
+//! *** HOLE CARDS ***
+//! Dealt to amy [Ah Kd]
+//! amy: raises $2 to $3
+//! ...
+//! ```
+//!
+//! Only heads-up hold'em is kept. Amounts are rescaled so the big blind is
+//! the engine's, and a seat whose cards were never shown is dealt cards
+//! no one else holds.
+
+use poker_engine::{full_deck, Action, Card, Chips, PokerGame, ReplayError, BIG_BLIND, MIN_RAISE};
+
+/// One heads-up hand, seated the engine's way: seat 0 is the player the
+/// cards were dealt to, or the first one listed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedHand {
+    pub id: String,
+    pub names: [String; 2],
+    pub button: usize,
+    pub stacks: [Chips; 2],
+    pub small_blind: Chips,
+    pub hole_cards: [Option<Vec<Card>>; 2],
+    pub board: Vec<Card>,
+    pub actions: Vec<(usize, Action)>,
+}
+
+/// The hands read from a file, and how many of its hands weren't heads-up
+/// hold'em or couldn't be read.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub hands: Vec<ImportedHand>,
+    pub skipped: usize,
+}
+
+pub fn parse(text: &str) -> Import {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.contains(" Hand #") {
+            blocks.push(Vec::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push(line);
+        }
+    }
+    let mut import = Import::default();
+    for block in blocks {
+        match parse_hand(&block) {
+            Some(hand) => import.hands.push(hand),
+            None => import.skipped += 1,
+        }
+    }
+    import
+}
+
+fn parse_hand(lines: &[&str]) -> Option<ImportedHand> {
+    let header = lines.first()?;
+    if !header.contains("Hold'em") {
+        return None;
+    }
+    let id = header
+        .split(" Hand #")
+        .nth(1)?
+        .split(':')
+        .next()?
+        .to_string();
+
+    let mut seats: Vec<(String, u64)> = Vec::new();
+    let mut small_blind = None;
+    let mut big_blind = None;
+    let mut hero = None;
+    let mut shown: Vec<(String, Vec<Card>)> = Vec::new();
+    let mut board = Vec::new();
+    let mut actions: Vec<(String, &str)> = Vec::new();
+    let mut dealt = false;
+    for line in &lines[1..] {
+        if line.starts_with("*** SUMMARY") {
+            break;
+        }
+        if let Some(street) = line.strip_prefix("*** ") {
+            dealt = true;
+            if !street.starts_with("HOLE CARDS") && !street.starts_with("SHOW") {
+                // Each street repeats the board so far, then its new cards.
+                let new_cards = street.rsplit('[').next()?.trim_end_matches(']');
+                board.extend(parse_cards(new_cards)?);
+            }
+        } else if let Some(seat) = line.strip_prefix("Seat ").filter(|_| !dealt) {
+            if let Some((_, player)) = seat.split_once(": ") {
+                let (name, stack) = player.rsplit_once(" (")?;
+                let stack = stack.split_whitespace().next()?;
+                seats.push((name.to_string(), cents(stack)?));
+            }
+        } else if let Some(rest) = line.strip_prefix("Dealt to ") {
+            let (name, cards) = rest.rsplit_once(" [")?;
+            hero = Some(name.to_string());
+            shown.push((name.to_string(), parse_cards(cards.trim_end_matches(']'))?));
+        } else if let Some((name, what)) = line.split_once(": ") {
+            if let Some(amount) = what.strip_prefix("posts small blind ") {
+                small_blind = Some(cents(amount.split_whitespace().next()?)?);
+            } else if let Some(amount) = what.strip_prefix("posts big blind ") {
+                big_blind = Some((name.to_string(), cents(amount.split_whitespace().next()?)?));
+            } else if let Some(cards) = what.strip_prefix("shows [") {
+                let cards = cards.split(']').next()?;
+                shown.push((name.to_string(), parse_cards(cards)?));
+            } else if ["folds", "checks", "calls ", "bets ", "raises "]
+                .iter()
+                .any(|verb| what.starts_with(verb))
+            {
+                actions.push((name.to_string(), what));
+            }
+        }
+    }
+
+    let [first, second] = <[(String, u64); 2]>::try_from(seats).ok()?;
+    let (big_blind_name, big_blind) = big_blind?;
+    if big_blind == 0 {
+        return None;
+    }
+    let seated = if hero.as_deref() == Some(second.0.as_str()) {
+        [second, first]
+    } else {
+        [first, second]
+    };
+    let seat_of = |name: &str| seated.iter().position(|(seated, _)| seated == name);
+    let chips = |cents: u64| scale(cents, big_blind).map(Chips::new);
+    // Heads up the button posts the small blind, so it sits opposite the
+    // big blind.
+    let button = 1 - seat_of(&big_blind_name)?;
+    let mut hole_cards = [None, None];
+    for (name, cards) in shown {
+        hole_cards[seat_of(&name)?] = Some(cards);
+    }
+    let actions = actions
+        .into_iter()
+        .map(|(name, what)| Some((seat_of(&name)?, parse_action(what, chips)?)))
+        .collect::<Option<Vec<_>>>()?;
+    Some(ImportedHand {
+        id,
+        stacks: [chips(seated[0].1)?, chips(seated[1].1)?],
+        names: seated.map(|(name, _)| name),
+        button,
+        small_blind: chips(small_blind.unwrap_or(big_blind / 2))?,
+        hole_cards,
+        board,
+        actions,
+    })
+}
+
+fn parse_action(what: &str, chips: impl Fn(u64) -> Option<Chips>) -> Option<Action> {
+    if what.ends_with("and is all-in") {
+        return Some(Action::AllIn);
+    }
+    let words: Vec<&str> = what.split_whitespace().collect();
+    Some(match words.as_slice() {
+        ["folds", ..] => Action::Fold,
+        ["checks", ..] => Action::Check,
+        ["calls", ..] => Action::Call,
+        ["bets", amount, ..] => Action::Bet(chips(cents(amount)?)?),
+        ["raises", _, "to", to, ..] => Action::Raise(chips(cents(to)?)?),
+        _ => return None,
+    })
+}
+
+/// `$1,250.5`, `€3` or `500` in hundredths; `None` if it isn't a number
+/// or is too big to count in them.
+fn cents(amount: &str) -> Option<u64> {
+    let amount: String = amount
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .filter(|&c| c != ',')
+        .collect();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((&amount, ""));
+    let fraction = format!("{:0<2}", fraction.get(..2).unwrap_or(fraction));
+    whole
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(100)?
+        .checked_add(fraction.parse::<u64>().ok()?)
+}
+
+// Rounded to the nearest chip; `None` if that's more chips than the engine
+// counts.
+fn scale(cents: u64, big_blind: u64) -> Option<u32> {
+    let big_blind_chips = u64::from(BIG_BLIND.amount());
+    let scaled = cents
+        .checked_mul(big_blind_chips)?
+        .checked_add(big_blind / 2)?
+        / big_blind;
+    u32::try_from(scaled).ok()
+}
+
+/// `Ah Td` in the engine's cards.
+fn parse_cards(text: &str) -> Option<Vec<Card>> {
+    let deck = full_deck();
+    text.split_whitespace()
+        .map(|code| {
+            let mut chars = code.chars();
+            let rank = match chars.next()?.to_ascii_uppercase() {
+                'T' => "10".to_string(),
+                rank => rank.to_string(),
+            };
+            let suit = match chars.next()?.to_ascii_lowercase() {
+                's' => "♠",
+                'h' => "♥",
+                'd' => "♦",
+                'c' => "♣",
+                _ => return None,
+            };
+            deck.iter()
+                .find(|card| card.rank == rank && card.suit == suit)
+                .cloned()
+        })
+        .collect()
+}
+
+impl ImportedHand {
+    /// Deals the hand and plays it out, handing `step` the game after the
+    /// deal and after every action.
+    pub fn play(&self, mut step: impl FnMut(&PokerGame)) -> Result<(), ReplayError> {
+        let mut game = PokerGame::new();
+        for (seat, player) in game.players.iter_mut().enumerate() {
+            player.name = self.names[seat].clone();
+            player.chips = self.stacks[seat];
+        }
+        game.small_blind = self.small_blind;
+        game.big_blind = BIG_BLIND;
+        game.dealer_position = self.button;
+        game.start_hand_seeded(0);
+
+        let known: Vec<&Card> = self.hole_cards.iter().flatten().flatten().collect();
+        let mut unseen = full_deck()
+            .into_iter()
+            .filter(|card| !known.contains(&card) && !self.board.contains(card));
+        for (seat, cards) in self.hole_cards.iter().enumerate() {
+            let cards = match cards {
+                Some(cards) => cards.clone(),
+                None => unseen.by_ref().take(2).collect(),
+            };
+            game.set_hole_cards(seat, cards);
+        }
+        game.stack_deck(&self.board);
+        step(&game);
+
+        for (index, &(seat, action)) in self.actions.iter().enumerate() {
+            let action = self.fit(&game, seat, action);
+            game.seat_action(seat, action)
+                .map_err(|error| ReplayError {
+                    index,
+                    action,
+                    error,
+                })?;
+            while !game.hand_complete && game.is_betting_round_complete() {
+                game.next_phase();
+            }
+            step(&game);
+        }
+        Ok(())
+    }
+
+    // Rescaled amounts can round a chip short of a minimum raise, or a chip
+    // past a stack.
+    fn fit(&self, game: &PokerGame, seat: usize, action: Action) -> Action {
+        let (Action::Bet(to) | Action::Raise(to)) = action else {
+            return action;
+        };
+        let player = &game.players[seat];
+        if to >= player.bet + player.chips {
+            return Action::AllIn;
+        }
+        let to = to.max(game.current_bet + MIN_RAISE);
+        match action {
+            Action::Bet(_) => Action::Bet(to),
+            _ => Action::Raise(to),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = "\
+PokerStars Hand #2471: Hold'em No Limit ($0.50/$1.00 USD) - 2024/01/05 21:04:11 ET
+Table 'Hydra' 2-max Seat #1 is the button
+Seat 1: zed ($80.50 in chips)
+Seat 2: amy ($100 in chips)
+zed: posts small blind $0.50
+amy: posts big blind $1
+*** HOLE CARDS ***
+Dealt to amy [Ah Kd]
+zed: raises $2 to $3
+amy: calls $2
+*** FLOP *** [As 7c 2d]
+amy: checks
+zed: bets $4
+amy: raises $8 to $12
+zed: folds
+Uncalled bet ($8) returned to amy
+amy collected $15 from pot
+*** SUMMARY ***
+Total pot $16 | Rake $1
+Board [As 7c 2d]
+Seat 1: zed (button) (small blind) folded on the Flop
+
+PokerStars Hand #2472: Omaha Pot Limit ($0.50/$1.00 USD) - 2024/01/05 21:05:30 ET
+";
+
+    #[test]
+    fn test_imported_hand_plays_out_as_written() {
+        let import = parse(HISTORY);
+        assert_eq!(import.skipped, 1);
+        let [hand] = import.hands.as_slice() else {
+            panic!("expected one hand, got {:?}", import.hands);
+        };
+        assert_eq!(hand.id, "2471");
+        assert_eq!(hand.names, ["amy", "zed"]);
+        assert_eq!(hand.button, 1);
+        assert_eq!(hand.stacks, [Chips::new(2000), Chips::new(1610)]);
+        assert_eq!(hand.board.len(), 3);
+        assert_eq!(hand.actions[0], (1, Action::Raise(Chips::new(60))));
+
+        let mut steps = 0;
+        let mut last = None;
+        hand.play(|game| {
+            steps += 1;
+            last = Some((
+                game.hand_complete,
+                game.players[0].chips,
+                game.community_cards.len(),
+            ));
+        })
+        .unwrap();
+        assert_eq!(steps, hand.actions.len() + 1);
+        assert_eq!(last, Some((true, Chips::new(2140), 3)));
+    }
+
+    #[test]
+    fn test_oversized_amounts_skip_the_hand() {
+        assert_eq!(cents("$1,250.5"), Some(125_050));
+        assert_eq!(cents("$184467440737095516.16"), None);
+        assert_eq!(scale(u64::MAX / 2, 100), None);
+
+        // Fits in cents, but not in the engine's chips.
+        let history = HISTORY.replace("($80.50 in chips)", "($80000000000 in chips)");
+        let import = parse(&history);
+        assert!(import.hands.is_empty());
+        assert_eq!(import.skipped, 2);
+    }
+}
@@ -0,0 +1,134 @@
+//! Keyboard shortcuts for the action buttons. Each action has one key, a
+//! single letter or digit, which the settings panel can change; a key can
+//! only belong to one action.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HotkeyAction {
+    Fold,
+    Check,
+    Call,
+    Raise,
+    AllIn,
+    NewHand,
+    HideCards,
+}
+
+impl HotkeyAction {
+    /// In the order ui.slint looks the keys up.
+    pub const ALL: [HotkeyAction; 7] = [
+        HotkeyAction::Fold,
+        HotkeyAction::Check,
+        HotkeyAction::Call,
+        HotkeyAction::Raise,
+        HotkeyAction::AllIn,
+        HotkeyAction::NewHand,
+        HotkeyAction::HideCards,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HotkeyAction::Fold => "Fold",
+            HotkeyAction::Check => "Check",
+            HotkeyAction::Call => "Call",
+            HotkeyAction::Raise => "Raise",
+            HotkeyAction::AllIn => "All-in",
+            HotkeyAction::NewHand => "New hand",
+            HotkeyAction::HideCards => "Hide my cards",
+        }
+    }
+
+    /// The settings file key, e.g. `hotkey_all_in`.
+    pub fn setting(self) -> &'static str {
+        match self {
+            HotkeyAction::Fold => "hotkey_fold",
+            HotkeyAction::Check => "hotkey_check",
+            HotkeyAction::Call => "hotkey_call",
+            HotkeyAction::Raise => "hotkey_raise",
+            HotkeyAction::AllIn => "hotkey_all_in",
+            HotkeyAction::NewHand => "hotkey_new_hand",
+            HotkeyAction::HideCards => "hotkey_hide_cards",
+        }
+    }
+
+    fn index(self) -> usize {
+        HotkeyAction::ALL
+            .iter()
+            .position(|&action| action == self)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hotkeys {
+    keys: [char; 7],
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Hotkeys {
+            keys: ['F', 'K', 'C', 'R', 'A', 'N', 'H'],
+        }
+    }
+}
+
+impl Hotkeys {
+    pub fn key(&self, action: HotkeyAction) -> char {
+        self.keys[action.index()]
+    }
+
+    /// Binds `text`, as typed, to `action`. Fails if it isn't a single
+    /// letter or digit, or if another action already uses it.
+    pub fn bind(&mut self, action: HotkeyAction, text: &str) -> Result<(), String> {
+        let key = parse_key(text).ok_or("Use a single letter or digit")?;
+        if let Some(&other) = HotkeyAction::ALL
+            .iter()
+            .find(|&&other| other != action && self.key(other) == key)
+        {
+            return Err(format!("{} is already the key for {}", key, other.name()));
+        }
+        self.keys[action.index()] = key;
+        Ok(())
+    }
+
+    /// Reads one `hotkey_*` line of the settings file, keeping the current
+    /// key if the value is unusable. Conflicts aren't checked, since the
+    /// lines come one at a time: swapped keys would clash half way.
+    pub fn parse_setting(&mut self, setting: &str, value: &str) {
+        let action = HotkeyAction::ALL
+            .iter()
+            .find(|action| action.setting() == setting);
+        if let (Some(&action), Some(key)) = (action, parse_key(value)) {
+            self.keys[action.index()] = key;
+        }
+    }
+}
+
+fn parse_key(text: &str) -> Option<char> {
+    let mut chars = text.trim().chars();
+    let key = chars.next()?;
+    if chars.next().is_some() || !key.is_ascii_alphanumeric() {
+        return None;
+    }
+    Some(key.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_rejects_conflicts_and_bad_keys() {
+        let mut hotkeys = Hotkeys::default();
+        assert_eq!(hotkeys.bind(HotkeyAction::Fold, "x"), Ok(()));
+        assert_eq!(hotkeys.key(HotkeyAction::Fold), 'X');
+        assert_eq!(
+            hotkeys.bind(HotkeyAction::Call, "k"),
+            Err("K is already the key for Check".to_string())
+        );
+        assert_eq!(hotkeys.key(HotkeyAction::Call), 'C');
+        assert!(hotkeys.bind(HotkeyAction::Call, "\u{f704}").is_err());
+        assert!(hotkeys.bind(HotkeyAction::Call, "ab").is_err());
+        // Rebinding an action to its own key is fine.
+        assert_eq!(hotkeys.bind(HotkeyAction::Check, "K"), Ok(()));
+    }
+}
@@ -0,0 +1,57 @@
+//! The Slint frontend. It's a library so the same code runs as the desktop
+//! app (`src/main.rs`) and in a browser: `wasm-pack build --target web --
+//! --no-default-features --features gui`, then serve `index.html` next to
+//! the `pkg` directory.
+#![cfg(feature = "gui")]
+
+mod audio;
+mod bookmarks;
+mod cloud_sync;
+mod crash;
+mod game_mode;
+mod hand_db;
+mod hand_history;
+mod hotkeys;
+mod logging;
+#[cfg(feature = "network")]
+mod network;
+mod notes;
+mod presence;
+mod profiles;
+mod session;
+mod settings;
+mod ui;
+
+use crash::Failure;
+use poker_engine::PokerGame;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use tracing::info;
+use ui::Tables;
+
+/// Starts the game; in the browser this runs when the module loads.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen::prelude::wasm_bindgen(start))]
+pub fn main() {
+    logging::init();
+    crash::install_panic_hook();
+    info!("TEXAS HOLD'EM POKER vs BOT");
+
+    let mut session = None;
+    let failure = match panic::catch_unwind(AssertUnwindSafe(|| run(&mut session))) {
+        Ok(Ok(())) => return,
+        Ok(Err(e)) => Failure::new("The window system failed", e.to_string()),
+        Err(_) => Failure::from_panic(),
+    };
+    crash::report(&failure, session);
+}
+
+/// Runs the tables until the last one closes. `session` is filled in as
+/// soon as the first game exists so the error boundary can save it if
+/// anything goes wrong.
+fn run(session: &mut Option<Rc<RefCell<PokerGame>>>) -> Result<(), slint::PlatformError> {
+    let tables = Tables::new();
+    let state = tables.open(None)?;
+    *session = Some(state.game.clone());
+    slint::run_event_loop_until_quit()
+}
@@ -0,0 +1,85 @@
+//! Log output. The filter comes from `--log-level <filter>` or the `POKER_LOG`
+//! environment variable (`debug`, `poker_engine=trace`, ...) and defaults to
+//! warnings only. `--log-file <path>` also writes the log to a file, for
+//! attaching to bug reports.
+
+use std::fs::File;
+use std::sync::Mutex;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, EnvFilter};
+
+const LOG_ENV_VAR: &str = "POKER_LOG";
+const DEFAULT_FILTER: &str = "warn";
+
+#[derive(Debug, Default, PartialEq)]
+struct LogOptions {
+    filter: Option<String>,
+    file: Option<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> LogOptions {
+    let mut options = LogOptions::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log-level" => options.filter = args.next(),
+            "--log-file" => options.file = args.next(),
+            _ => {}
+        }
+    }
+    options
+}
+
+/// Installs the global subscriber; call once at startup.
+pub fn init() {
+    let options = parse_args(std::env::args().skip(1));
+    let directives = options
+        .filter
+        .or_else(|| std::env::var(LOG_ENV_VAR).ok())
+        .unwrap_or_else(|| DEFAULT_FILTER.to_string());
+    let (filter, bad_filter) = match EnvFilter::try_new(&directives) {
+        Ok(filter) => (filter, None),
+        Err(error) => (EnvFilter::new(DEFAULT_FILTER), Some(error)),
+    };
+
+    let (file_layer, file_error) = match options.file {
+        Some(path) => match File::create(&path) {
+            Ok(file) => (
+                Some(fmt::layer().with_ansi(false).with_writer(Mutex::new(file))),
+                None,
+            ),
+            Err(error) => (None, Some((path, error))),
+        },
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(file_layer)
+        .init();
+
+    if let Some(error) = bad_filter {
+        tracing::warn!(%directives, %error, "invalid log filter, using {}", DEFAULT_FILTER);
+    }
+    if let Some((path, error)) = file_error {
+        tracing::warn!(%path, %error, "cannot open log file");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_reads_level_and_file() {
+        let options = parse_args(args(&["--log-level", "debug", "--log-file", "poker.log"]));
+        assert_eq!(options.filter.as_deref(), Some("debug"));
+        assert_eq!(options.file.as_deref(), Some("poker.log"));
+        assert_eq!(parse_args(args(&["--other"])), LogOptions::default());
+    }
+}
@@ -0,0 +1,72 @@
+//! Chat at a LAN table. Lines go through the host, which drops any from a
+//! player sending too fast before passing them on to everyone.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+pub const MAX_LINE_CHARS: usize = 200;
+// At most this many lines from one player in any `FLOOD_WINDOW`.
+const FLOOD_LINES: usize = 4;
+const FLOOD_WINDOW: Duration = Duration::from_secs(8);
+
+/// `text` as a chat line: trimmed, on one line and cut to
+/// [`MAX_LINE_CHARS`]; `None` if nothing is left.
+pub fn tidy(text: &str) -> Option<String> {
+    let line: String = text
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_LINE_CHARS)
+        .collect();
+    let line = line.trim_end();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+/// Keeps one player from flooding the table.
+#[derive(Debug, Default)]
+pub struct FloodGuard {
+    sent: VecDeque<Instant>,
+}
+
+impl FloodGuard {
+    /// Whether a line at `now` is let through; one that isn't doesn't
+    /// count against the player.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= FLOOD_WINDOW)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= FLOOD_LINES {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_tidied_and_floods_held_back() {
+        assert_eq!(tidy("  gl\nhf  "), Some("gl hf".to_string()));
+        assert_eq!(tidy(" \t "), None);
+        assert_eq!(tidy(&"a".repeat(500)).unwrap().len(), MAX_LINE_CHARS);
+
+        let mut guard = FloodGuard::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        assert!((0..FLOOD_LINES).all(|_| guard.allow(at(0))));
+        assert!(!guard.allow(at(1)));
+        assert!((0..FLOOD_LINES).all(|_| guard.allow(at(8))));
+        assert!(!guard.allow(at(9)));
+    }
+}
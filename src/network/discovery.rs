@@ -0,0 +1,168 @@
+//! Finding tables on the LAN. A host broadcasts its [`TableInfo`] every
+//! second on [`DISCOVERY_PORT`]; the lobby listens there and lists every
+//! table heard from lately.
+
+use super::protocol::{TableInfo, MIN_PROTOCOL_VERSION};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tracing::debug;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+pub const DISCOVERY_PORT: u16 = 47476;
+// A table not heard from for this long has closed or gone off the network.
+const STALE_AFTER: Duration = Duration::from_secs(4);
+const MAX_DATAGRAM: usize = 2048;
+
+/// Tells the LAN about a hosted table.
+pub struct Announcer {
+    socket: UdpSocket,
+}
+
+impl Announcer {
+    pub fn open() -> io::Result<Self> {
+        if cfg!(target_arch = "wasm32") {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        Ok(Announcer { socket })
+    }
+
+    pub fn announce(&self, table: &TableInfo) {
+        self.announce_to(table, (Ipv4Addr::BROADCAST, DISCOVERY_PORT).into());
+    }
+
+    fn announce_to(&self, table: &TableInfo, target: SocketAddr) {
+        let Ok(text) = serde_json::to_string(table) else {
+            return;
+        };
+        // A missed announcement is made up for a second later.
+        if let Err(error) = self.socket.send_to(text.as_bytes(), target) {
+            debug!(%error, "cannot announce the table");
+        }
+    }
+}
+
+/// A table heard from, and the address it was announced from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Listing {
+    pub host: IpAddr,
+    pub table: TableInfo,
+}
+
+impl Listing {
+    /// The address to join the table at.
+    pub fn address(&self) -> String {
+        SocketAddr::new(self.host, self.table.port).to_string()
+    }
+}
+
+/// The lobby's ear on the LAN.
+pub struct TableBrowser {
+    socket: UdpSocket,
+    heard: Vec<(Listing, Instant)>,
+}
+
+impl TableBrowser {
+    pub fn open() -> io::Result<Self> {
+        if cfg!(target_arch = "wasm32") {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        Self::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT).into())
+    }
+
+    fn bind(address: SocketAddr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(TableBrowser {
+            socket,
+            heard: Vec::new(),
+        })
+    }
+
+    /// Every table heard from lately, by name.
+    pub fn poll(&mut self) -> Vec<Listing> {
+        self.receive();
+        if let Some(cutoff) = Instant::now().checked_sub(STALE_AFTER) {
+            self.forget_before(cutoff);
+        }
+        let mut listings: Vec<Listing> = self
+            .heard
+            .iter()
+            .map(|(listing, _)| listing.clone())
+            .collect();
+        listings.sort_by(|a, b| a.table.name.cmp(&b.table.name));
+        listings
+    }
+
+    fn receive(&mut self) {
+        let mut buffer = [0; MAX_DATAGRAM];
+        while let Ok((length, from)) = self.socket.recv_from(&mut buffer) {
+            let table: TableInfo = match serde_json::from_slice(&buffer[..length]) {
+                Ok(table) => table,
+                Err(error) => {
+                    debug!(%from, %error, "ignoring a datagram that isn't a table");
+                    continue;
+                }
+            };
+            if table.version < MIN_PROTOCOL_VERSION {
+                continue;
+            }
+            let listing = Listing {
+                host: from.ip(),
+                table,
+            };
+            let now = Instant::now();
+            match self
+                .heard
+                .iter_mut()
+                .find(|(heard, _)| heard.address() == listing.address())
+            {
+                Some(entry) => *entry = (listing, now),
+                None => self.heard.push((listing, now)),
+            }
+        }
+    }
+
+    fn forget_before(&mut self, cutoff: Instant) {
+        self.heard.retain(|(_, heard_at)| *heard_at >= cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::protocol::PROTOCOL_VERSION;
+
+    #[test]
+    fn test_lobby_lists_announced_tables_until_they_go_quiet() {
+        let mut browser = TableBrowser::bind((Ipv4Addr::LOCALHOST, 0).into()).unwrap();
+        let target = browser.socket.local_addr().unwrap();
+        let announcer = Announcer::open().unwrap();
+        let table = |name: &str, port| TableInfo {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            seats: 2,
+            seats_open: 1,
+            port,
+            ..TableInfo::default()
+        };
+        announcer.announce_to(&table("Zed's table", 47474), target);
+        announcer.announce_to(&table("Amy's table", 47480), target);
+        announcer.announce_to(&table("Amy's table", 47480), target);
+        browser.socket.send_to(b"hello?", target).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+
+        let listings = browser.poll();
+        let names: Vec<&str> = listings.iter().map(|l| l.table.name.as_str()).collect();
+        assert_eq!(names, ["Amy's table", "Zed's table"]);
+        assert_eq!(listings[0].address(), "127.0.0.1:47480");
+
+        browser.forget_before(Instant::now() + Duration::from_secs(1));
+        assert!(browser.heard.is_empty());
+    }
+}
@@ -0,0 +1,519 @@
+//! Heads-up over a LAN. The host runs the real [`PokerGame`] with the guest
+//! in seat 1, and is the only side holding the deck and both hands. The
+//! guest keeps a replica with itself in seat 0, rebuilt from the host's
+//! messages, which are made from the game's
+//! [`poker_engine::PublicState`] and only the receiver's own
+//! [`poker_engine::PrivateState`]: the guest's hole cards go only to the
+//! guest, and the host's only at a showdown, so the replica never holds
+//! the host's cards before they are shown.
+//!
+//! Anyone who joins once the seat is taken watches instead: a spectator is
+//! sent the host's side of the table with no hole cards in it.
+//!
+//! Another copy of the app joins over TCP and a browser over a WebSocket;
+//! both speak the JSON messages in [`protocol`]. Seats and the button are
+//! always from the receiver's side of the table.
+
+pub mod chat;
+pub mod discovery;
+pub mod online;
+pub mod overlay;
+pub mod protocol;
+mod websocket;
+
+use poker_engine::{full_deck, Action, Card, Chips, GameEvent, GamePhase, PokerGame};
+use protocol::{Peer, Received, TableSnapshot, MIN_PROTOCOL_VERSION};
+use std::io::{self, BufRead, BufReader, Write as _};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+pub const DEFAULT_PORT: u16 = 47474;
+pub const WEBSOCKET_PORT: u16 = 47475;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Hand {
+        hand_number: u32,
+        dealer: usize,
+        stacks: Vec<Chips>,
+        small_blind: Chips,
+        big_blind: Chips,
+    },
+    HoleCards(Vec<Card>),
+    Act {
+        seat: usize,
+        action: Action,
+    },
+    Street(Vec<Card>),
+    Showdown(Vec<Card>),
+    /// The whole table after the messages before it.
+    Table(TableSnapshot),
+    /// Sent to a connection that joined once the seat was taken.
+    Watching,
+    /// A chat line from `seat`.
+    Chat {
+        seat: usize,
+        text: String,
+    },
+    /// The seated guest's key to its seat, should it drop.
+    Welcome {
+        token: String,
+    },
+    /// A reconnecting guest's claim on its held seat.
+    Rejoin {
+        token: String,
+    },
+}
+
+fn parse_cards<'a>(texts: impl Iterator<Item = &'a str>) -> Option<Vec<Card>> {
+    let deck = full_deck();
+    texts
+        .map(|text| deck.iter().find(|card| card.to_string() == text).cloned())
+        .collect()
+}
+
+/// What the guest is told about `events` from the host's game.
+pub fn messages_for_guest(game: &PokerGame, events: &[GameEvent]) -> Vec<Message> {
+    messages_for(game, events, Some(1))
+}
+
+/// What a spectator is told about `events`, seated as the host sees them.
+pub fn messages_for_spectators(game: &PokerGame, events: &[GameEvent]) -> Vec<Message> {
+    messages_for(game, events, None)
+}
+
+/// `events` for the player in seat `viewer`, or for a spectator if `None`,
+/// with seats numbered from the receiver's side.
+fn messages_for(game: &PokerGame, events: &[GameEvent], viewer: Option<usize>) -> Vec<Message> {
+    let public = game.public_state();
+    let seats = public.seats.len();
+    let turn = viewer.unwrap_or(0);
+    let from_viewer = |seat: usize| (seat + seats - turn) % seats;
+    let mut messages: Vec<Message> = events
+        .iter()
+        .filter_map(|event| match event {
+            GameEvent::HandStarted {
+                hand_number,
+                dealer,
+            } => Some(Message::Hand {
+                hand_number: *hand_number,
+                dealer: from_viewer(*dealer),
+                stacks: (0..seats)
+                    .map(|seat| public.seats[(seat + turn) % seats].hand_start_chips)
+                    .collect(),
+                small_blind: public.small_blind,
+                big_blind: public.big_blind,
+            }),
+            GameEvent::CardsDealt {
+                seat: Some(seat),
+                cards,
+            } if Some(*seat) == viewer => Some(Message::HoleCards(cards.clone())),
+            GameEvent::CardsDealt { seat: None, cards } => Some(Message::Street(cards.clone())),
+            GameEvent::PlayerActed { seat, action, .. } => Some(Message::Act {
+                seat: from_viewer(*seat),
+                action: *action,
+            }),
+            GameEvent::PhaseAdvanced {
+                phase: GamePhase::Showdown,
+            } => {
+                let mut hidden: Vec<usize> =
+                    (0..seats).filter(|seat| Some(*seat) != viewer).collect();
+                hidden.sort_by_key(|seat| from_viewer(*seat));
+                Some(Message::Showdown(
+                    hidden
+                        .into_iter()
+                        .flat_map(|seat| public.seats[seat].shown.clone().unwrap_or_default())
+                        .collect(),
+                ))
+            }
+            _ => None,
+        })
+        .collect();
+    if !messages.is_empty() {
+        messages.push(Message::Table(match viewer {
+            Some(seat) => TableSnapshot::seen_by(game, seat),
+            None => TableSnapshot::watched(game),
+        }));
+    }
+    messages
+}
+
+/// Plays one of the host's messages on the guest's replica, or on a
+/// spectator's, where every seat holds stand-ins until the showdown.
+pub fn apply_to_replica(game: &mut PokerGame, message: Message) -> Result<(), String> {
+    match message {
+        Message::Hand {
+            hand_number,
+            dealer,
+            stacks,
+            small_blind,
+            big_blind,
+        } => {
+            for (player, stack) in game.players.iter_mut().zip(stacks) {
+                player.chips = stack;
+            }
+            game.dealer_position = dealer;
+            game.hand_number = hand_number.saturating_sub(1);
+            game.small_blind = small_blind;
+            game.big_blind = big_blind;
+            game.start_hand();
+        }
+        Message::HoleCards(cards) => game.set_hole_cards(0, cards),
+        Message::Act { seat, action } => game
+            .seat_action(seat, action)
+            .map_err(|error| format!("{:?} for seat {} rejected: {}", action, seat, error))?,
+        Message::Street(cards) => {
+            // Hidden seats hold stand-ins; none may be one the board needs.
+            for seat in 0..game.players.len() {
+                if !game.players[seat]
+                    .cards
+                    .iter()
+                    .any(|card| cards.contains(card))
+                {
+                    continue;
+                }
+                let seen: Vec<Card> = cards
+                    .iter()
+                    .chain(&game.community_cards)
+                    .chain(game.players.iter().flat_map(|player| &player.cards))
+                    .cloned()
+                    .collect();
+                let stand_ins = full_deck()
+                    .into_iter()
+                    .filter(|card| !seen.contains(card))
+                    .take(2)
+                    .collect();
+                game.set_hole_cards(seat, stand_ins);
+            }
+            game.stack_deck(&cards);
+            game.next_phase();
+        }
+        Message::Showdown(cards) => {
+            // The hidden seats are the last ones: the guest sits in seat 0.
+            let first = game.players.len().saturating_sub(cards.len() / 2);
+            for (seat, pair) in (first..).zip(cards.chunks(2)) {
+                game.set_hole_cards(seat, pair.to_vec());
+            }
+            game.next_phase();
+        }
+        Message::Table(table) => {
+            let stacks: Vec<u32> = game.players.iter().map(|p| p.chips.amount()).collect();
+            let hosts: Vec<u32> = table.seats.iter().map(|seat| seat.chips).collect();
+            if stacks != hosts {
+                return Err(format!(
+                    "stacks {:?} out of step with the host's {:?}",
+                    stacks, hosts
+                ));
+            }
+        }
+        Message::Watching
+        | Message::Chat { .. }
+        | Message::Welcome { .. }
+        | Message::Rejoin { .. } => {}
+    }
+    Ok(())
+}
+
+/// A link to the other player. Messages are read and written on threads
+/// of their own: sent ones queue for the writer, and received ones wait in
+/// a channel until [`Connection::receive`] picks them up.
+pub struct Connection {
+    peer: Peer,
+    outgoing: Sender<String>,
+    incoming: Receiver<Message>,
+    socket: TcpStream,
+}
+
+impl Connection {
+    /// To another copy of the app, one line per message.
+    fn lines(stream: TcpStream, peer: Peer) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(false)?;
+        let reader = stream.try_clone()?;
+        let mut writer = stream.try_clone()?;
+        let (received, incoming) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if !deliver(&line, peer, &received) {
+                    break;
+                }
+            }
+        });
+        let (outgoing, to_send) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            for line in to_send {
+                if writeln!(writer, "{}", line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Connection::opened(peer, outgoing, incoming, stream))
+    }
+
+    /// Says hello over a link whose threads are running.
+    fn opened(
+        peer: Peer,
+        outgoing: Sender<String>,
+        incoming: Receiver<Message>,
+        socket: TcpStream,
+    ) -> Self {
+        let _ = outgoing.send(protocol::hello(peer));
+        Connection {
+            peer,
+            outgoing,
+            incoming,
+            socket,
+        }
+    }
+
+    /// Turns a guest who came too late into a spectator.
+    pub fn watch(&mut self) -> io::Result<()> {
+        self.peer = Peer::Spectator;
+        self.send(&Message::Watching)
+    }
+
+    pub fn send(&self, message: &Message) -> io::Result<()> {
+        let text = protocol::encode(message, self.peer)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not for this peer"))?;
+        self.outgoing
+            .send(text)
+            .map_err(|_| io::ErrorKind::BrokenPipe.into())
+    }
+
+    /// The next message, if one has arrived. `Err` means the other side
+    /// has gone.
+    pub fn receive(&self) -> Result<Option<Message>, TryRecvError> {
+        match self.incoming.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Drop for Connection {
+    // Also ends the reader, which holds its own handle on the socket.
+    fn drop(&mut self) {
+        let _ = self.socket.shutdown(Shutdown::Both);
+    }
+}
+
+/// Passes on what `peer` sent as `text`; false once the link should close.
+fn deliver(text: &str, peer: Peer, received: &Sender<Message>) -> bool {
+    match protocol::decode(text, peer) {
+        Some(Received::Message(message)) => received.send(message).is_ok(),
+        Some(Received::Hello { version }) if version < MIN_PROTOCOL_VERSION => {
+            warn!(version, "the other player's version is too old");
+            false
+        }
+        Some(Received::Hello { version }) => {
+            debug!(version, ?peer, "said hello");
+            true
+        }
+        Some(Received::Skipped) => {
+            debug!(text, "skipped a message from a later version");
+            true
+        }
+        None => {
+            warn!(text, "unreadable message");
+            true
+        }
+    }
+}
+
+/// Waits for a guest without blocking: another copy of the game on
+/// [`DEFAULT_PORT`] or a browser on [`WEBSOCKET_PORT`]. Call
+/// [`Lobby::accept`] until it returns a connection, and after that for
+/// spectators.
+pub struct Lobby {
+    lines: TcpListener,
+    browsers: TcpListener,
+}
+
+impl Lobby {
+    pub fn open() -> io::Result<Self> {
+        if cfg!(target_arch = "wasm32") {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        let listen = |port| {
+            let listener = TcpListener::bind(("0.0.0.0", port))?;
+            listener.set_nonblocking(true)?;
+            io::Result::Ok(listener)
+        };
+        Ok(Lobby {
+            lines: listen(DEFAULT_PORT)?,
+            browsers: listen(WEBSOCKET_PORT)?,
+        })
+    }
+
+    pub fn accept(&self) -> io::Result<Option<Connection>> {
+        if let Some(stream) = accepted(&self.lines)? {
+            return Connection::lines(stream, Peer::Guest).map(Some);
+        }
+        match accepted(&self.browsers)? {
+            Some(stream) => websocket::accept(stream).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn accepted(listener: &TcpListener) -> io::Result<Option<TcpStream>> {
+    match listener.accept() {
+        Ok((stream, _)) => Ok(Some(stream)),
+        Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Connects to a host at `address`, with or without a port, on a thread;
+/// the result arrives on the returned channel.
+pub fn join(address: &str) -> Receiver<io::Result<Connection>> {
+    let (sender, receiver) = mpsc::channel();
+    if cfg!(target_arch = "wasm32") {
+        let _ = sender.send(Err(io::ErrorKind::Unsupported.into()));
+        return receiver;
+    }
+    let address = address.to_string();
+    std::thread::spawn(move || {
+        let connected = connect(&address, DEFAULT_PORT)
+            .and_then(|stream| Connection::lines(stream, Peer::Host));
+        let _ = sender.send(connected);
+    });
+    receiver
+}
+
+/// Connects to `address`, on `default_port` if it names none.
+fn connect(address: &str, default_port: u16) -> io::Result<TcpStream> {
+    let address = if address.contains(':') {
+        address.trim().to_string()
+    } else {
+        format!("{}:{}", address.trim(), default_port)
+    };
+    let address = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such host"))?;
+    TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+}
+
+/// This machine's address on the LAN, for the host to read out: the local
+/// end of a UDP socket pointed outwards. Nothing is sent.
+pub fn local_address() -> Option<String> {
+    let socket = std::net::UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("8.8.8.8", 80)).ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replica_follows_the_host_without_seeing_its_cards() {
+        let mut host = PokerGame::new();
+        let mut replica = PokerGame::new();
+        host.start_hand_seeded(3);
+        let hidden = host.players[0].cards.clone();
+        let mut sent = Vec::new();
+        let mut play = |host: &mut PokerGame, replica: &mut PokerGame| {
+            let events = host.drain_events();
+            for message in messages_for_guest(host, &events) {
+                sent.push(message.clone());
+                apply_to_replica(replica, message).unwrap();
+            }
+        };
+        play(&mut host, &mut replica);
+        assert_eq!(replica.players[0].cards, host.players[1].cards);
+
+        // Call and check down to the showdown.
+        while !host.hand_complete {
+            if host.is_betting_round_complete() {
+                host.next_phase();
+            } else {
+                let seat = host.current_player;
+                let action = if host.players[seat].bet < host.current_bet {
+                    Action::Call
+                } else {
+                    Action::Check
+                };
+                host.seat_action(seat, action).unwrap();
+            }
+            play(&mut host, &mut replica);
+        }
+        let shown = sent
+            .iter()
+            .position(|message| matches!(message, Message::Showdown(_)))
+            .unwrap();
+        assert_eq!(sent[shown], Message::Showdown(hidden.clone()));
+        let hidden_names: Vec<String> = hidden.iter().map(Card::to_string).collect();
+        assert!(sent[..shown].iter().all(|message| match message {
+            Message::HoleCards(cards) | Message::Street(cards) =>
+                cards.iter().all(|card| !hidden.contains(card)),
+            Message::Table(table) => table
+                .seats
+                .iter()
+                .all(|seat| seat.cards.iter().all(|card| !hidden_names.contains(card))),
+            _ => true,
+        }));
+        assert!(replica.hand_complete);
+        assert_eq!(replica.community_cards, host.community_cards);
+        assert_eq!(replica.players[1].cards, host.players[0].cards);
+        assert_eq!(replica.players[0].chips, host.players[1].chips);
+        assert_eq!(replica.players[1].chips, host.players[0].chips);
+    }
+
+    #[test]
+    fn test_spectator_sees_no_hole_cards_until_the_showdown() {
+        let mut host = PokerGame::new();
+        let mut watched = PokerGame::new();
+        host.start_hand_seeded(8);
+        let dealt: Vec<Card> = host.players.iter().flat_map(|p| p.cards.clone()).collect();
+        let mut sent = Vec::new();
+        while !host.hand_complete {
+            let events = host.drain_events();
+            for message in messages_for_spectators(&host, &events) {
+                sent.push(message.clone());
+                apply_to_replica(&mut watched, message).unwrap();
+            }
+            if host.is_betting_round_complete() {
+                host.next_phase();
+            } else {
+                let seat = host.current_player;
+                let action = if host.players[seat].bet < host.current_bet {
+                    Action::Call
+                } else {
+                    Action::Check
+                };
+                host.seat_action(seat, action).unwrap();
+            }
+        }
+        let events = host.drain_events();
+        for message in messages_for_spectators(&host, &events) {
+            sent.push(message.clone());
+            apply_to_replica(&mut watched, message).unwrap();
+        }
+
+        let shown = sent
+            .iter()
+            .position(|message| matches!(message, Message::Showdown(_)))
+            .unwrap();
+        assert_eq!(sent[shown], Message::Showdown(dealt.clone()));
+        assert!(sent[..shown].iter().all(|message| match message {
+            Message::HoleCards(_) => false,
+            Message::Table(table) => table.seats.iter().all(|seat| seat.cards.is_empty()),
+            _ => true,
+        }));
+        assert!(watched.hand_complete);
+        assert_eq!(watched.community_cards, host.community_cards);
+        for (seen, played) in watched.players.iter().zip(&host.players) {
+            assert_eq!(seen.cards, played.cards);
+            assert_eq!(seen.chips, played.chips);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Playing over the internet through a [`poker_rendezvous`] service: the
+//! host is given a code to read out, and the guest joins with the code
+//! instead of an address. The service relays the connection, so neither
+//! side needs an open port. Once paired, it is the same game as on a LAN.
+
+use super::protocol::Peer;
+use super::{connect, Connection};
+use poker_rendezvous::{read_line, write_line, Reply, Request, DEFAULT_PORT};
+use std::io;
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver};
+
+/// How hosting through the service goes.
+pub enum Hosting {
+    /// The table is open under `code`. Shutting `socket` down closes it.
+    Open {
+        code: String,
+        socket: TcpStream,
+    },
+    Paired(Connection),
+}
+
+/// Opens a table named `name` on the service at `server`, on a thread; the
+/// code and then the guest arrive on the returned channel.
+pub fn host(server: &str, name: &str) -> Receiver<io::Result<Hosting>> {
+    let (sender, receiver) = mpsc::channel();
+    let (server, name) = (server.to_string(), name.to_string());
+    std::thread::spawn(move || {
+        let hosted = (|| {
+            let mut stream = connect(&server, DEFAULT_PORT)?;
+            match ask(&mut stream, &Request::Host { name })? {
+                Reply::Hosting { code } => {
+                    let socket = stream.try_clone()?;
+                    let _ = sender.send(Ok(Hosting::Open { code, socket }));
+                }
+                reply => return Err(refused(reply)),
+            }
+            match answer(&mut stream)? {
+                Reply::Paired => Connection::lines(stream, Peer::Guest).map(Hosting::Paired),
+                reply => Err(refused(reply)),
+            }
+        })();
+        let _ = sender.send(hosted);
+    });
+    receiver
+}
+
+/// Joins the table with `code` on the service at `server`.
+pub fn join(server: &str, code: &str) -> Receiver<io::Result<Connection>> {
+    let (sender, receiver) = mpsc::channel();
+    let (server, code) = (server.to_string(), code.trim().to_string());
+    std::thread::spawn(move || {
+        let joined = connect(&server, DEFAULT_PORT).and_then(|mut stream| {
+            match ask(&mut stream, &Request::Join { code })? {
+                Reply::Paired => Connection::lines(stream, Peer::Host),
+                reply => Err(refused(reply)),
+            }
+        });
+        let _ = sender.send(joined);
+    });
+    receiver
+}
+
+fn ask(stream: &mut TcpStream, request: &Request) -> io::Result<Reply> {
+    write_line(stream, request)?;
+    answer(stream)
+}
+
+fn answer(stream: &mut TcpStream) -> io::Result<Reply> {
+    let line = read_line(stream)?;
+    serde_json::from_str(&line).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn refused(reply: Reply) -> io::Error {
+    match reply {
+        Reply::Error { message } => io::Error::other(message),
+        reply => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected {:?} from the server", reply),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Message;
+    use poker_engine::{Action, Chips};
+    use poker_rendezvous::Rendezvous;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_host_and_guest_meet_by_code() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let server = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || Rendezvous::default().serve(listener));
+
+        let hosting = host(&server, "Amy's table");
+        let Ok(Hosting::Open { code, .. }) = hosting.recv().unwrap() else {
+            panic!("table not opened");
+        };
+        let guest = join(&server, &code).recv().unwrap().unwrap();
+        let Ok(Hosting::Paired(host)) = hosting.recv().unwrap() else {
+            panic!("guest never came");
+        };
+
+        let hand = Message::Hand {
+            hand_number: 1,
+            dealer: 0,
+            stacks: vec![Chips::new(1000); 2],
+            small_blind: Chips::new(5),
+            big_blind: Chips::new(10),
+        };
+        host.send(&hand).unwrap();
+        guest
+            .send(&Message::Act {
+                seat: 0,
+                action: Action::Call,
+            })
+            .unwrap();
+        let receive = |connection: &Connection| loop {
+            if let Some(message) = connection.receive().unwrap() {
+                break message;
+            }
+        };
+        assert_eq!(receive(&guest), hand);
+        assert!(matches!(receive(&host), Message::Act { .. }));
+
+        let missing = join(&server, "NOPE").recv().unwrap();
+        assert!(missing.is_err());
+    }
+}
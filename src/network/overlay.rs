@@ -0,0 +1,145 @@
+//! A stream overlay. While on, the table is served on localhost at
+//! [`OVERLAY_PORT`]: `/` is a page to add as an OBS browser source, and
+//! `/state.json` the [`TableSnapshot`] it draws, for overlays of your own.
+//! The snapshot is a spectator's, so no hole cards show before a showdown.
+
+use super::protocol::TableSnapshot;
+use poker_engine::PokerGame;
+use std::cell::RefCell;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+pub const OVERLAY_PORT: u16 = 47477;
+const PAGE: &str = include_str!("../../assets/overlay.html");
+// How often the server checks for a browser, or for being turned off.
+const ACCEPT_EVERY: Duration = Duration::from_millis(100);
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The overlay server, shared by every table.
+#[derive(Default)]
+pub struct Overlay {
+    // What `/state.json` serves, while on; the server stops when it's dropped.
+    served: RefCell<Option<Arc<Mutex<String>>>>,
+    last: RefCell<String>,
+}
+
+impl Overlay {
+    pub fn set_enabled(&self, enabled: bool) -> io::Result<()> {
+        let mut served = self.served.borrow_mut();
+        if !enabled {
+            served.take();
+            return Ok(());
+        }
+        if served.is_some() {
+            return Ok(());
+        }
+        if cfg!(target_arch = "wasm32") {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, OVERLAY_PORT))?;
+        *served = Some(serve(listener, self.last.borrow().clone())?);
+        Ok(())
+    }
+
+    pub fn show(&self, game: &PokerGame) {
+        let Ok(text) = serde_json::to_string(&TableSnapshot::watched(game)) else {
+            return;
+        };
+        if let Some(served) = self.served.borrow().as_ref() {
+            *served.lock().unwrap() = text.clone();
+        }
+        *self.last.borrow_mut() = text;
+    }
+}
+
+fn serve(listener: TcpListener, state: String) -> io::Result<Arc<Mutex<String>>> {
+    listener.set_nonblocking(true)?;
+    let served = Arc::new(Mutex::new(state));
+    let weak = Arc::downgrade(&served);
+    std::thread::spawn(move || loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let Some(served) = weak.upgrade() else { break };
+                if let Err(error) = respond(stream, &served) {
+                    debug!(%error, "overlay request failed");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                if Weak::strong_count(&weak) == 0 {
+                    break;
+                }
+                std::thread::sleep(ACCEPT_EVERY);
+            }
+            Err(error) => {
+                warn!(%error, "the stream overlay stopped");
+                break;
+            }
+        }
+    });
+    Ok(served)
+}
+
+fn respond(stream: TcpStream, served: &Mutex<String>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // The headers are read so closing doesn't reset the connection.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    let (status, content_type, body) = match path.split('?').next() {
+        Some("/" | "/index.html") => ("200 OK", "text/html; charset=utf-8", PAGE.to_string()),
+        Some("/state.json") => ("200 OK", "application/json", served.lock().unwrap().clone()),
+        _ => ("404 Not Found", "text/plain", "Not found".to_string()),
+    };
+    write!(
+        &stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nAccess-Control-Allow-Origin: *\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_overlay_serves_the_latest_table() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let served = serve(listener, "{}".to_string()).unwrap();
+        let mut game = PokerGame::new();
+        game.start_hand();
+        *served.lock().unwrap() = serde_json::to_string(&TableSnapshot::watched(&game)).unwrap();
+
+        let response = get(port, "/state.json?t=1");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let table: TableSnapshot = serde_json::from_str(body).unwrap();
+        assert_eq!(table.pot, game.pot.amount());
+        assert!(table.seats.iter().all(|seat| seat.cards.is_empty()));
+        assert!(get(port, "/").contains("state.json"));
+        assert!(get(port, "/secret").starts_with("HTTP/1.1 404"));
+    }
+}
@@ -0,0 +1,513 @@
+//! The messages on the wire, for every way of joining a table: one JSON
+//! object each, as a line over TCP or a text frame over a WebSocket. The
+//! host sends [`ServerMessage`]s and the guest [`ClientMessage`]s, each
+//! tagged with its `"type"`.
+//!
+//! Both ends open with `hello`, giving [`PROTOCOL_VERSION`]. A peer older
+//! than [`MIN_PROTOCOL_VERSION`] is turned away; one that says nothing is
+//! taken to speak version 1. To stay readable by older peers, later
+//! versions only add: a missing field takes its default, and unknown
+//! fields and message types are skipped.
+//!
+//! The first to join takes the open seat and is sent a `welcome` token;
+//! anyone joining after that is a spectator, told so by `watching`. A guest
+//! who drops gets its held seat back by opening with `rejoin` and that
+//! token (since version 2). A spectator sees the table as the host
+//! does, with no hole cards until a showdown turns them up.
+
+use super::{parse_cards, Message};
+use poker_engine::{Action, Card, Chips, PokerGame, PrivateState, PublicState, SeatStatus};
+use serde::{Deserialize, Serialize};
+
+pub const PROTOCOL_VERSION: u32 = 2;
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// An action as sent: `{"action": "raise", "amount": 40}`. `amount` is the
+/// total bet and comes only with `bet` and `raise`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum PlayerAction {
+    Fold,
+    Check,
+    Call,
+    Bet { amount: u32 },
+    Raise { amount: u32 },
+    AllIn,
+}
+
+impl From<Action> for PlayerAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Fold => PlayerAction::Fold,
+            Action::Check => PlayerAction::Check,
+            Action::Call => PlayerAction::Call,
+            Action::Bet(amount) => PlayerAction::Bet {
+                amount: amount.amount(),
+            },
+            Action::Raise(amount) => PlayerAction::Raise {
+                amount: amount.amount(),
+            },
+            Action::AllIn => PlayerAction::AllIn,
+        }
+    }
+}
+
+impl From<PlayerAction> for Action {
+    fn from(action: PlayerAction) -> Self {
+        match action {
+            PlayerAction::Fold => Action::Fold,
+            PlayerAction::Check => Action::Check,
+            PlayerAction::Call => Action::Call,
+            PlayerAction::Bet { amount } => Action::Bet(Chips::new(amount)),
+            PlayerAction::Raise { amount } => Action::Raise(Chips::new(amount)),
+            PlayerAction::AllIn => Action::AllIn,
+        }
+    }
+}
+
+/// The table as one seat sees it, sent after every change so a client can
+/// draw it without running a game of its own. Seat 0 is the receiver, or
+/// the host for a spectator.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableSnapshot {
+    pub hand: u32,
+    /// `preflop`, `flop`, `turn`, `river` or `showdown`.
+    pub phase: String,
+    pub button: usize,
+    /// The seat to act, if anyone is.
+    pub to_act: Option<usize>,
+    pub pot: u32,
+    pub current_bet: u32,
+    pub board: Vec<String>,
+    pub seats: Vec<SeatSnapshot>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeatSnapshot {
+    pub name: String,
+    pub chips: u32,
+    pub bet: u32,
+    /// Empty while hidden from the receiver, or once folded.
+    pub cards: Vec<String>,
+    pub folded: bool,
+}
+
+impl TableSnapshot {
+    /// `game` as `seat` sees it: the other seat's cards only once a
+    /// showdown turns them up.
+    pub fn seen_by(game: &PokerGame, seat: usize) -> Self {
+        Self::seen_from(&game.public_state(), Some(&game.private_state(seat)))
+    }
+
+    /// `game` as a spectator sees it: no one's cards until a showdown.
+    pub fn watched(game: &PokerGame) -> Self {
+        Self::seen_from(&game.public_state(), None)
+    }
+
+    /// The table from `public`, with `private`'s hole cards when a player
+    /// is looking. Seat 0 is that player, or the host for a spectator.
+    fn seen_from(public: &PublicState, private: Option<&PrivateState>) -> Self {
+        let seats = public.seats.len();
+        let viewer = private.map(|private| private.seat);
+        let from_viewer = |other: usize| (other + seats - viewer.unwrap_or(0)) % seats;
+        let mut in_order: Vec<(usize, SeatSnapshot)> = public
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(index, seat)| {
+                let cards = match (private, &seat.shown) {
+                    (_, Some(shown)) => shown.clone(),
+                    (Some(private), None) if private.seat == index => private.hole_cards.clone(),
+                    _ => Vec::new(),
+                };
+                (
+                    from_viewer(index),
+                    SeatSnapshot {
+                        name: seat.name.clone(),
+                        chips: seat.chips.amount(),
+                        bet: seat.bet.amount(),
+                        cards: cards.iter().map(Card::to_string).collect(),
+                        folded: seat.status == SeatStatus::Folded,
+                    },
+                )
+            })
+            .collect();
+        in_order.sort_by_key(|(position, _)| *position);
+        TableSnapshot {
+            hand: public.hand_number,
+            phase: public.phase.name().to_string(),
+            button: from_viewer(public.dealer),
+            to_act: public.to_act.map(from_viewer),
+            pot: public.pot.amount(),
+            current_bet: public.current_bet.amount(),
+            board: public.board.iter().map(Card::to_string).collect(),
+            seats: in_order.into_iter().map(|(_, seat)| seat).collect(),
+        }
+    }
+}
+
+/// What a host announces about its table for the lobby, as one JSON
+/// datagram; see [`super::discovery`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableInfo {
+    pub version: u32,
+    pub name: String,
+    pub variant: String,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub seats: u32,
+    pub seats_open: u32,
+    pub spectators: u32,
+    /// Where another copy of the app joins.
+    pub port: u16,
+    /// Where a browser joins.
+    pub websocket_port: u16,
+}
+
+/// From the host to the guest or a spectator.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Hello {
+        version: u32,
+    },
+    /// A new hand: its number, the seat on the button, both stacks before
+    /// the blinds, and the small and big blind.
+    Hand {
+        hand: u32,
+        button: usize,
+        stacks: Vec<u32>,
+        blinds: [u32; 2],
+    },
+    /// The guest's hole cards; never sent to a spectator.
+    Cards {
+        cards: Vec<String>,
+    },
+    Act {
+        seat: usize,
+        #[serde(flatten)]
+        action: PlayerAction,
+    },
+    /// The flop, turn or river.
+    Street {
+        cards: Vec<String>,
+    },
+    /// The cards the receiver hadn't seen, seat by seat: the host's to the
+    /// guest, both seats' to a spectator. None if they stay hidden.
+    Showdown {
+        cards: Vec<String>,
+    },
+    Table(TableSnapshot),
+    /// The seats are taken, so this connection only watches.
+    Watching,
+    /// A chat line, as passed on by the host.
+    Chat {
+        seat: usize,
+        text: String,
+    },
+    /// The token that reclaims the guest's seat if it drops; never sent to
+    /// a spectator.
+    Welcome {
+        token: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// From the guest to the host.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        version: u32,
+    },
+    /// The guest's own action; one that isn't legal when it arrives is
+    /// dropped.
+    Act {
+        #[serde(flatten)]
+        action: PlayerAction,
+    },
+    /// A chat line; the host passes it on, unless it comes too fast.
+    Chat {
+        text: String,
+    },
+    /// Sent first on reconnecting, with the `welcome` token; without it a
+    /// connection to a held seat only watches.
+    Rejoin {
+        token: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Who is at the other end of a connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Peer {
+    Host,
+    Guest,
+    /// Only watches; what it sends is ignored.
+    Spectator,
+}
+
+/// A line or frame as it was read.
+#[derive(Debug, PartialEq)]
+pub enum Received {
+    Hello {
+        version: u32,
+    },
+    Message(Message),
+    /// A message type this version doesn't know.
+    Skipped,
+}
+
+/// The opening line for `peer`.
+pub fn hello(peer: Peer) -> String {
+    let version = PROTOCOL_VERSION;
+    let text = match peer {
+        Peer::Guest | Peer::Spectator => serde_json::to_string(&ServerMessage::Hello { version }),
+        Peer::Host => serde_json::to_string(&ClientMessage::Hello { version }),
+    };
+    text.unwrap_or_default()
+}
+
+/// `message` as `peer` is sent it, or `None` if it isn't one `peer` takes.
+pub fn encode(message: &Message, peer: Peer) -> Option<String> {
+    let cards = |cards: &[Card]| cards.iter().map(Card::to_string).collect();
+    let text = match peer {
+        Peer::Guest | Peer::Spectator => serde_json::to_string(&match message {
+            Message::Hand {
+                hand_number,
+                dealer,
+                stacks,
+                small_blind,
+                big_blind,
+            } => ServerMessage::Hand {
+                hand: *hand_number,
+                button: *dealer,
+                stacks: stacks.iter().map(|stack| stack.amount()).collect(),
+                blinds: [small_blind.amount(), big_blind.amount()],
+            },
+            Message::HoleCards(_) | Message::Welcome { .. } if peer == Peer::Spectator => {
+                return None
+            }
+            Message::HoleCards(hole_cards) => ServerMessage::Cards {
+                cards: cards(hole_cards),
+            },
+            Message::Act { seat, action } => ServerMessage::Act {
+                seat: *seat,
+                action: (*action).into(),
+            },
+            Message::Street(board) => ServerMessage::Street {
+                cards: cards(board),
+            },
+            Message::Showdown(shown) => ServerMessage::Showdown {
+                cards: cards(shown),
+            },
+            Message::Table(table) => ServerMessage::Table(table.clone()),
+            Message::Watching => ServerMessage::Watching,
+            Message::Chat { seat, text } => ServerMessage::Chat {
+                seat: *seat,
+                text: text.clone(),
+            },
+            Message::Welcome { token } => ServerMessage::Welcome {
+                token: token.clone(),
+            },
+            Message::Rejoin { .. } => return None,
+        }),
+        Peer::Host => match message {
+            Message::Act { action, .. } => serde_json::to_string(&ClientMessage::Act {
+                action: (*action).into(),
+            }),
+            Message::Chat { text, .. } => {
+                serde_json::to_string(&ClientMessage::Chat { text: text.clone() })
+            }
+            Message::Rejoin { token } => serde_json::to_string(&ClientMessage::Rejoin {
+                token: token.clone(),
+            }),
+            _ => return None,
+        },
+    };
+    text.ok()
+}
+
+/// A line or frame from `peer`, or `None` if it can't be read.
+pub fn decode(text: &str, peer: Peer) -> Option<Received> {
+    let cards = |names: Vec<String>| parse_cards(names.iter().map(String::as_str));
+    let message = match peer {
+        Peer::Host => match serde_json::from_str(text).ok()? {
+            ServerMessage::Hello { version } => return Some(Received::Hello { version }),
+            ServerMessage::Hand {
+                hand,
+                button,
+                stacks,
+                blinds: [small_blind, big_blind],
+            } => Message::Hand {
+                hand_number: hand,
+                dealer: button,
+                stacks: stacks.into_iter().map(Chips::new).collect(),
+                small_blind: Chips::new(small_blind),
+                big_blind: Chips::new(big_blind),
+            },
+            ServerMessage::Cards { cards: names } => Message::HoleCards(cards(names)?),
+            ServerMessage::Act { seat, action } => Message::Act {
+                seat,
+                action: action.into(),
+            },
+            ServerMessage::Street { cards: names } => Message::Street(cards(names)?),
+            ServerMessage::Showdown { cards: names } => Message::Showdown(cards(names)?),
+            ServerMessage::Table(table) => Message::Table(table),
+            ServerMessage::Watching => Message::Watching,
+            ServerMessage::Chat { seat, text } => Message::Chat { seat, text },
+            ServerMessage::Welcome { token } => Message::Welcome { token },
+            ServerMessage::Unknown => return Some(Received::Skipped),
+        },
+        Peer::Guest | Peer::Spectator => match serde_json::from_str(text).ok()? {
+            ClientMessage::Hello { version } => return Some(Received::Hello { version }),
+            // The guest's own seat, as it sees the table.
+            ClientMessage::Act { action } => Message::Act {
+                seat: 0,
+                action: action.into(),
+            },
+            ClientMessage::Chat { text } => Message::Chat { seat: 0, text },
+            ClientMessage::Rejoin { token } => Message::Rejoin { token },
+            ClientMessage::Unknown => return Some(Received::Skipped),
+        },
+    };
+    Some(Received::Message(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::full_deck;
+
+    #[test]
+    fn test_messages_survive_the_wire_both_ways() {
+        let messages = [
+            Message::Hand {
+                hand_number: 12,
+                dealer: 1,
+                stacks: vec![Chips::new(1000), Chips::new(990)],
+                small_blind: Chips::new(5),
+                big_blind: Chips::new(10),
+            },
+            Message::HoleCards(full_deck()[..2].to_vec()),
+            Message::Act {
+                seat: 1,
+                action: Action::Raise(Chips::new(40)),
+            },
+            Message::Street(full_deck()[40..43].to_vec()),
+            Message::Showdown(Vec::new()),
+            Message::Table(TableSnapshot::seen_by(&PokerGame::new(), 1)),
+            Message::Watching,
+            Message::Chat {
+                seat: 1,
+                text: "nh".to_string(),
+            },
+            Message::Welcome {
+                token: "5eat".to_string(),
+            },
+        ];
+        for message in messages {
+            let text = encode(&message, Peer::Guest).unwrap();
+            assert_eq!(decode(&text, Peer::Host), Some(Received::Message(message)));
+        }
+
+        let fold = Message::Act {
+            seat: 0,
+            action: Action::Fold,
+        };
+        let text = encode(&fold, Peer::Host).unwrap();
+        assert_eq!(text, r#"{"type":"act","action":"fold"}"#);
+        assert_eq!(decode(&text, Peer::Guest), Some(Received::Message(fold)));
+        let chat = Message::Chat {
+            seat: 0,
+            text: "gl".to_string(),
+        };
+        let text = encode(&chat, Peer::Host).unwrap();
+        assert_eq!(text, r#"{"type":"chat","text":"gl"}"#);
+        assert_eq!(decode(&text, Peer::Guest), Some(Received::Message(chat)));
+        let rejoin = Message::Rejoin {
+            token: "5eat".to_string(),
+        };
+        let text = encode(&rejoin, Peer::Host).unwrap();
+        assert_eq!(decode(&text, Peer::Guest), Some(Received::Message(rejoin)));
+        let welcome = Message::Welcome {
+            token: "5eat".to_string(),
+        };
+        assert_eq!(encode(&welcome, Peer::Spectator), None);
+        assert_eq!(encode(&Message::Showdown(Vec::new()), Peer::Host), None);
+        let hole_cards = Message::HoleCards(full_deck()[..2].to_vec());
+        assert_eq!(encode(&hole_cards, Peer::Spectator), None);
+        assert_eq!(
+            encode(
+                &Message::Act {
+                    seat: 0,
+                    action: Action::Bet(Chips::new(30))
+                },
+                Peer::Guest
+            )
+            .unwrap(),
+            r#"{"type":"act","seat":0,"action":"bet","amount":30}"#
+        );
+    }
+
+    #[test]
+    fn test_older_and_newer_peers_are_still_understood() {
+        assert_eq!(
+            decode(&hello(Peer::Host), Peer::Guest),
+            Some(Received::Hello {
+                version: PROTOCOL_VERSION
+            })
+        );
+        // Fields and messages from a later version are skipped.
+        assert_eq!(
+            decode(
+                r#"{"type":"act","action":"raise","amount":40,"seat":0,"think_ms":900}"#,
+                Peer::Guest
+            ),
+            Some(Received::Message(Message::Act {
+                seat: 0,
+                action: Action::Raise(Chips::new(40))
+            }))
+        );
+        assert_eq!(
+            decode(r#"{"type":"emote","emote":"gg"}"#, Peer::Host),
+            Some(Received::Skipped)
+        );
+        // Fields a peer doesn't send take their defaults.
+        let Some(Received::Message(Message::Table(table))) =
+            decode(r#"{"type":"table","hand":3,"pot":30}"#, Peer::Host)
+        else {
+            panic!("table snapshot not read");
+        };
+        assert_eq!((table.hand, table.pot, table.to_act), (3, 30, None));
+        assert!(table.seats.is_empty());
+
+        assert_eq!(decode("not json", Peer::Host), None);
+        assert_eq!(
+            decode(r#"{"type":"cards","cards":["Z♠"]}"#, Peer::Host),
+            None
+        );
+    }
+
+    #[test]
+    fn test_snapshot_hides_the_other_seats_cards() {
+        let mut game = PokerGame::new();
+        game.start_hand_seeded(5);
+        let table = TableSnapshot::seen_by(&game, 1);
+        assert_eq!(table.seats[0].name, game.players[1].name);
+        assert_eq!(table.seats[0].cards.len(), 2);
+        assert!(table.seats[1].cards.is_empty());
+        assert_eq!(table.button, 1 - game.dealer_position);
+        assert_eq!(table.to_act, Some(1 - game.current_player));
+
+        let watched = TableSnapshot::watched(&game);
+        assert_eq!(watched.seats[0].name, game.players[0].name);
+        assert!(watched.seats.iter().all(|seat| seat.cards.is_empty()));
+        assert_eq!(watched.button, game.dealer_position);
+    }
+}
@@ -0,0 +1,151 @@
+//! The table over a WebSocket, for clients in a browser. The host's lobby
+//! listens on [`super::WEBSOCKET_PORT`]; a client that connects takes the
+//! guest's seat, with one message from [`super::protocol`] per text frame.
+//!
+//! The client is seat 0 and the host seat 1. A card is its rank (`2`–`10`,
+//! `J`, `Q`, `K`, `A`) then its suit (`♠`, `♥`, `♦`, `♣`). The client
+//! should wait for its own `act` to come back before acting again. A client
+//! told `watching` is a spectator instead: seat 0 is then the host, and its
+//! `act`s are ignored.
+
+use super::protocol::Peer;
+use super::{deliver, Connection};
+use std::io;
+use std::net::TcpStream;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
+use tracing::warn;
+use tungstenite::Message as Frame;
+
+// How long a read waits for the client before queued messages go out.
+const READ_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// Takes a client that has just connected. The handshake and everything
+/// after it happen on a thread, so a bad handshake shows up as the client
+/// leaving.
+pub(super) fn accept(stream: TcpStream) -> io::Result<Connection> {
+    stream.set_nodelay(true)?;
+    stream.set_nonblocking(false)?;
+    let socket = stream.try_clone()?;
+    let (received, incoming) = mpsc::channel();
+    let (outgoing, to_send) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        let mut websocket = match tungstenite::accept(stream) {
+            Ok(websocket) => websocket,
+            Err(error) => {
+                warn!(%error, "WebSocket handshake failed");
+                return;
+            }
+        };
+        if let Err(error) = websocket.get_ref().set_read_timeout(Some(READ_TIMEOUT)) {
+            warn!(%error, "cannot poll the WebSocket");
+            return;
+        }
+        loop {
+            loop {
+                match to_send.try_recv() {
+                    Ok(text) => {
+                        if websocket.send(Frame::text(text)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let _ = websocket.close(None);
+                        return;
+                    }
+                }
+            }
+            match websocket.read() {
+                Ok(Frame::Text(text)) => {
+                    if !deliver(&text, Peer::Guest, &received) {
+                        return;
+                    }
+                }
+                Ok(Frame::Close(_)) => return,
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(error))
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) => {}
+                Err(_) => return,
+            }
+        }
+    });
+    Ok(Connection::opened(Peer::Guest, outgoing, incoming, socket))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::protocol::{self, Received, PROTOCOL_VERSION};
+    use crate::network::Message;
+    use poker_engine::{Action, Chips};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_browser_client_plays_through_the_websocket() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let client = std::thread::spawn(move || {
+            let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            let url = format!("ws://127.0.0.1:{}", port);
+            let (mut websocket, _) = tungstenite::client(url, stream).unwrap();
+            let hello = websocket.read().unwrap().into_text().unwrap();
+            let hand = websocket.read().unwrap().into_text().unwrap();
+            websocket
+                .send(Frame::text(
+                    r#"{"type": "act", "action": "raise", "amount": 40}"#,
+                ))
+                .unwrap();
+            websocket.send(Frame::text("not json")).unwrap();
+            websocket.send(Frame::text(r#"{"type": "act"}"#)).unwrap();
+            websocket
+                .send(Frame::text(r#"{"type": "act", "action": "fold"}"#))
+                .unwrap();
+            (hello, hand)
+        });
+
+        let connection = accept(listener.accept().unwrap().0).unwrap();
+        let hand = Message::Hand {
+            hand_number: 12,
+            dealer: 1,
+            stacks: vec![Chips::new(1000), Chips::new(990)],
+            small_blind: Chips::new(5),
+            big_blind: Chips::new(10),
+        };
+        connection.send(&hand).unwrap();
+        let (hello, sent) = client.join().unwrap();
+        assert_eq!(
+            protocol::decode(&hello, Peer::Host),
+            Some(Received::Hello {
+                version: PROTOCOL_VERSION
+            })
+        );
+        assert_eq!(
+            protocol::decode(&sent, Peer::Host),
+            Some(Received::Message(hand))
+        );
+
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            if let Some(message) = connection.receive().unwrap() {
+                received.push(message);
+            }
+        }
+        assert_eq!(
+            received,
+            [
+                Message::Act {
+                    seat: 0,
+                    action: Action::Raise(Chips::new(40))
+                },
+                Message::Act {
+                    seat: 0,
+                    action: Action::Fold
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! The user's notes on each opponent, with a color label, kept in the
+//! profile's directory as `name = color | note` lines. Opponents are known
+//! by name, which for now is the bot's personality.
+
+use crate::profiles;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use tracing::warn;
+
+const NOTES_FILE: &str = "notes.txt";
+
+/// The labels a note can carry, in the order of the picker; "None" leaves it
+/// uncolored.
+pub const COLORS: [&str; 6] = ["None", "Red", "Orange", "Yellow", "Green", "Blue"];
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpponentNote {
+    pub color: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Notes {
+    notes: BTreeMap<String, OpponentNote>,
+}
+
+impl Notes {
+    /// `profile`'s notes; none if there is no file yet.
+    pub fn load(profile: &str) -> Self {
+        let Some(path) = profiles::path(profile, NOTES_FILE) else {
+            return Notes::default();
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Notes::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Notes::default(),
+            Err(error) => {
+                warn!(path = %path.display(), %error, "cannot read opponent notes");
+                Notes::default()
+            }
+        }
+    }
+
+    pub fn save(&self, profile: &str) -> io::Result<()> {
+        let path = profiles::path(profile, NOTES_FILE)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, self.to_text())
+    }
+
+    pub fn get(&self, opponent: &str) -> OpponentNote {
+        self.notes.get(opponent).cloned().unwrap_or_default()
+    }
+
+    /// Every note, by opponent.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &OpponentNote)> {
+        self.notes
+            .iter()
+            .map(|(opponent, note)| (opponent.as_str(), note))
+    }
+
+    /// Replaces the note on `opponent`. A note with no text and no color is
+    /// dropped; unknown colors become "None".
+    pub fn set(&mut self, opponent: &str, color: &str, text: &str) {
+        let color = if COLORS.contains(&color) {
+            color
+        } else {
+            "None"
+        };
+        // One line per note in the file.
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() && color == "None" {
+            self.notes.remove(opponent);
+            return;
+        }
+        self.notes.insert(
+            opponent.to_string(),
+            OpponentNote {
+                color: color.to_string(),
+                text,
+            },
+        );
+    }
+
+    fn parse(text: &str) -> Self {
+        let mut notes = Notes::default();
+        for line in text.lines() {
+            let Some((opponent, rest)) = line.split_once('=') else {
+                continue;
+            };
+            let (color, note) = rest.split_once('|').unwrap_or((rest, ""));
+            notes.set(opponent.trim(), color.trim(), note);
+        }
+        notes
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (opponent, note) in &self.notes {
+            let _ = writeln!(text, "{} = {} | {}", opponent, note.color, note.text);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_round_trip_and_drop_empty_ones() {
+        let mut notes = Notes::default();
+        notes.set("Maniac", "Red", "Shoves any ace.\nCalls light | river");
+        notes.set("Rock", "Purple", "Folds to 3-bets");
+        notes.set("Trapper", "None", "  ");
+        assert_eq!(notes.get("Rock").color, "None");
+        assert_eq!(notes.get("Trapper"), OpponentNote::default());
+        assert_eq!(
+            notes.get("Maniac").text,
+            "Shoves any ace. Calls light | river"
+        );
+        assert_eq!(Notes::parse(&notes.to_text()), notes);
+
+        notes.set("Maniac", "None", "");
+        assert_eq!(notes.notes.len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+//! Discord Rich Presence: "Playing Texas Hold'em — Turn, pot $340" on the
+//! user's Discord profile while the desktop app runs. It talks to the
+//! Discord client over its local IPC socket, and needs the app's Discord
+//! application id at build time in `POKER_DISCORD_APP_ID`.
+
+use poker_engine::{GamePhase, PokerGame};
+use serde_json::json;
+use std::cell::{Cell, RefCell};
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+const APPLICATION_ID: Option<&str> = option_env!("POKER_DISCORD_APP_ID");
+// Discord drops activity updates sent faster than about one per 4 seconds.
+const UPDATE_EVERY: Duration = Duration::from_secs(4);
+const RECONNECT_EVERY: Duration = Duration::from_secs(15);
+const HANDSHAKE: u32 = 0;
+const FRAME: u32 = 1;
+const CLOSE: u32 = 2;
+
+/// What the profile shows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Activity {
+    pub details: String,
+    pub state: String,
+}
+
+impl Activity {
+    pub fn at_table(game: &PokerGame) -> Self {
+        let state = if game.hand_complete {
+            "Between hands".to_string()
+        } else {
+            let street = match game.phase {
+                GamePhase::PreFlop => "Preflop",
+                GamePhase::Flop => "Flop",
+                GamePhase::Turn => "Turn",
+                GamePhase::River => "River",
+                GamePhase::Showdown => "Showdown",
+            };
+            format!("{}, pot {}", street, game.pot)
+        };
+        Activity {
+            details: "Playing Texas Hold'em".to_string(),
+            state,
+        }
+    }
+
+    pub fn in_menu() -> Self {
+        Activity {
+            details: "Texas Hold'em".to_string(),
+            state: "In the menu".to_string(),
+        }
+    }
+}
+
+/// The link to Discord, shared by every table. Updates are dropped while
+/// it's turned off.
+#[derive(Default)]
+pub struct Presence {
+    enabled: Cell<bool>,
+    // The worker thread, while enabled; it clears the activity and stops
+    // when this is dropped.
+    worker: RefCell<Option<Sender<Activity>>>,
+    last: RefCell<Option<Activity>>,
+}
+
+impl Presence {
+    pub fn set_enabled(&self, enabled: bool) {
+        if enabled == self.enabled.get() {
+            return;
+        }
+        self.enabled.set(enabled);
+        if !enabled {
+            self.worker.borrow_mut().take();
+            return;
+        }
+        let Some(application_id) = APPLICATION_ID else {
+            warn!("built without POKER_DISCORD_APP_ID, Discord presence disabled");
+            return;
+        };
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+        let (updates, received) = mpsc::channel();
+        std::thread::spawn(move || run(application_id, received));
+        if let Some(activity) = self.last.borrow().clone() {
+            let _ = updates.send(activity);
+        }
+        *self.worker.borrow_mut() = Some(updates);
+    }
+
+    pub fn show(&self, activity: Activity) {
+        if self.last.borrow().as_ref() == Some(&activity) {
+            return;
+        }
+        if let Some(worker) = self.worker.borrow().as_ref() {
+            let _ = worker.send(activity.clone());
+        }
+        *self.last.borrow_mut() = Some(activity);
+    }
+}
+
+fn run(application_id: &str, updates: Receiver<Activity>) {
+    let mut socket = None;
+    let mut pending = None;
+    let mut sent_at: Option<Instant> = None;
+    let mut connect_at = Instant::now();
+    loop {
+        match updates.recv_timeout(UPDATE_EVERY) {
+            Ok(activity) => pending = Some(activity),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        // Only the latest activity matters, so bursts of updates collapse.
+        while let Ok(activity) = updates.try_recv() {
+            pending = Some(activity);
+        }
+        if pending.is_none() || sent_at.is_some_and(|at| at.elapsed() < UPDATE_EVERY) {
+            continue;
+        }
+        if socket.is_none() && Instant::now() >= connect_at {
+            socket = match connect(application_id) {
+                Ok(socket) => {
+                    info!("connected to Discord");
+                    Some(socket)
+                }
+                Err(error) => {
+                    debug!(%error, "Discord isn't running");
+                    connect_at = Instant::now() + RECONNECT_EVERY;
+                    None
+                }
+            };
+        }
+        let Some(connected) = socket.as_mut() else {
+            continue;
+        };
+        let activity = pending.as_ref().map(|activity| {
+            json!({
+                "details": activity.details,
+                "state": activity.state,
+            })
+        });
+        // A failed update stays pending until Discord is back.
+        match set_activity(connected, activity) {
+            Ok(()) => pending = None,
+            Err(error) => {
+                warn!(%error, "lost the connection to Discord");
+                socket = None;
+                connect_at = Instant::now() + RECONNECT_EVERY;
+            }
+        }
+        sent_at = Some(Instant::now());
+    }
+    if let Some(mut socket) = socket {
+        let _ = set_activity(&mut socket, None);
+    }
+}
+
+fn set_activity(socket: &mut Socket, activity: Option<serde_json::Value>) -> io::Result<()> {
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string();
+    let command = json!({
+        "cmd": "SET_ACTIVITY",
+        "args": { "pid": std::process::id(), "activity": activity },
+        "nonce": nonce,
+    });
+    write_frame(socket, FRAME, &command)?;
+    read_frame(socket)
+}
+
+fn connect(application_id: &str) -> io::Result<Socket> {
+    let mut last_error = io::Error::from(io::ErrorKind::NotFound);
+    for path in socket_paths() {
+        match open(&path) {
+            Ok(mut socket) => {
+                write_frame(
+                    &mut socket,
+                    HANDSHAKE,
+                    &json!({ "v": 1, "client_id": application_id }),
+                )?;
+                // Discord answers with a READY frame, or closes on a bad id.
+                read_frame(&mut socket)?;
+                return Ok(socket);
+            }
+            Err(error) => last_error = error,
+        }
+    }
+    Err(last_error)
+}
+
+// Frames are an opcode and a length, both little-endian u32, then JSON.
+fn write_frame(socket: &mut Socket, opcode: u32, body: &serde_json::Value) -> io::Result<()> {
+    let body = body.to_string();
+    let mut frame = Vec::with_capacity(8 + body.len());
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(body.as_bytes());
+    socket.write_all(&frame)
+}
+
+fn read_frame(socket: &mut Socket) -> io::Result<()> {
+    let mut header = [0; 8];
+    socket.read_exact(&mut header)?;
+    let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    let mut body = vec![0; length as usize];
+    socket.read_exact(&mut body)?;
+    if opcode == CLOSE {
+        let reason = String::from_utf8_lossy(&body).into_owned();
+        return Err(io::Error::new(io::ErrorKind::ConnectionRefused, reason));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+type Socket = std::os::unix::net::UnixStream;
+#[cfg(not(unix))]
+type Socket = std::fs::File;
+
+#[cfg(unix)]
+fn socket_paths() -> Vec<std::path::PathBuf> {
+    let directory = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| "/tmp".into());
+    (0..10)
+        .map(|n| directory.join(format!("discord-ipc-{}", n)))
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn socket_paths() -> Vec<std::path::PathBuf> {
+    (0..10)
+        .map(|n| format!(r"\\?\pipe\discord-ipc-{}", n).into())
+        .collect()
+}
+
+#[cfg(unix)]
+fn open(path: &std::path::Path) -> io::Result<Socket> {
+    let socket = Socket::connect(path)?;
+    socket.set_read_timeout(Some(Duration::from_secs(5)))?;
+    Ok(socket)
+}
+
+#[cfg(not(unix))]
+fn open(path: &std::path::Path) -> io::Result<Socket> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::Chips;
+
+    #[test]
+    fn test_activity_names_the_street_and_pot() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        game.phase = GamePhase::Turn;
+        game.pot = Chips::new(340);
+        assert_eq!(
+            Activity::at_table(&game),
+            Activity {
+                details: "Playing Texas Hold'em".to_string(),
+                state: "Turn, pot $340".to_string(),
+            }
+        );
+        game.hand_complete = true;
+        assert_eq!(Activity::at_table(&game).state, "Between hands");
+    }
+}
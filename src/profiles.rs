@@ -0,0 +1,102 @@
+//! People sharing the game on one machine. Each profile is a directory
+//! under `slint-poker/profiles` in the user's config directory, holding its
+//! own settings and saved match.
+
+use std::io;
+use std::path::PathBuf;
+use tracing::warn;
+
+const MAX_NAME_LEN: usize = 24;
+
+/// The existing profiles, sorted by name.
+pub fn list() -> Vec<String> {
+    let Some(dir) = profiles_dir() else {
+        return Vec::new();
+    };
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(error) => {
+            warn!(path = %dir.display(), %error, "cannot list profiles");
+            return Vec::new();
+        }
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| clean_name(name).as_deref() == Some(name.as_str()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Makes a profile called `name`, or finds the one that already exists, and
+/// returns the name it was stored under.
+pub fn create(name: &str) -> io::Result<String> {
+    let name = clean_name(name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "use letters, digits, spaces, - or _",
+        )
+    })?;
+    let Some(dir) = profiles_dir() else {
+        // A browser has nowhere to keep files, so there profiles only last
+        // the session.
+        if cfg!(target_arch = "wasm32") {
+            return Ok(name);
+        }
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no config directory",
+        ));
+    };
+    std::fs::create_dir_all(dir.join(&name))?;
+    Ok(name)
+}
+
+/// `file` in `profile`'s directory.
+pub fn path(profile: &str, file: &str) -> Option<PathBuf> {
+    Some(profiles_dir()?.join(profile).join(file))
+}
+
+/// `file` next to the profiles, for data every profile shares.
+#[cfg_attr(not(feature = "stats-db"), allow(dead_code))]
+pub fn shared_path(file: &str) -> Option<PathBuf> {
+    Some(app_dir()?.join(file))
+}
+
+fn profiles_dir() -> Option<PathBuf> {
+    Some(app_dir()?.join("profiles"))
+}
+
+fn app_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| std::env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("slint-poker"))
+}
+
+/// `name` trimmed, if it is short and safe to use as a directory name.
+fn clean_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    let safe = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    (safe && !name.is_empty() && name.chars().count() <= MAX_NAME_LEN).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_are_trimmed_and_kept_safe() {
+        assert_eq!(clean_name("  Ana María "), Some("Ana María".to_string()));
+        assert_eq!(clean_name("big_stack-99"), Some("big_stack-99".to_string()));
+        assert_eq!(clean_name("../settings"), None);
+        assert_eq!(clean_name("   "), None);
+        assert_eq!(clean_name(&"x".repeat(MAX_NAME_LEN + 1)), None);
+    }
+}
@@ -0,0 +1,237 @@
+//! A match put aside with "Save & Quit", kept as `key = value` lines in the
+//! profile's directory. The hand in progress is not saved: the match resumes from
+//! the stacks it started with, and the save is used up once loaded.
+//!
+//! Also the goals the user sets for a sitting, and how far along it is.
+
+use crate::game_mode::GameMode;
+use crate::profiles;
+use poker_engine::{Chips, PokerGame};
+use std::fmt::Write as _;
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tracing::warn;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+const SESSION_FILE: &str = "session.txt";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavedMatch {
+    pub mode: GameMode,
+    pub player_chips: u32,
+    pub bot_chips: u32,
+    pub dealer_position: usize,
+    /// Hands finished before the one that was in progress.
+    pub hands_played: u32,
+}
+
+impl SavedMatch {
+    pub fn from_game(game: &PokerGame, mode: GameMode) -> Self {
+        SavedMatch {
+            mode,
+            player_chips: game.hand_start_chips[0].amount(),
+            bot_chips: game.hand_start_chips[1].amount(),
+            dealer_position: game.dealer_position,
+            hands_played: game.hand_number.saturating_sub(1),
+        }
+    }
+
+    /// Puts the stacks and the button back; the next hand dealt is the one
+    /// that was interrupted.
+    pub fn apply(&self, game: &mut PokerGame) {
+        game.players[0].chips = Chips::new(self.player_chips);
+        game.players[1].chips = Chips::new(self.bot_chips);
+        game.dealer_position = self.dealer_position % game.players.len();
+        game.hand_number = self.hands_played;
+    }
+
+    /// Reads and removes `profile`'s saved match, if there is one.
+    pub fn take(profile: &str) -> Option<Self> {
+        let path = profiles::path(profile, SESSION_FILE)?;
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                warn!(path = %path.display(), %error, "cannot read saved match");
+                return None;
+            }
+        };
+        if let Err(error) = std::fs::remove_file(&path) {
+            warn!(path = %path.display(), %error, "cannot remove saved match");
+        }
+        SavedMatch::parse(&text)
+    }
+
+    pub fn save(&self, profile: &str) -> io::Result<()> {
+        let path = profiles::path(profile, SESSION_FILE)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, self.to_text())
+    }
+
+    /// Every key but the mode is required; a save missing one is ignored.
+    fn parse(text: &str) -> Option<Self> {
+        let value = |key: &str| {
+            text.lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(name, _)| name.trim() == key)
+                .map(|(_, value)| value.trim())
+        };
+        let saved = SavedMatch {
+            mode: value("mode")
+                .and_then(GameMode::from_name)
+                .unwrap_or_default(),
+            player_chips: value("player_chips")?.parse().ok()?,
+            bot_chips: value("bot_chips")?.parse().ok()?,
+            dealer_position: value("dealer_position")?.parse().ok()?,
+            hands_played: value("hands_played")?.parse().ok()?,
+        };
+        (saved.player_chips > 0 && saved.bot_chips > 0).then_some(saved)
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "mode = {}", self.mode.name());
+        let _ = writeln!(text, "player_chips = {}", self.player_chips);
+        let _ = writeln!(text, "bot_chips = {}", self.bot_chips);
+        let _ = writeln!(text, "dealer_position = {}", self.dealer_position);
+        let _ = writeln!(text, "hands_played = {}", self.hands_played);
+        text
+    }
+}
+
+/// Limits the user sets for a sitting, kept with the settings; zero means
+/// no limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SessionGoals {
+    pub minutes: u32,
+    pub hands: u32,
+    pub profit: u32,
+    pub stop_loss: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Goal {
+    StopLoss,
+    Profit,
+    Hands,
+    Time,
+}
+
+impl SessionGoals {
+    /// The first goal met by a sitting `minutes` long with `hands` played
+    /// and `net` chips won, the stop-loss first.
+    pub fn reached(&self, minutes: u32, hands: u32, net: i32) -> Option<Goal> {
+        let met = |limit: u32, value: u32| limit > 0 && value >= limit;
+        if met(self.stop_loss, net.min(0).unsigned_abs()) {
+            Some(Goal::StopLoss)
+        } else if met(self.profit, net.max(0) as u32) {
+            Some(Goal::Profit)
+        } else if met(self.hands, hands) {
+            Some(Goal::Hands)
+        } else if met(self.minutes, minutes) {
+            Some(Goal::Time)
+        } else {
+            None
+        }
+    }
+
+    pub fn describe(&self, goal: Goal) -> String {
+        match goal {
+            Goal::StopLoss => format!("You've lost ${}, your stop-loss.", self.stop_loss),
+            Goal::Profit => format!("You've reached your ${} profit target.", self.profit),
+            Goal::Hands => format!("You've played your {} hands.", self.hands),
+            Goal::Time => format!("You've played for {} minutes.", self.minutes),
+        }
+    }
+}
+
+/// A sitting measured against the goals: when it began, from which hand and
+/// with how many chips. Each sitting warns once.
+#[derive(Clone, Copy, Debug)]
+pub struct SessionTracker {
+    started: Instant,
+    hand_number: u32,
+    chips: i32,
+    warned: bool,
+}
+
+impl SessionTracker {
+    pub fn new(game: &PokerGame) -> Self {
+        SessionTracker {
+            started: Instant::now(),
+            hand_number: game.hand_number,
+            chips: game.players[0].chips.as_i32(),
+            warned: false,
+        }
+    }
+
+    /// The goal the finished hand in `game` reached, unless the sitting has
+    /// warned already.
+    pub fn check(&mut self, goals: &SessionGoals, game: &PokerGame) -> Option<Goal> {
+        if self.warned {
+            return None;
+        }
+        let minutes = (self.started.elapsed().as_secs() / 60) as u32;
+        let hands = game.hand_number.saturating_sub(self.hand_number);
+        let net = game.players[0].chips.as_i32() - self.chips;
+        let goal = goals.reached(minutes, hands, net);
+        self.warned = goal.is_some();
+        goal
+    }
+
+    /// Counts chips handed to the user after a bust as bought, not won.
+    pub fn rebuy(&mut self, chips: i32) {
+        self.chips += chips;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_match_resumes_the_interrupted_hand() {
+        let mut game = PokerGame::new();
+        game.start_hand_seeded(7);
+        game.start_hand_seeded(8);
+        let saved = SavedMatch::from_game(&game, GameMode::SitAndGo);
+        assert_eq!(SavedMatch::parse(&saved.to_text()), Some(saved.clone()));
+
+        let mut resumed = PokerGame::new();
+        saved.apply(&mut resumed);
+        resumed.start_hand_seeded(9);
+        assert_eq!(resumed.hand_number, game.hand_number);
+        assert_eq!(resumed.dealer_position, game.dealer_position);
+        assert_eq!(resumed.hand_start_chips, game.hand_start_chips);
+
+        assert_eq!(SavedMatch::parse("player_chips = 500\nbot_chips = 0"), None);
+    }
+
+    #[test]
+    fn test_session_warns_once_at_the_first_goal() {
+        let goals = SessionGoals {
+            hands: 2,
+            profit: 100,
+            stop_loss: 200,
+            ..SessionGoals::default()
+        };
+        assert_eq!(goals.reached(90, 1, -50), None);
+        assert_eq!(goals.reached(0, 5, -200), Some(Goal::StopLoss));
+        assert_eq!(goals.reached(0, 5, 150), Some(Goal::Profit));
+        assert_eq!(goals.reached(0, 2, 0), Some(Goal::Hands));
+
+        let mut game = PokerGame::new();
+        let mut tracker = SessionTracker::new(&game);
+        game.start_hand_seeded(1);
+        assert_eq!(tracker.check(&goals, &game), None);
+        game.start_hand_seeded(2);
+        assert_eq!(tracker.check(&goals, &game), Some(Goal::Hands));
+        game.start_hand_seeded(3);
+        assert_eq!(tracker.check(&goals, &game), None);
+    }
+}
@@ -0,0 +1,282 @@
+//! The settings panel's choices, saved as `key = value` lines in the
+//! profile's `settings.txt` so they survive a restart. Unknown keys and
+//! unreadable values are skipped, and a missing file just means the
+//! defaults.
+
+use crate::hotkeys::{HotkeyAction, Hotkeys};
+use crate::profiles;
+use crate::session::SessionGoals;
+use std::fmt::Write as _;
+use std::io;
+use tracing::warn;
+
+const SETTINGS_FILE: &str = "settings.txt";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub fullscreen: bool,
+    pub commentary: bool,
+    pub show_hud: bool,
+    pub mute_emotes: bool,
+    pub show_mucked_cards: bool,
+    pub touch_layout: bool,
+    pub animation_speed: String,
+    /// Where the user's seat sits: "Bottom", "Left" or "Right".
+    pub seat_side: String,
+    /// "Pause" or "Keep playing".
+    pub while_minimized: String,
+    pub hotkeys: Hotkeys,
+    pub goals: SessionGoals,
+    pub confirm_free_fold: bool,
+    pub music_enabled: bool,
+    pub music_track: i32,
+    pub music_volume: f32,
+    pub avatar: String,
+    pub theme: String,
+    pub card_skin: String,
+    pub four_color: bool,
+    pub card_back: String,
+    pub felt: String,
+    pub texture: String,
+    /// Path to an image drawn behind the table; empty for none.
+    pub background_image: String,
+    /// The window's size and position when last out of fullscreen, in
+    /// physical pixels; `None` leaves it to the platform.
+    pub window_size: Option<(u32, u32)>,
+    pub window_position: Option<(i32, i32)>,
+    pub show_action_log: bool,
+    pub show_decision_log: bool,
+    pub last_hand_expanded: bool,
+    /// Where to find players online, as `host:port`; empty until set.
+    pub rendezvous_server: String,
+    pub discord_presence: bool,
+    pub stream_overlay: bool,
+    /// Where to sync this profile, as an `https://` address; empty to keep
+    /// it on this machine.
+    pub sync_server: String,
+    pub sync_token: String,
+}
+
+impl Default for Settings {
+    // Matches the defaults in ui.slint.
+    fn default() -> Self {
+        Settings {
+            fullscreen: false,
+            commentary: true,
+            show_hud: true,
+            mute_emotes: false,
+            show_mucked_cards: false,
+            touch_layout: false,
+            animation_speed: "Normal".to_string(),
+            seat_side: "Bottom".to_string(),
+            while_minimized: "Pause".to_string(),
+            hotkeys: Hotkeys::default(),
+            goals: SessionGoals::default(),
+            confirm_free_fold: true,
+            music_enabled: false,
+            music_track: 0,
+            music_volume: 50.0,
+            avatar: "Fox".to_string(),
+            theme: "Dark".to_string(),
+            card_skin: "Classic".to_string(),
+            four_color: false,
+            card_back: "Blue panel".to_string(),
+            felt: "Theme".to_string(),
+            texture: "Plain".to_string(),
+            background_image: String::new(),
+            window_size: None,
+            window_position: None,
+            show_action_log: false,
+            show_decision_log: false,
+            last_hand_expanded: false,
+            rendezvous_server: String::new(),
+            discord_presence: false,
+            stream_overlay: false,
+            sync_server: String::new(),
+            sync_token: String::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Reads `profile`'s saved settings, falling back to the defaults.
+    pub fn load(profile: &str) -> Self {
+        let Some(path) = profiles::path(profile, SETTINGS_FILE) else {
+            return Settings::default();
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Settings::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Settings::default(),
+            Err(error) => {
+                warn!(path = %path.display(), %error, "cannot read settings");
+                Settings::default()
+            }
+        }
+    }
+
+    pub fn save(&self, profile: &str) {
+        let Some(path) = profiles::path(profile, SETTINGS_FILE) else {
+            warn!("no config directory, settings not saved");
+            return;
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, self.to_text()));
+        if let Err(error) = written {
+            warn!(path = %path.display(), %error, "cannot save settings");
+        }
+    }
+
+    fn parse(text: &str) -> Self {
+        let mut settings = Settings::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "fullscreen" => set_parsed(&mut settings.fullscreen, value),
+                "commentary" => set_parsed(&mut settings.commentary, value),
+                "show_hud" => set_parsed(&mut settings.show_hud, value),
+                "mute_emotes" => set_parsed(&mut settings.mute_emotes, value),
+                "show_mucked_cards" => set_parsed(&mut settings.show_mucked_cards, value),
+                "touch_layout" => set_parsed(&mut settings.touch_layout, value),
+                "animation_speed" => settings.animation_speed = value.to_string(),
+                "seat_side" => settings.seat_side = value.to_string(),
+                "while_minimized" => settings.while_minimized = value.to_string(),
+                "confirm_free_fold" => set_parsed(&mut settings.confirm_free_fold, value),
+                "music_enabled" => set_parsed(&mut settings.music_enabled, value),
+                "music_track" => set_parsed(&mut settings.music_track, value),
+                "music_volume" => set_parsed(&mut settings.music_volume, value),
+                "avatar" => settings.avatar = value.to_string(),
+                "theme" => settings.theme = value.to_string(),
+                "card_skin" => settings.card_skin = value.to_string(),
+                "four_color" => set_parsed(&mut settings.four_color, value),
+                "card_back" => settings.card_back = value.to_string(),
+                "felt" => settings.felt = value.to_string(),
+                "texture" => settings.texture = value.to_string(),
+                "background_image" => settings.background_image = value.to_string(),
+                "window_size" => settings.window_size = parse_pair(value, 'x'),
+                "window_position" => settings.window_position = parse_pair(value, ','),
+                "show_action_log" => set_parsed(&mut settings.show_action_log, value),
+                "show_decision_log" => set_parsed(&mut settings.show_decision_log, value),
+                "last_hand_expanded" => set_parsed(&mut settings.last_hand_expanded, value),
+                "rendezvous_server" => settings.rendezvous_server = value.to_string(),
+                "discord_presence" => set_parsed(&mut settings.discord_presence, value),
+                "stream_overlay" => set_parsed(&mut settings.stream_overlay, value),
+                "sync_server" => settings.sync_server = value.to_string(),
+                "sync_token" => settings.sync_token = value.to_string(),
+                "goal_minutes" => set_parsed(&mut settings.goals.minutes, value),
+                "goal_hands" => set_parsed(&mut settings.goals.hands, value),
+                "goal_profit" => set_parsed(&mut settings.goals.profit, value),
+                "goal_stop_loss" => set_parsed(&mut settings.goals.stop_loss, value),
+                key if key.starts_with("hotkey_") => settings.hotkeys.parse_setting(key, value),
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "fullscreen = {}", self.fullscreen);
+        let _ = writeln!(text, "commentary = {}", self.commentary);
+        let _ = writeln!(text, "show_hud = {}", self.show_hud);
+        let _ = writeln!(text, "mute_emotes = {}", self.mute_emotes);
+        let _ = writeln!(text, "show_mucked_cards = {}", self.show_mucked_cards);
+        let _ = writeln!(text, "touch_layout = {}", self.touch_layout);
+        let _ = writeln!(text, "animation_speed = {}", self.animation_speed);
+        let _ = writeln!(text, "seat_side = {}", self.seat_side);
+        let _ = writeln!(text, "while_minimized = {}", self.while_minimized);
+        for action in HotkeyAction::ALL {
+            let _ = writeln!(text, "{} = {}", action.setting(), self.hotkeys.key(action));
+        }
+        let _ = writeln!(text, "goal_minutes = {}", self.goals.minutes);
+        let _ = writeln!(text, "goal_hands = {}", self.goals.hands);
+        let _ = writeln!(text, "goal_profit = {}", self.goals.profit);
+        let _ = writeln!(text, "goal_stop_loss = {}", self.goals.stop_loss);
+        let _ = writeln!(text, "confirm_free_fold = {}", self.confirm_free_fold);
+        let _ = writeln!(text, "music_enabled = {}", self.music_enabled);
+        let _ = writeln!(text, "music_track = {}", self.music_track);
+        let _ = writeln!(text, "music_volume = {}", self.music_volume);
+        let _ = writeln!(text, "avatar = {}", self.avatar);
+        let _ = writeln!(text, "theme = {}", self.theme);
+        let _ = writeln!(text, "card_skin = {}", self.card_skin);
+        let _ = writeln!(text, "four_color = {}", self.four_color);
+        let _ = writeln!(text, "card_back = {}", self.card_back);
+        let _ = writeln!(text, "felt = {}", self.felt);
+        let _ = writeln!(text, "texture = {}", self.texture);
+        let _ = writeln!(text, "background_image = {}", self.background_image);
+        if let Some((width, height)) = self.window_size {
+            let _ = writeln!(text, "window_size = {}x{}", width, height);
+        }
+        if let Some((x, y)) = self.window_position {
+            let _ = writeln!(text, "window_position = {},{}", x, y);
+        }
+        let _ = writeln!(text, "show_action_log = {}", self.show_action_log);
+        let _ = writeln!(text, "show_decision_log = {}", self.show_decision_log);
+        let _ = writeln!(text, "last_hand_expanded = {}", self.last_hand_expanded);
+        let _ = writeln!(text, "rendezvous_server = {}", self.rendezvous_server);
+        let _ = writeln!(text, "discord_presence = {}", self.discord_presence);
+        let _ = writeln!(text, "stream_overlay = {}", self.stream_overlay);
+        let _ = writeln!(text, "sync_server = {}", self.sync_server);
+        let _ = writeln!(text, "sync_token = {}", self.sync_token);
+        text
+    }
+}
+
+fn set_parsed<T: std::str::FromStr>(field: &mut T, value: &str) {
+    if let Ok(parsed) = value.parse() {
+        *field = parsed;
+    }
+}
+
+/// Two numbers written like "1280x800" or "40,30".
+fn parse_pair<T: std::str::FromStr>(value: &str, separator: char) -> Option<(T, T)> {
+    let (first, second) = value.split_once(separator)?;
+    Some((first.trim().parse().ok()?, second.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip_and_skip_bad_lines() {
+        let settings = Settings {
+            music_enabled: true,
+            felt: "Burgundy".to_string(),
+            background_image: "/home/me/my table.png".to_string(),
+            rendezvous_server: "poker.example.org:47480".to_string(),
+            discord_presence: true,
+            stream_overlay: true,
+            sync_server: "https://sync.example.org".to_string(),
+            sync_token: "s3cret".to_string(),
+            window_size: Some((1280, 800)),
+            window_position: Some((-40, 30)),
+            show_decision_log: true,
+            seat_side: "Left".to_string(),
+            goals: SessionGoals {
+                stop_loss: 300,
+                ..SessionGoals::default()
+            },
+            hotkeys: {
+                let mut hotkeys = Hotkeys::default();
+                hotkeys.bind(HotkeyAction::Fold, "x").unwrap();
+                hotkeys
+            },
+            ..Settings::default()
+        };
+        assert_eq!(Settings::parse(&settings.to_text()), settings);
+
+        let parsed =
+            Settings::parse("music_volume = loud\ncolour = red\nno separator\ntheme = Light");
+        assert_eq!(parsed.music_volume, Settings::default().music_volume);
+        assert_eq!(parsed.theme, "Light");
+
+        let parsed = Settings::parse("window_size = wide\nwindow_position = 10,20");
+        assert_eq!(parsed.window_size, None);
+        assert_eq!(parsed.window_position, Some((10, 20)));
+    }
+}
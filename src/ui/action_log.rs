@@ -0,0 +1,112 @@
+use poker_engine::{Action, Card, Chips, GameEvent, GamePhase, PokerGame};
+
+/// One line per thing that happened this hand, oldest first, written from
+/// the user's point of view ("You call $40", "Flop: K♠ 7♦ 2♣").
+pub fn action_log(game: &PokerGame) -> Vec<String> {
+    let mut board_cards = 0;
+    let mut blinds_posted = 0;
+    let mut lines = Vec::new();
+    for event in game.events() {
+        let line = match event {
+            GameEvent::HandStarted { hand_number, .. } => format!("Hand #{}", hand_number),
+            GameEvent::BlindPosted { seat, amount } => {
+                blinds_posted += 1;
+                let blind = if blinds_posted == 1 { "small" } else { "big" };
+                format!(
+                    "{} the {} blind {}",
+                    subject(game, *seat, "post", "posts"),
+                    blind,
+                    amount
+                )
+            }
+            GameEvent::CardsDealt {
+                seat: Some(seat),
+                cards,
+            } if game.players[*seat].is_user => format!("You are dealt {}", cards_text(cards)),
+            GameEvent::CardsDealt { seat: Some(_), .. } => continue,
+            GameEvent::CardsDealt { seat: None, cards } => {
+                let street = match board_cards {
+                    0 => "Flop",
+                    3 => "Turn",
+                    _ => "River",
+                };
+                board_cards += cards.len();
+                format!("{}: {}", street, cards_text(cards))
+            }
+            GameEvent::PlayerActed {
+                seat,
+                action,
+                amount,
+            } => describe_action(game, *seat, *action, *amount),
+            GameEvent::PhaseAdvanced {
+                phase: GamePhase::Showdown,
+            } => "Showdown".to_string(),
+            GameEvent::PhaseAdvanced { .. } | GameEvent::StateRestored => continue,
+            GameEvent::PotAwarded {
+                seat,
+                amount,
+                pot: 0,
+            } => format!("{} {}", subject(game, *seat, "win", "wins"), amount),
+            GameEvent::PotAwarded { seat, amount, .. } => format!(
+                "{} {} from the side pot",
+                subject(game, *seat, "take", "takes"),
+                amount
+            ),
+        };
+        lines.push(line);
+    }
+    lines
+}
+
+pub fn describe_action(game: &PokerGame, seat: usize, action: Action, amount: Chips) -> String {
+    match action {
+        Action::Fold => subject(game, seat, "fold", "folds"),
+        Action::Check => subject(game, seat, "check", "checks"),
+        Action::Call => format!("{} {}", subject(game, seat, "call", "calls"), amount),
+        Action::Bet(to) => format!("{} {}", subject(game, seat, "bet", "bets"), to),
+        Action::Raise(to) => format!("{} to {}", subject(game, seat, "raise", "raises"), to),
+        Action::AllIn => format!(
+            "{} all-in for {}",
+            subject(game, seat, "go", "goes"),
+            amount
+        ),
+    }
+}
+
+/// "You call" for the user, "Bot calls" for anyone else.
+pub fn subject(game: &PokerGame, seat: usize, user_verb: &str, other_verb: &str) -> String {
+    let player = &game.players[seat];
+    if player.is_user {
+        format!("You {}", user_verb)
+    } else {
+        format!("{} {}", player.name, other_verb)
+    }
+}
+
+fn cards_text(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(Card::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_follows_the_hand() {
+        let mut game = PokerGame::new();
+        game.start_hand_seeded(3);
+        let caller = game.players[game.current_player].is_user;
+        game.player_action(Action::Call).unwrap();
+
+        let log = action_log(&game);
+        assert!(log[0].starts_with("Hand #"));
+        assert!(log.iter().any(|line| line.contains("small blind $")));
+        assert!(log.iter().any(|line| line.starts_with("You are dealt ")));
+        let call = if caller { "You call $" } else { "calls $" };
+        assert!(log.last().unwrap().contains(call));
+    }
+}
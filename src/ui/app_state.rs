@@ -0,0 +1,1776 @@
+#[cfg(feature = "network")]
+use super::chat::Chat;
+use super::clock::TournamentClock;
+use super::commands::{ActionQueue, Command};
+use super::debug::Scenario;
+use super::diagnostics::Diagnostics;
+use super::emotes::{Emote, Emotes};
+use super::equity_meter::EquityMeter;
+#[cfg(feature = "network")]
+use super::lan::{self, Lan, LanEvent, Role};
+use super::minimize::MinimizeWatch;
+use super::models::hotkeys_model;
+use super::recap::recap;
+use super::share;
+use super::stats::{self, HandRecorder};
+use super::toasts::Toasts;
+use super::tutorial;
+use super::view::TableView;
+use super::worker;
+#[cfg(feature = "network")]
+use super::LobbyTableUI;
+use super::{
+    Avatars, CardBack, CardSkin, GameModeUI, HandRecapUI, MainWindow, Motion, TableStyle, Theme,
+};
+use crate::audio::{MusicPlayer, MusicSettings, TRACKS};
+use crate::bookmarks;
+use crate::cloud_sync::{self, Bundle, SyncState};
+use crate::game_mode::GameMode;
+use crate::hand_db::HandDb;
+use crate::hand_history;
+use crate::hotkeys::HotkeyAction;
+#[cfg(feature = "network")]
+use crate::network::discovery::Listing;
+#[cfg(feature = "network")]
+use crate::network::overlay::{Overlay, OVERLAY_PORT};
+#[cfg(feature = "network")]
+use crate::network::{self, chat, Message};
+use crate::notes::{self, Notes};
+use crate::presence::{Activity, Presence};
+use crate::profiles;
+use crate::session::{SavedMatch, SessionGoals, SessionTracker};
+use crate::settings::Settings;
+use poker_engine::{
+    Action, ActionError, BotMove, BotPersonality, BotStyle, DifficultyController, GameEvent,
+    GamePhase, HandLog, History, PlayerStats, PokerGame, MIN_RAISE, PHASE_TRANSITION_TIME_MS,
+};
+use slint::{
+    ComponentHandle, Image, PhysicalPosition, PhysicalSize, SharedString, Timer, TimerMode,
+    VecModel,
+};
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+const BOT_THINK_TIME_MS: u64 = 800;
+// How long the bot's cards sit face up before the rest of a showdown plays out.
+const SHOWDOWN_REVEAL_MS: u64 = 1200;
+const COMMAND_TICK_MS: u64 = 16;
+// A beat per startup step so the splash screen's progress bar is seen to move.
+const LOADING_STEP_MS: u64 = 150;
+const LOADING_STEPS: f32 = 3.0;
+const HISTORY_CAPACITY: usize = 200;
+// The pause between hands when watching bots play, at normal speed.
+const SPECTATE_NEXT_HAND_MS: u64 = 2500;
+const MIN_SPECTATE_SPEED: f32 = 0.25;
+const SPECTATOR_BOT_NAME: &str = "Bot 2";
+
+/// A bot move finished on the worker thread, tagged with the turn it was
+/// planned for.
+type BotMailbox = Arc<Mutex<Option<(u64, Option<Box<BotMove>>)>>>;
+
+/// A finished sync: the profile, what was sent, and what the server ended
+/// up holding with its `ETag`.
+type SyncMailbox = Arc<Mutex<Option<(String, Bundle, io::Result<(Bundle, Option<String>)>)>>>;
+
+pub struct AppState {
+    pub game: Rc<RefCell<PokerGame>>,
+    pub difficulty: Rc<RefCell<DifficultyController>>,
+    pub main_window: slint::Weak<MainWindow>,
+    turn_timer: Rc<Timer>,
+    commands: Rc<RefCell<ActionQueue>>,
+    command_timer: Rc<Timer>,
+    history: Rc<RefCell<History>>,
+    // Bumped whenever pending work is cancelled, so a bot move planned
+    // before that is dropped when it arrives.
+    bot_turn: Rc<Cell<u64>>,
+    bot_mailbox: BotMailbox,
+    sync_mailbox: SyncMailbox,
+    music: Rc<MusicPlayer>,
+    presence: Rc<Presence>,
+    #[cfg(feature = "network")]
+    overlay: Rc<Overlay>,
+    recorder: HandRecorder,
+    settings: Rc<RefCell<Settings>>,
+    bookmarks: Rc<RefCell<Vec<HandLog>>>,
+    // Whose settings and saved match are in use; picked on the splash screen.
+    profile: Rc<RefCell<String>>,
+    // While the pause menu or the main menu is open commands wait in the
+    // queue, so nothing moves until it closes.
+    paused: Rc<Cell<bool>>,
+    mode: Rc<Cell<GameMode>>,
+    // The sitting measured against the user's goals, while a match is on.
+    session: Rc<Cell<Option<SessionTracker>>>,
+    clock: TournamentClock,
+    diagnostics: Diagnostics,
+    minimize: MinimizeWatch,
+    #[cfg(feature = "network")]
+    lan: Lan,
+    #[cfg(feature = "network")]
+    chat: Chat,
+    view: TableView,
+}
+
+impl AppState {
+    pub fn new(
+        window: slint::Weak<MainWindow>,
+        music: Rc<MusicPlayer>,
+        presence: Rc<Presence>,
+        #[cfg(feature = "network")] overlay: Rc<Overlay>,
+        hand_db: Rc<HandDb>,
+    ) -> Self {
+        let difficulty = Rc::new(RefCell::new(DifficultyController::new()));
+        let turn_timer = Rc::new(Timer::default());
+        let history = Rc::new(RefCell::new(History::new(HISTORY_CAPACITY)));
+        let view = TableView {
+            main_window: window.clone(),
+            difficulty: difficulty.clone(),
+            turn_timer: turn_timer.clone(),
+            chip_timer: Rc::default(),
+            action_timer: Rc::default(),
+            winner_timer: Rc::default(),
+            history: history.clone(),
+            equity: EquityMeter::default(),
+            toasts: Toasts::default(),
+            emotes: Emotes::default(),
+            notes: Rc::default(),
+            bot_revealed: Rc::default(),
+            mode: Rc::default(),
+            hud: Rc::default(),
+        };
+        let recorder = HandRecorder {
+            db: hand_db,
+            profile: Rc::default(),
+            mode: view.mode.clone(),
+            counted: Rc::default(),
+            hud: view.hud.clone(),
+        };
+
+        let mut game = PokerGame::new();
+        let state_view = view.clone();
+        let history_recorder = history.clone();
+        game.on_state_changed(move |game, events| {
+            // Restores come from stepping through the history itself.
+            if events != [GameEvent::StateRestored] {
+                history_recorder.borrow_mut().record(game, events);
+            }
+            state_view.render_events(game, events);
+        });
+        let winner_view = view.clone();
+        game.on_hand_finished(move |game| winner_view.show_winner(game));
+        #[cfg(feature = "network")]
+        let lan = Lan::default();
+        let hand_recorder = recorder.clone();
+        #[cfg(feature = "network")]
+        let seat = lan.clone();
+        game.on_hand_finished(move |game| {
+            // A guest's replica holds stand-ins for the host's hole cards.
+            #[cfg(feature = "network")]
+            if seat.role() == Some(Role::Guest) {
+                return;
+            }
+            hand_recorder.record(game);
+        });
+        #[cfg(feature = "network")]
+        let guest = lan.clone();
+        #[cfg(feature = "network")]
+        game.on_state_changed(move |game, events| guest.forward(game, events));
+        let table_presence = presence.clone();
+        game.on_state_changed(move |game, _| table_presence.show(Activity::at_table(game)));
+        #[cfg(feature = "network")]
+        let table_overlay = overlay.clone();
+        #[cfg(feature = "network")]
+        game.on_state_changed(move |game, _| table_overlay.show(game));
+
+        Self {
+            game: Rc::new(RefCell::new(game)),
+            difficulty,
+            main_window: window,
+            turn_timer,
+            commands: Rc::default(),
+            command_timer: Rc::default(),
+            history,
+            bot_turn: Rc::new(Cell::new(0)),
+            bot_mailbox: BotMailbox::default(),
+            sync_mailbox: SyncMailbox::default(),
+            music,
+            presence,
+            #[cfg(feature = "network")]
+            overlay,
+            profile: recorder.profile.clone(),
+            recorder,
+            settings: Rc::default(),
+            bookmarks: Rc::default(),
+            paused: Rc::default(),
+            mode: view.mode.clone(),
+            session: Rc::default(),
+            clock: TournamentClock::default(),
+            diagnostics: Diagnostics::default(),
+            minimize: MinimizeWatch::default(),
+            #[cfg(feature = "network")]
+            lan,
+            #[cfg(feature = "network")]
+            chat: Chat::default(),
+            view,
+        }
+    }
+
+    pub fn update_ui(&self) {
+        self.view.render(&self.game.borrow());
+    }
+
+    /// Starts draining queued commands; call once the window exists.
+    pub fn start_commands(&self) {
+        let state = self.clone();
+        self.command_timer.start(
+            TimerMode::Repeated,
+            Duration::from_millis(COMMAND_TICK_MS),
+            move || state.drain_commands(),
+        );
+        if let Some(window) = self.main_window.upgrade() {
+            self.minimize.start(&window);
+        }
+    }
+
+    pub fn push(&self, command: Command) {
+        self.commands.borrow_mut().push(command);
+    }
+
+    fn drain_commands(&self) {
+        // The replayer only ever steps through history.
+        if self.paused.get() || self.minimize.holding() || self.mode.get() == GameMode::Replayer {
+            return;
+        }
+        loop {
+            // Popped on its own line so the queue isn't borrowed while the
+            // command runs and queues more.
+            let command = self.commands.borrow_mut().pop();
+            let Some(command) = command else { break };
+            debug!(?command, "running command");
+            self.diagnostics.time_tick(|| match command {
+                Command::Act(action) => self.process_action(action),
+                Command::Bot { turn, planned } => self.play_bot_move(turn, planned),
+                Command::NextPhase => self.run_phase_transition(),
+                Command::NewHand => self.new_hand(),
+                Command::AbandonHand => self.abandon_hand(),
+            });
+        }
+    }
+
+    /// Starts loading behind the splash screen. The profiles are read
+    /// first; the rest waits until one is picked.
+    pub fn start_loading(&self) {
+        self.set_loading(0.0, "Finding profiles…");
+        self.schedule(LOADING_STEP_MS, |state| {
+            let profiles: Vec<SharedString> =
+                profiles::list().into_iter().map(Into::into).collect();
+            if let Some(window) = state.main_window.upgrade() {
+                window.set_profiles(Rc::new(VecModel::from(profiles)).into());
+                window.set_profiles_loaded(true);
+            }
+            state.set_loading(1.0, "Who's playing?");
+        });
+    }
+
+    /// Sets up a table opened beside others: `profile` was already picked,
+    /// so the splash screen is skipped for the main menu.
+    pub fn join(&self, profile: &str) {
+        *self.profile.borrow_mut() = profile.to_string();
+        *self.settings.borrow_mut() = Settings::load(profile);
+        self.init_settings();
+        self.load_bookmarks();
+        self.load_notes();
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_splash(false);
+        }
+        self.open_main_menu();
+    }
+
+    pub fn profile(&self) -> String {
+        self.profile.borrow().clone()
+    }
+
+    /// Saves the window's size, position and panels, while it's still on
+    /// screen to be measured.
+    pub fn save_layout(&self) {
+        if !self.profile.borrow().is_empty() {
+            self.apply_settings();
+        }
+    }
+
+    /// Stops every timer once the table's window has closed.
+    pub fn shut_down(&self) {
+        self.cancel_pending();
+        self.command_timer.stop();
+        self.clock.close();
+        self.diagnostics.stop();
+    }
+
+    pub fn open_clock(&self) {
+        self.clock.open();
+    }
+
+    pub fn send_emote(&self, index: usize) {
+        let Some(&emote) = Emote::ALL.get(index) else {
+            return;
+        };
+        let game = self.game.borrow();
+        self.view.emotes.send(&self.main_window, &game, emote);
+    }
+
+    pub fn create_profile(&self, name: &str) {
+        match profiles::create(name) {
+            Ok(name) => self.select_profile(&name),
+            Err(error) => {
+                warn!(name, %error, "cannot create profile");
+                self.set_loading(1.0, &format!("Cannot create that profile: {}", error));
+            }
+        }
+    }
+
+    /// Loads `name`'s settings and saved match, then leaves the splash
+    /// screen.
+    pub fn select_profile(&self, name: &str) {
+        info!(profile = name, "profile selected");
+        *self.profile.borrow_mut() = name.to_string();
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_profiles_loaded(false);
+        }
+        self.set_loading(1.0, "Loading settings…");
+        self.schedule(LOADING_STEP_MS, |state| {
+            *state.settings.borrow_mut() = Settings::load(&state.profile.borrow());
+            state.init_settings();
+            state.load_bookmarks();
+            state.load_notes();
+            state.sync_profile();
+            state.set_loading(2.0, "Checking for a saved match…");
+            state.schedule(LOADING_STEP_MS, |state| {
+                state.set_loading(LOADING_STEPS, "Ready");
+                if let Some(window) = state.main_window.upgrade() {
+                    window.set_show_splash(false);
+                }
+                state.start();
+            });
+        });
+    }
+
+    fn set_loading(&self, steps_done: f32, status: &str) {
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_loading_progress(steps_done / LOADING_STEPS);
+            window.set_loading_status(status.into());
+        }
+    }
+
+    /// Resumes a saved match, or leaves the main menu up for the user to
+    /// pick one.
+    pub fn start(&self) {
+        let Some(saved) = SavedMatch::take(&self.profile.borrow()) else {
+            self.open_main_menu();
+            return;
+        };
+        info!(?saved, "resuming saved match");
+        self.mode.set(saved.mode);
+        let mut game = self.game.borrow_mut();
+        saved.apply(&mut game);
+        self.recorder.sync(&game);
+        self.session.set(Some(SessionTracker::new(&game)));
+        self.deal(&mut game);
+        drop(game);
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_main_menu(false);
+        }
+        self.schedule_turn();
+    }
+
+    /// Puts the table on hold behind the main menu.
+    pub fn open_main_menu(&self) {
+        self.cancel_pending();
+        self.session.set(None);
+        self.clock.close();
+        #[cfg(feature = "network")]
+        self.lan.close();
+        self.paused.set(true);
+        self.presence.show(Activity::in_menu());
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        window.set_show_chat(false);
+        let modes: Vec<GameModeUI> = GameMode::ALL
+            .into_iter()
+            .map(|mode| GameModeUI {
+                name: mode.name().into(),
+                description: mode.description().into(),
+            })
+            .collect();
+        window.set_game_modes(Rc::new(VecModel::from(modes)).into());
+        let personalities: Vec<SharedString> = personalities()
+            .into_iter()
+            .map(|personality| personality.name().into())
+            .collect();
+        window.set_menu_personalities(Rc::new(VecModel::from(personalities)).into());
+        window.set_paused(true);
+        window.set_replaying(false);
+        window.set_spectating(false);
+        window.set_show_main_menu(true);
+    }
+
+    /// Sits down to a new match with the mode and opponent picked in the
+    /// main menu.
+    pub fn start_match(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let mode = GameMode::ALL
+            .get(window.get_selected_mode() as usize)
+            .copied()
+            .unwrap_or_default();
+        info!(?mode, "starting a match");
+        self.cancel_pending();
+        *self.commands.borrow_mut() = ActionQueue::default();
+        self.mode.set(mode);
+        self.paused.set(false);
+        window.set_paused(false);
+        window.set_show_main_menu(false);
+        window.set_replaying(mode == GameMode::Replayer);
+        window.set_spectating(mode == GameMode::Spectate);
+
+        if mode == GameMode::Replayer {
+            let first = self.history.borrow_mut().first().cloned();
+            match first {
+                Some(entry) => self.game.borrow_mut().restore(&entry.snapshot),
+                None => window.set_show_bookmarks(true),
+            }
+            self.recorder.sync(&self.game.borrow());
+            return;
+        }
+
+        let mut difficulty = self.difficulty.borrow_mut();
+        difficulty.set_enabled(window.get_menu_adaptive());
+        let mut game = self.game.borrow_mut();
+        game.bot_params = difficulty.params();
+        drop(difficulty);
+        game.bot_personality = personalities()
+            .get(window.get_menu_personality() as usize)
+            .copied()
+            .unwrap_or(BotPersonality::Balanced);
+        // In a bot-against-bot match there is no user to exploit.
+        game.bot_style = if window.get_menu_exploitative() && mode.is_playing() {
+            BotStyle::Exploitative
+        } else {
+            BotStyle::Standard
+        };
+        for player in &mut game.players {
+            player.chips = mode.starting_chips();
+        }
+        // Watching hands the user's seat to a second bot.
+        let spectating = mode == GameMode::Spectate;
+        game.players[0].is_user = !spectating;
+        game.players[0].name = if spectating {
+            SPECTATOR_BOT_NAME
+        } else {
+            "You"
+        }
+        .to_string();
+        game.players[1].name = "Bot".to_string();
+        if spectating {
+            window.set_show_action_log(true);
+        }
+        game.dealer_position = 0;
+        game.hand_number = 0;
+        game.bot_stats = PlayerStats::default();
+        self.recorder.sync(&game);
+        self.session
+            .set(mode.is_playing().then(|| SessionTracker::new(&game)));
+        self.deal(&mut game);
+        drop(game);
+        self.schedule_turn();
+    }
+
+    /// Shows the LAN lobby, listing the tables announced on the network.
+    #[cfg(feature = "network")]
+    pub fn open_lobby(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let stakes: Vec<SharedString> = lan::STAKES
+            .iter()
+            .map(|(small, big)| format!("${}/${}", small, big).into())
+            .collect();
+        window.set_lobby_stakes(Rc::new(VecModel::from(stakes)).into());
+        if window.get_lobby_table_name().is_empty() {
+            window.set_lobby_table_name(format!("{}'s table", self.profile.borrow()).into());
+        }
+        window.set_lobby_tables(Rc::new(VecModel::<LobbyTableUI>::default()).into());
+        window.set_show_lobby(true);
+        let state = self.clone();
+        if let Err(error) = self.lan.browse(move |tables| state.show_tables(&tables)) {
+            warn!(%error, "cannot listen for LAN tables");
+            window.set_lan_status(format!("Can't look for tables: {}", error).into());
+        }
+    }
+
+    /// Shows the profile's results over every hand recorded, and where it
+    /// stands among the others.
+    pub fn open_stats(&self) {
+        if let Some(window) = self.main_window.upgrade() {
+            stats::show(&window, &self.recorder.db, &self.profile.borrow());
+        }
+    }
+
+    #[cfg(feature = "network")]
+    pub fn close_lobby(&self) {
+        self.lan.stop_browsing();
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_lobby(false);
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn show_tables(&self, listings: &[Listing]) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let tables: Vec<LobbyTableUI> = listings
+            .iter()
+            .map(|listing| LobbyTableUI {
+                name: listing.table.name.as_str().into(),
+                variant: listing.table.variant.as_str().into(),
+                stakes: format!(
+                    "${}/${}",
+                    listing.table.small_blind, listing.table.big_blind
+                )
+                .into(),
+                seats: listing.table.seats as i32,
+                seats_open: listing.table.seats_open as i32,
+                spectators: listing.table.spectators as i32,
+                address: listing.address().into(),
+            })
+            .collect();
+        window.set_lobby_tables(Rc::new(VecModel::from(tables)).into());
+    }
+
+    /// Opens a table named `name` at the `stakes`th blinds to a guest on
+    /// the LAN.
+    #[cfg(feature = "network")]
+    pub fn host_lan(&self, name: &str, stakes: usize) {
+        let stakes = lan::STAKES.get(stakes).copied().unwrap_or(lan::STAKES[0]);
+        let name = self.table_name(name);
+        let state = self.clone();
+        let status = match self.lan.host(&name, stakes, move || state.poll_lan()) {
+            Ok(address) => format!("Waiting for a guest at {}…", address),
+            Err(error) => {
+                warn!(%error, "cannot host a LAN match");
+                format!("Cannot host: {}", error)
+            }
+        };
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_lan_status(status.into());
+        }
+    }
+
+    /// Connects to a host at `address`, with or without the port.
+    #[cfg(feature = "network")]
+    pub fn join_lan(&self, address: &str) {
+        if address.trim().is_empty() {
+            return;
+        }
+        let state = self.clone();
+        self.lan.join(address, move || state.poll_lan());
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_lan_status(format!("Connecting to {}…", address.trim()).into());
+        }
+    }
+
+    /// Opens a table on the rendezvous service at `server`, for a friend
+    /// to join by its code rather than an address.
+    #[cfg(feature = "network")]
+    pub fn host_online(&self, server: &str, name: &str, stakes: usize) {
+        let Some(server) = self.rendezvous_server(server) else {
+            return;
+        };
+        let stakes = lan::STAKES.get(stakes).copied().unwrap_or(lan::STAKES[0]);
+        let name = self.table_name(name);
+        let state = self.clone();
+        self.lan
+            .host_online(&server, &name, stakes, move || state.poll_lan());
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_lan_status(format!("Opening the table at {}…", server).into());
+        }
+    }
+
+    /// Joins the table with `code` on the rendezvous service at `server`.
+    #[cfg(feature = "network")]
+    pub fn join_online(&self, server: &str, code: &str) {
+        if code.trim().is_empty() {
+            return;
+        }
+        let Some(server) = self.rendezvous_server(server) else {
+            return;
+        };
+        let state = self.clone();
+        self.lan
+            .join_online(&server, code, move || state.poll_lan());
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_lan_status(format!("Looking for {} at {}…", code.trim(), server).into());
+        }
+    }
+
+    /// The name to host a table under: `name`, or the player's own if
+    /// that was left blank.
+    #[cfg(feature = "network")]
+    fn table_name(&self, name: &str) -> String {
+        match name.trim() {
+            "" => format!("{}'s table", self.profile()),
+            name => name.to_string(),
+        }
+    }
+
+    /// `server`, saved for next time; `None` if none was given.
+    #[cfg(feature = "network")]
+    fn rendezvous_server(&self, server: &str) -> Option<String> {
+        let window = self.main_window.upgrade()?;
+        if server.trim().is_empty() {
+            window.set_lan_status("Enter the rendezvous server first".into());
+            return None;
+        }
+        self.apply_settings();
+        Some(server.trim().to_string())
+    }
+
+    #[cfg(feature = "network")]
+    fn poll_lan(&self) {
+        for event in self.lan.poll() {
+            match event {
+                LanEvent::Code(code) => {
+                    if let Some(window) = self.main_window.upgrade() {
+                        let status = format!("Open online: your friend joins with code {}", code);
+                        window.set_lan_status(status.into());
+                    }
+                }
+                LanEvent::Connected => {
+                    if let Some(role) = self.lan.role() {
+                        self.start_lan_match(role);
+                    }
+                }
+                LanEvent::Away => {
+                    let notice = match self.lan.role() {
+                        Some(Role::Guest) => "Connection lost; reconnecting…",
+                        _ => "The guest dropped; holding their seat",
+                    };
+                    self.view.toasts.push(&self.main_window, notice.to_string());
+                    self.schedule_turn();
+                }
+                LanEvent::Resumed => {
+                    let notice = match self.lan.role() {
+                        Some(Role::Guest) => "Reconnected",
+                        _ => "The guest is back",
+                    };
+                    self.view.toasts.push(&self.main_window, notice.to_string());
+                }
+                LanEvent::Received(message) => self.receive_lan(message),
+                LanEvent::Lost(reason) => {
+                    self.open_main_menu();
+                    self.open_lobby();
+                    if let Some(window) = self.main_window.upgrade() {
+                        window.set_lan_status(reason.into());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sits down across from the other player. Only the host deals; the
+    /// guest's table waits for the first hand to arrive.
+    #[cfg(feature = "network")]
+    fn start_lan_match(&self, role: Role) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        info!(?role, "starting a LAN match");
+        self.close_lobby();
+        let mode = GameMode::Lan;
+        self.cancel_pending();
+        *self.commands.borrow_mut() = ActionQueue::default();
+        self.mode.set(mode);
+        self.paused.set(false);
+        window.set_paused(false);
+        window.set_show_main_menu(false);
+        window.set_replaying(false);
+        window.set_spectating(false);
+        window.set_lan_status("".into());
+        self.chat.open(&window);
+        window.set_show_chat(true);
+
+        let mut game = self.game.borrow_mut();
+        for player in &mut game.players {
+            player.chips = mode.starting_chips();
+        }
+        game.players[0].is_user = true;
+        game.players[0].name = "You".to_string();
+        game.players[1].name = match role {
+            Role::Host => "Guest",
+            Role::Guest => "Host",
+        }
+        .to_string();
+        game.dealer_position = 0;
+        game.hand_number = 0;
+        self.recorder.sync(&game);
+        if role == Role::Host {
+            self.deal(&mut game);
+        }
+    }
+
+    /// The host plays the guest's actions for seat 1; the guest's table
+    /// replays everything the host sends.
+    #[cfg(feature = "network")]
+    fn receive_lan(&self, message: Message) {
+        if let Message::Chat { seat, text } = message {
+            self.receive_chat(seat, text);
+            return;
+        }
+        match self.lan.role() {
+            Some(Role::Host) => {
+                let Message::Act { action, .. } = message else {
+                    warn!(?message, "unexpected message from the guest");
+                    return;
+                };
+                let applied = self.game.borrow_mut().seat_action(1, action);
+                match applied {
+                    Ok(()) => self.after_action(),
+                    Err(error) => warn!(?action, %error, "guest's action rejected"),
+                }
+            }
+            Some(Role::Guest) => {
+                let was_complete = self.game.borrow().hand_complete;
+                let applied = network::apply_to_replica(&mut self.game.borrow_mut(), message);
+                if let Err(error) = applied {
+                    warn!(%error, "cannot follow the host's game");
+                }
+                if self.game.borrow().hand_complete && !was_complete {
+                    self.finish_hand();
+                } else {
+                    self.view.render_controls(&self.game.borrow());
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Says `text` at the table; the host passes it on, and a guest's line
+    /// shows once the host has sent it back.
+    #[cfg(feature = "network")]
+    pub fn send_chat(&self, text: &str) {
+        let Some(text) = chat::tidy(text) else {
+            return;
+        };
+        if !self.chat.may_send() {
+            self.view.toasts.push(
+                &self.main_window,
+                "Slow down: too many messages".to_string(),
+            );
+            return;
+        }
+        match self.lan.role() {
+            Some(Role::Host) => {
+                self.lan.relay_chat(0, &text);
+                self.show_chat_line(0, &text);
+            }
+            Some(Role::Guest) => self.lan.send(&Message::Chat { seat: 0, text }),
+            None => {}
+        }
+    }
+
+    /// The host passes on the guest's line unless it comes too fast; the
+    /// guest shows what the host sends.
+    #[cfg(feature = "network")]
+    fn receive_chat(&self, seat: usize, text: String) {
+        if self.lan.role() != Some(Role::Host) {
+            self.show_chat_line(seat, &text);
+            return;
+        }
+        match chat::tidy(&text).filter(|_| self.chat.may_pass()) {
+            Some(text) => {
+                self.lan.relay_chat(1, &text);
+                self.show_chat_line(1, &text);
+            }
+            None => debug!("dropping a chat line from the guest"),
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn show_chat_line(&self, seat: usize, text: &str) {
+        let name = match self.game.borrow().players.get(seat) {
+            Some(player) => player.name.clone(),
+            None => return,
+        };
+        self.chat.show(seat, &name, text);
+    }
+
+    /// Mutes the other player's chat, or unmutes it.
+    #[cfg(feature = "network")]
+    pub fn toggle_chat_mute(&self) {
+        let muted = self.chat.toggle_mute(1);
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_chat_muted(muted);
+        }
+    }
+
+    /// Starts the next hand at the blinds the mode calls for.
+    fn deal(&self, game: &mut PokerGame) {
+        #[cfg(feature = "network")]
+        let (small_blind, big_blind) = match self.lan.stakes() {
+            Some(stakes) if self.mode.get() == GameMode::Lan => stakes,
+            _ => self.mode.get().blinds(game.hand_number + 1),
+        };
+        #[cfg(not(feature = "network"))]
+        let (small_blind, big_blind) = self.mode.get().blinds(game.hand_number + 1);
+        game.small_blind = small_blind;
+        game.big_blind = big_blind;
+        game.start_hand();
+        let mode = self.mode.get();
+        if mode == GameMode::Tutorial {
+            tutorial::stack(game);
+        }
+        if mode.has_blind_levels() {
+            self.clock.hand_dealt(mode, game.hand_number);
+        } else {
+            self.clock.close();
+        }
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_has_blind_levels(mode.has_blind_levels());
+        }
+    }
+
+    pub fn cycle_bot_personality(&self) {
+        let mut game = self.game.borrow_mut();
+        game.bot_personality = game.bot_personality.next();
+        self.recorder.sync(&game);
+        drop(game);
+        self.update_ui();
+    }
+
+    pub fn toggle_bot_style(&self) {
+        let mut game = self.game.borrow_mut();
+        game.bot_style = match game.bot_style {
+            BotStyle::Standard => BotStyle::Exploitative,
+            BotStyle::Exploitative => BotStyle::Standard,
+        };
+        drop(game);
+        self.update_ui();
+    }
+
+    /// Fills in the track list, shows the saved settings and applies them.
+    pub fn init_settings(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let tracks: Vec<SharedString> = TRACKS.iter().map(|&name| name.into()).collect();
+        window.set_music_tracks(Rc::new(VecModel::from(tracks)).into());
+
+        let settings = self.settings.borrow().clone();
+        if let Some((width, height)) = settings.window_size {
+            window.window().set_size(PhysicalSize::new(width, height));
+        }
+        if let Some((x, y)) = settings.window_position {
+            window.window().set_position(PhysicalPosition::new(x, y));
+        }
+        window.set_fullscreen(settings.fullscreen);
+        window.window().set_fullscreen(settings.fullscreen);
+        window.set_show_action_log(settings.show_action_log);
+        window.set_show_decision_log(settings.show_decision_log);
+        window.set_last_hand_expanded(settings.last_hand_expanded);
+        window.set_rendezvous_server(settings.rendezvous_server.as_str().into());
+        window.set_discord_presence(settings.discord_presence);
+        window.set_stream_overlay(settings.stream_overlay);
+        window.set_sync_server(settings.sync_server.as_str().into());
+        window.set_sync_token(settings.sync_token.as_str().into());
+        #[cfg(feature = "network")]
+        window.set_overlay_address(format!("http://127.0.0.1:{}", OVERLAY_PORT).into());
+        window.set_commentary_enabled(settings.commentary);
+        window.set_show_hud(settings.show_hud);
+        window.set_mute_emotes(settings.mute_emotes);
+        window.set_show_mucked_cards(settings.show_mucked_cards);
+        window.set_touch_layout(settings.touch_layout);
+        window.set_hotkeys(hotkeys_model(&settings.hotkeys));
+        window.set_hotkey_error("".into());
+        window.set_goal_minutes(settings.goals.minutes as i32);
+        window.set_goal_hands(settings.goals.hands as i32);
+        window.set_goal_profit(settings.goals.profit as i32);
+        window.set_goal_stop_loss(settings.goals.stop_loss as i32);
+        window
+            .global::<Motion>()
+            .set_speed(settings.animation_speed.as_str().into());
+        window.set_seat_side(settings.seat_side.as_str().into());
+        window.set_while_minimized(settings.while_minimized.as_str().into());
+        window.set_confirm_free_fold(settings.confirm_free_fold);
+        window.set_music_enabled(settings.music_enabled);
+        window.set_music_track(settings.music_track);
+        window.set_music_volume(settings.music_volume);
+        window
+            .global::<Theme>()
+            .set_name(settings.theme.as_str().into());
+        let card_skin = window.global::<CardSkin>();
+        card_skin.set_name(settings.card_skin.as_str().into());
+        card_skin.set_four_color(settings.four_color);
+        window
+            .global::<CardBack>()
+            .set_name(settings.card_back.as_str().into());
+        let table = window.global::<TableStyle>();
+        table.set_felt(settings.felt.as_str().into());
+        table.set_texture(settings.texture.as_str().into());
+        table.set_background_path(settings.background_image.as_str().into());
+        load_background(&window, &settings.background_image);
+
+        self.music.apply(&MusicSettings::from_ui(
+            settings.music_enabled,
+            settings.music_track,
+            settings.music_volume,
+        ));
+        self.presence.set_enabled(settings.discord_presence);
+        self.serve_overlay(settings.stream_overlay);
+    }
+
+    /// Applies the settings panel's values and saves them if they changed.
+    pub fn apply_settings(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let table = window.global::<TableStyle>();
+        // A fullscreen window keeps the geometry it had before.
+        let (window_size, window_position) = if window.window().is_fullscreen() {
+            let saved = self.settings.borrow();
+            (saved.window_size, saved.window_position)
+        } else {
+            let size = window.window().size();
+            let position = window.window().position();
+            (
+                Some((size.width, size.height)),
+                Some((position.x, position.y)),
+            )
+        };
+        let settings = Settings {
+            fullscreen: window.get_fullscreen(),
+            commentary: window.get_commentary_enabled(),
+            show_hud: window.get_show_hud(),
+            mute_emotes: window.get_mute_emotes(),
+            show_mucked_cards: window.get_show_mucked_cards(),
+            touch_layout: window.get_touch_layout(),
+            animation_speed: window.global::<Motion>().get_speed().into(),
+            seat_side: window.get_seat_side().into(),
+            while_minimized: window.get_while_minimized().into(),
+            hotkeys: self.settings.borrow().hotkeys.clone(),
+            goals: SessionGoals {
+                minutes: window.get_goal_minutes().max(0) as u32,
+                hands: window.get_goal_hands().max(0) as u32,
+                profit: window.get_goal_profit().max(0) as u32,
+                stop_loss: window.get_goal_stop_loss().max(0) as u32,
+            },
+            confirm_free_fold: window.get_confirm_free_fold(),
+            music_enabled: window.get_music_enabled(),
+            music_track: window.get_music_track(),
+            music_volume: window.get_music_volume(),
+            avatar: window.global::<Avatars>().get_player().into(),
+            theme: window.global::<Theme>().get_name().into(),
+            card_skin: window.global::<CardSkin>().get_name().into(),
+            four_color: window.global::<CardSkin>().get_four_color(),
+            card_back: window.global::<CardBack>().get_name().into(),
+            felt: table.get_felt().into(),
+            texture: table.get_texture().into(),
+            background_image: table.get_background_path().trim().into(),
+            window_size,
+            window_position,
+            show_action_log: window.get_show_action_log(),
+            show_decision_log: window.get_show_decision_log(),
+            last_hand_expanded: window.get_last_hand_expanded(),
+            rendezvous_server: window.get_rendezvous_server().trim().into(),
+            discord_presence: window.get_discord_presence(),
+            stream_overlay: window.get_stream_overlay(),
+            sync_server: window.get_sync_server().trim().into(),
+            sync_token: window.get_sync_token().trim().into(),
+        };
+        self.music.apply(&MusicSettings::from_ui(
+            settings.music_enabled,
+            settings.music_track,
+            settings.music_volume,
+        ));
+        self.presence.set_enabled(settings.discord_presence);
+        self.serve_overlay(settings.stream_overlay);
+
+        window.window().set_fullscreen(settings.fullscreen);
+
+        let mut saved = self.settings.borrow_mut();
+        if *saved == settings {
+            return;
+        }
+        if saved.background_image != settings.background_image {
+            load_background(&window, &settings.background_image);
+        }
+        settings.save(&self.profile.borrow());
+        *saved = settings;
+    }
+
+    #[cfg(feature = "network")]
+    fn serve_overlay(&self, enabled: bool) {
+        if let Err(error) = self.overlay.set_enabled(enabled) {
+            warn!(%error, "cannot serve the stream overlay");
+            self.view
+                .toasts
+                .push(&self.main_window, format!("No stream overlay: {}", error));
+        }
+    }
+
+    // Without networking there's no overlay, and no checkbox for it.
+    #[cfg(not(feature = "network"))]
+    fn serve_overlay(&self, _enabled: bool) {}
+
+    /// Whether this table is a guest's copy of a network match, which only
+    /// follows the host's.
+    fn is_guest(&self) -> bool {
+        #[cfg(feature = "network")]
+        return self.lan.role() == Some(Role::Guest);
+        #[cfg(not(feature = "network"))]
+        false
+    }
+
+    /// Binds the key typed as `text` to the `index`th hotkey and saves it,
+    /// or says why it can't be used.
+    pub fn rebind_hotkey(&self, index: usize, text: &str) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let Some(&action) = HotkeyAction::ALL.get(index) else {
+            return;
+        };
+        let mut settings = self.settings.borrow_mut();
+        match settings.hotkeys.bind(action, text) {
+            Ok(()) => {
+                window.set_hotkeys(hotkeys_model(&settings.hotkeys));
+                window.set_hotkey_error("".into());
+                settings.save(&self.profile.borrow());
+            }
+            Err(error) => window.set_hotkey_error(error.into()),
+        }
+    }
+
+    pub fn toggle_debug_panel(&self) {
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_debug_panel(!window.get_show_debug_panel());
+        }
+        self.update_ui();
+    }
+
+    pub fn toggle_diagnostics(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let shown = !window.get_show_diagnostics();
+        window.set_show_diagnostics(shown);
+        if shown {
+            let state = self.clone();
+            self.diagnostics.start(&window, move || state.timers());
+        } else {
+            self.diagnostics.stop();
+        }
+    }
+
+    /// The table's timers, named for the performance overlay.
+    fn timers(&self) -> Vec<(&'static str, bool)> {
+        vec![
+            ("commands", self.command_timer.running()),
+            ("bot turn", self.turn_timer.running()),
+            ("chip flight", self.view.chip_timer.running()),
+            ("action flourish", self.view.action_timer.running()),
+            ("winner banner", self.view.winner_timer.running()),
+        ]
+    }
+
+    pub fn force_scenario(&self, name: &str) {
+        let Some(scenario) = Scenario::from_name(name) else {
+            warn!(name, "unknown debug scenario");
+            return;
+        };
+        self.cancel_pending();
+        scenario.apply(&mut self.game.borrow_mut());
+        self.schedule_turn();
+    }
+
+    /// Restores an earlier or later recorded state. Play stays paused until
+    /// the history is back at the latest state or the user acts, which drops
+    /// the states after this one.
+    pub fn step_history(&self, direction: &str) {
+        self.cancel_pending();
+        let mut history = self.history.borrow_mut();
+        let entry = match direction {
+            "first" => history.first(),
+            "back" => history.back(),
+            "forward" => history.forward(),
+            "live" => history.latest(),
+            _ => {
+                warn!(direction, "unknown history step");
+                None
+            }
+        }
+        .cloned();
+        let is_live = history.is_live();
+        drop(history);
+
+        if let Some(entry) = entry {
+            self.game.borrow_mut().restore(&entry.snapshot);
+        }
+        // A finished hand was already scored when it was first played.
+        if is_live && !self.game.borrow().hand_complete {
+            self.after_action();
+        }
+    }
+
+    /// Stars or unstars the hand being played. A hand in progress is saved
+    /// once it finishes, so the bookmark replays all of it.
+    pub fn toggle_bookmark(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let bookmarked = !window.get_hand_bookmarked();
+        window.set_hand_bookmarked(bookmarked);
+        let game = self.game.borrow();
+        if game.hand_complete || !bookmarked {
+            let log = game.hand_log().clone();
+            drop(game);
+            self.set_bookmark(log, bookmarked);
+        }
+    }
+
+    fn set_bookmark(&self, log: HandLog, bookmarked: bool) {
+        let mut saved = self.bookmarks.borrow_mut();
+        saved.retain(|other| !bookmarks::same_hand(other, &log));
+        if bookmarked {
+            saved.push(log);
+        }
+        let profile = self.profile.borrow();
+        if !profile.is_empty() {
+            if let Err(error) = bookmarks::save(&profile, &saved) {
+                warn!(%error, "cannot save bookmarks");
+            }
+        }
+        drop(saved);
+        self.show_bookmarks();
+    }
+
+    fn load_bookmarks(&self) {
+        *self.bookmarks.borrow_mut() = bookmarks::load(&self.profile.borrow());
+        self.show_bookmarks();
+    }
+
+    /// Fills the replayer's bookmarks tab, newest first.
+    fn show_bookmarks(&self) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        let recaps: Vec<HandRecapUI> = self
+            .bookmarks
+            .borrow()
+            .iter()
+            .rev()
+            .filter_map(|log| match poker_engine::replay(log) {
+                Ok(game) => Some(recap(&game)),
+                Err(error) => {
+                    warn!(hand = log.hand_number, %error, "cannot replay bookmarked hand");
+                    None
+                }
+            })
+            .collect();
+        window.set_bookmarks(Rc::new(VecModel::from(recaps)).into());
+    }
+
+    /// Loads a bookmarked hand into the replayer, one state per action, in
+    /// place of the recorded history.
+    pub fn open_bookmark(&self, index: usize) {
+        let Some(log) = self.bookmarks.borrow().iter().rev().nth(index).cloned() else {
+            return;
+        };
+        let mut history = History::new(HISTORY_CAPACITY);
+        for count in 0..=log.actions.len() {
+            let partial = HandLog {
+                actions: log.actions[..count].to_vec(),
+                ..log.clone()
+            };
+            match poker_engine::replay(&partial) {
+                Ok(game) => history.record(&game, game.events()),
+                Err(error) => {
+                    warn!(hand = log.hand_number, %error, "cannot replay bookmarked hand");
+                    return;
+                }
+            }
+        }
+        *self.history.borrow_mut() = history;
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_bookmarks(false);
+        }
+        self.step_history("first");
+    }
+
+    /// Loads the hands in the hand history file at `path` into the
+    /// replayer, in place of the hands it had.
+    pub fn import_hands(&self, path: &str) {
+        let path = path.trim();
+        if path.is_empty() {
+            return;
+        }
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => {
+                warn!(path, %error, "cannot read hand history");
+                self.view
+                    .toasts
+                    .push(&self.main_window, format!("Can't read {}: {}", path, error));
+                return;
+            }
+        };
+        let import = hand_history::parse(&text);
+        let steps = import.hands.iter().map(|hand| hand.actions.len() + 1).sum();
+        let mut history = History::new(steps);
+        let mut imported = 0;
+        let mut skipped = import.skipped;
+        for hand in &import.hands {
+            // Played through once first, so a hand the engine turns down
+            // leaves no half of it behind.
+            if let Err(error) = hand.play(|_| {}) {
+                warn!(hand = hand.id, %error, "cannot replay imported hand");
+                skipped += 1;
+                continue;
+            }
+            let _ = hand.play(|game| history.record(game, game.events()));
+            imported += 1;
+        }
+        info!(path, imported, skipped, "hand history imported");
+        let plural = if imported == 1 { "" } else { "s" };
+        let notice = match (imported, skipped) {
+            (0, _) => "No heads-up hold'em hands found in that file".to_string(),
+            (imported, 0) => format!("Imported {} hand{}", imported, plural),
+            (imported, skipped) => {
+                format!("Imported {} hand{}, skipped {}", imported, plural, skipped)
+            }
+        };
+        self.view.toasts.push(&self.main_window, notice);
+        if imported == 0 {
+            return;
+        }
+        *self.history.borrow_mut() = history;
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_show_bookmarks(false);
+        }
+        self.step_history("first");
+    }
+
+    fn load_notes(&self) {
+        *self.view.notes.borrow_mut() = Notes::load(&self.profile.borrow());
+        if let Some(window) = self.main_window.upgrade() {
+            let colors: Vec<SharedString> =
+                notes::COLORS.iter().map(|&color| color.into()).collect();
+            window.set_note_colors(Rc::new(VecModel::from(colors)).into());
+        }
+        self.view.render_controls(&self.game.borrow());
+    }
+
+    pub fn share_hand(&self) {
+        share::share(&self.main_window, &self.profile.borrow(), &self.view.toasts);
+    }
+
+    pub fn copy_hand(&self) {
+        share::copy(&self.main_window, &self.view.toasts);
+    }
+
+    /// Syncs the profile with the sync server on a worker thread, if one is
+    /// set.
+    pub fn sync_profile(&self) {
+        let profile = self.profile.borrow().clone();
+        let settings = self.settings.borrow();
+        if profile.is_empty() || settings.sync_server.is_empty() {
+            return;
+        }
+        let (server, token) = (settings.sync_server.clone(), settings.sync_token.clone());
+        drop(settings);
+        let state = SyncState::load(&profile);
+        let local = Bundle::local(&profile, &self.recorder.db);
+        let mailbox = self.sync_mailbox.clone();
+        let window = self.main_window.clone();
+        worker::spawn(move || {
+            let synced = cloud_sync::exchange(&server, &token, &profile, &state, &local);
+            *mailbox.lock().unwrap_or_else(PoisonError::into_inner) =
+                Some((profile, local, synced));
+            let posted = slint::invoke_from_event_loop(move || {
+                if let Some(window) = window.upgrade() {
+                    window.invoke_sync_ready();
+                }
+            });
+            if let Err(error) = posted {
+                warn!(%error, "cannot hand the synced profile back to the UI");
+            }
+        });
+    }
+
+    pub fn finish_sync(&self) {
+        let delivered = self
+            .sync_mailbox
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let Some((profile, sent, synced)) = delivered else {
+            return;
+        };
+        if profile != *self.profile.borrow() {
+            return;
+        }
+        let (merged, etag) = match synced {
+            Ok(synced) => synced,
+            Err(error) => {
+                warn!(%error, "cannot sync the profile");
+                self.view
+                    .toasts
+                    .push(&self.main_window, format!("Sync failed: {}", error));
+                return;
+            }
+        };
+        // Keep whatever changed here while the sync was under way.
+        let current = Bundle::local(&profile, &self.recorder.db);
+        let applied = cloud_sync::merge(&sent, &current, &merged)
+            .apply(&profile, &self.recorder.db)
+            .and_then(|()| SyncState { etag, base: merged }.save(&profile));
+        if let Err(error) = applied {
+            warn!(%error, "cannot save the synced profile");
+            self.view
+                .toasts
+                .push(&self.main_window, format!("Sync failed: {}", error));
+            return;
+        }
+        self.load_bookmarks();
+        self.load_notes();
+        self.recorder.sync(&self.game.borrow());
+        self.view
+            .toasts
+            .push(&self.main_window, "Profile synced".to_string());
+    }
+
+    /// Replaces the note on the current opponent.
+    pub fn save_note(&self, color: &str, text: &str) {
+        let opponent = self.game.borrow().bot_personality.name();
+        let mut notes = self.view.notes.borrow_mut();
+        notes.set(opponent, color, text);
+        let profile = self.profile.borrow();
+        if !profile.is_empty() {
+            if let Err(error) = notes.save(&profile) {
+                warn!(%error, "cannot save opponent notes");
+            }
+        }
+        drop(notes);
+        self.view.render_controls(&self.game.borrow());
+    }
+
+    /// Warns when the sitting reaches one of the user's goals, offering to
+    /// end it.
+    fn check_goals(&self) {
+        let Some(mut tracker) = self.session.get() else {
+            return;
+        };
+        let goal = tracker.check(&self.settings.borrow().goals, &self.game.borrow());
+        self.session.set(Some(tracker));
+        if let (Some(goal), Some(window)) = (goal, self.main_window.upgrade()) {
+            info!(?goal, "session goal reached");
+            let message = self.settings.borrow().goals.describe(goal);
+            window.set_goal_alert(message.into());
+        }
+    }
+
+    fn on_hand_complete(&self) {
+        let mut game = self.game.borrow_mut();
+        let user_net = game.players[0].chips.as_i32() - game.hand_start_chips[0].as_i32();
+        let mut difficulty = self.difficulty.borrow_mut();
+        difficulty.record_hand(user_net);
+        game.bot_params = difficulty.params();
+    }
+
+    pub fn toggle_adaptive_difficulty(&self) {
+        let mut difficulty = self.difficulty.borrow_mut();
+        let enabled = !difficulty.enabled;
+        difficulty.set_enabled(enabled);
+        self.game.borrow_mut().bot_params = difficulty.params();
+        drop(difficulty);
+        self.update_ui();
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        debug!(paused, "pause menu");
+        self.paused.set(paused);
+    }
+
+    /// Saves the match as it stood when this hand began and closes the
+    /// window.
+    pub fn save_and_quit(&self) {
+        let mode = self.mode.get();
+        if mode.is_saved() {
+            let saved = SavedMatch::from_game(&self.game.borrow(), mode);
+            match saved.save(&self.profile.borrow()) {
+                Ok(()) => info!(?saved, "match saved"),
+                Err(error) => warn!(%error, "cannot save the match"),
+            }
+        }
+        if let Some(window) = self.main_window.upgrade() {
+            if let Err(error) = window.hide() {
+                warn!(%error, "cannot close the window");
+            }
+        }
+    }
+
+    pub fn cancel_pending(&self) {
+        self.turn_timer.stop();
+        self.bot_turn.set(self.bot_turn.get() + 1);
+        self.view.set_bot_thinking(false);
+    }
+
+    fn schedule(&self, delay_ms: u64, step: fn(&AppState)) {
+        let state = self.clone();
+        self.turn_timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(self.paced(delay_ms)),
+            move || step(&state),
+        );
+    }
+
+    /// `delay_ms` at the speed picked for watching bots; other modes keep
+    /// their normal pace.
+    fn paced(&self, delay_ms: u64) -> u64 {
+        let speed = match self.main_window.upgrade() {
+            Some(window) if self.mode.get() == GameMode::Spectate => window.get_spectate_speed(),
+            _ => 1.0,
+        };
+        (delay_ms as f32 / speed.max(MIN_SPECTATE_SPEED)) as u64
+    }
+
+    pub fn schedule_turn(&self) {
+        // Across a LAN the other seat is a person, whose actions arrive as
+        // messages; if they have dropped, their turn plays itself.
+        if self.mode.get() == GameMode::Lan {
+            #[cfg(feature = "network")]
+            if self.lan.role() == Some(Role::Host)
+                && self.lan.is_away()
+                && self.game.borrow().is_bot_turn()
+            {
+                self.schedule(lan::AWAY_TURN_MS, Self::act_for_absent_guest);
+            }
+            return;
+        }
+        if self.game.borrow().is_bot_turn() {
+            if let Some(window) = self.main_window.upgrade() {
+                window.set_bot_think_time(self.paced(BOT_THINK_TIME_MS) as i64);
+            }
+            self.view.set_bot_thinking(true);
+            self.schedule(BOT_THINK_TIME_MS, Self::start_bot_move);
+        }
+    }
+
+    /// Checks, or folds to a bet, for a guest who is still away.
+    #[cfg(feature = "network")]
+    fn act_for_absent_guest(&self) {
+        let mut game = self.game.borrow_mut();
+        if !self.lan.is_away() || !game.is_bot_turn() {
+            return;
+        }
+        let action = if game.players[1].bet < game.current_bet {
+            Action::Fold
+        } else {
+            Action::Check
+        };
+        info!(?action, "acting for the absent guest");
+        if let Err(error) = game.seat_action(1, action) {
+            warn!(?action, %error, "cannot act for the absent guest");
+            return;
+        }
+        drop(game);
+        self.after_action();
+    }
+
+    /// Plans the bot's move on a worker thread; the result comes back
+    /// through the window's `bot_move_ready` callback.
+    fn start_bot_move(&self) {
+        let game = self.game.borrow();
+        if !game.is_bot_turn() {
+            drop(game);
+            self.view.set_bot_thinking(false);
+            return;
+        }
+        if self.mode.get() == GameMode::Tutorial {
+            let action = tutorial::bot_action(&game);
+            drop(game);
+            self.view.set_bot_thinking(false);
+            if let Err(error) = self.game.borrow_mut().player_action(action) {
+                warn!(?action, %error, "scripted bot move rejected");
+            }
+            self.after_action();
+            return;
+        }
+        let snapshot = game.snapshot();
+        drop(game);
+
+        let turn = self.bot_turn.get();
+        let mailbox = self.bot_mailbox.clone();
+        let window = self.main_window.clone();
+        worker::spawn(move || {
+            let bot_move = PokerGame::plan_bot_move(&snapshot).map(Box::new);
+            let mut slot = mailbox.lock().unwrap_or_else(PoisonError::into_inner);
+            // A cancelled worker finishing late must not replace a newer move.
+            if slot.as_ref().is_none_or(|(queued, _)| *queued < turn) {
+                *slot = Some((turn, bot_move));
+            }
+            drop(slot);
+            let posted = slint::invoke_from_event_loop(move || {
+                if let Some(window) = window.upgrade() {
+                    window.invoke_bot_move_ready();
+                }
+            });
+            if let Err(error) = posted {
+                warn!(%error, "cannot hand the bot move back to the UI");
+            }
+        });
+    }
+
+    pub fn finish_bot_move(&self) {
+        let delivered = self
+            .bot_mailbox
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some((turn, planned)) = delivered {
+            self.push(Command::Bot { turn, planned });
+        }
+    }
+
+    fn play_bot_move(&self, turn: u64, planned: Option<Box<BotMove>>) {
+        if turn != self.bot_turn.get() {
+            debug!("dropping a bot move planned before the table changed");
+            return;
+        }
+        self.view.set_bot_thinking(false);
+        if let Some(bot_move) = planned {
+            self.game.borrow_mut().apply_bot_move(*bot_move);
+        }
+        self.after_action();
+    }
+
+    fn run_phase_transition(&self) {
+        self.game.borrow_mut().next_phase();
+        // With everyone all-in the next street is already complete, so this
+        // keeps dealing until showdown.
+        self.after_action();
+    }
+
+    fn after_action(&self) {
+        let game = self.game.borrow();
+        let hand_complete = game.hand_complete;
+        let round_complete = game.is_betting_round_complete();
+        // No more betting and nobody folded: the rest of the hand is the
+        // showdown, so the bot's cards go face up first.
+        let showdown_next = round_complete
+            && game.players.iter().all(|player| !player.cards.is_empty())
+            && (game.phase == GamePhase::River
+                || game.players.iter().any(|player| player.chips.is_zero()));
+        drop(game);
+        if hand_complete {
+            self.finish_hand();
+            return;
+        }
+        if showdown_next && !self.view.bot_revealed() {
+            self.view.reveal_bot_cards(&self.game.borrow());
+            self.schedule(SHOWDOWN_REVEAL_MS, |state| state.push(Command::NextPhase));
+        } else if round_complete {
+            self.schedule(PHASE_TRANSITION_TIME_MS, |state| {
+                state.push(Command::NextPhase)
+            });
+        } else {
+            self.schedule_turn();
+        }
+        self.view.render_controls(&self.game.borrow());
+    }
+
+    fn finish_hand(&self) {
+        self.on_hand_complete();
+        self.check_goals();
+        if self
+            .main_window
+            .upgrade()
+            .is_some_and(|window| window.get_hand_bookmarked())
+        {
+            let log = self.game.borrow().hand_log().clone();
+            self.set_bookmark(log, true);
+        }
+        self.view.render_controls(&self.game.borrow());
+        self.deal_next_when_spectating();
+    }
+
+    /// Bots don't press "new hand", so a watched match deals on by itself.
+    fn deal_next_when_spectating(&self) {
+        if self.mode.get() == GameMode::Spectate {
+            self.schedule(SPECTATE_NEXT_HAND_MS, |state| state.push(Command::NewHand));
+        }
+    }
+
+    fn process_action(&self, action: Action) {
+        let mut game = self.game.borrow_mut();
+        // Clicks that land during a transition or the bot's turn are stale.
+        if self.turn_timer.running() || !game.is_user_turn() {
+            debug!(?action, "ignoring action out of turn");
+            return;
+        }
+        // The host checks and plays it, then sends it back.
+        #[cfg(feature = "network")]
+        if self.lan.role() == Some(Role::Guest) {
+            drop(game);
+            self.lan.send(&Message::Act { seat: 0, action });
+            return;
+        }
+        match game.seat_action(0, action) {
+            Ok(()) => {
+                debug!(pot = %game.pot, "user action applied");
+                drop(game);
+                self.after_action();
+            }
+            Err(error) => {
+                let error_msg = match (error, action) {
+                    (ActionError::InsufficientChips, Action::Call) => {
+                        "Not enough chips to call".to_string()
+                    }
+                    (ActionError::InsufficientChips, Action::Bet(_) | Action::Raise(_)) => {
+                        "Not enough chips to raise".to_string()
+                    }
+                    (ActionError::BelowMinRaise, _) => {
+                        format!("Minimum raise is to {}", game.current_bet + MIN_RAISE)
+                    }
+                    _ => error.to_string(),
+                };
+                drop(game);
+                self.update_ui();
+                self.view.set_error_message(error_msg);
+            }
+        }
+    }
+
+    /// Calls off the hand in progress: every stack goes back to what it was
+    /// when the hand began and the next hand is dealt.
+    fn abandon_hand(&self) {
+        let mut game = self.game.borrow_mut();
+        if game.hand_complete || self.is_guest() {
+            return;
+        }
+        info!(hand = game.hand_number, "hand abandoned");
+        let start_chips = game.hand_start_chips.clone();
+        for (player, chips) in game.players.iter_mut().zip(start_chips) {
+            player.chips = chips;
+        }
+        drop(game);
+        self.new_hand();
+    }
+
+    fn new_hand(&self) {
+        // Across a LAN only the host deals.
+        if self.is_guest() {
+            return;
+        }
+        self.cancel_pending();
+        let mut game = self.game.borrow_mut();
+        if game.is_game_over() {
+            let winner = game.get_winner_name();
+            info!(%winner, "game over");
+
+            if self.mode.get().is_freezeout() {
+                drop(game);
+                self.open_main_menu();
+                return;
+            }
+            // Outside a freezeout the match starts over, so the next hand
+            // deals into fresh stacks rather than the bust.
+            let chips = self.mode.get().starting_chips();
+            if let Some(mut tracker) = self.session.get() {
+                tracker.rebuy(chips.as_i32() - game.players[0].chips.as_i32());
+                self.session.set(Some(tracker));
+            }
+            game.players[0].chips = chips;
+            game.players[1].chips = chips;
+            game.dealer_position = 0;
+
+            drop(game);
+            if let Some(window) = self.main_window.upgrade() {
+                window.set_show_winner(true);
+                window.set_winner_name(winner.into());
+                window.set_hand_complete(true);
+            }
+            self.deal_next_when_spectating();
+            return;
+        }
+        game.dealer_position = (game.dealer_position + 1) % 2;
+        self.deal(&mut game);
+        drop(game);
+        self.schedule_turn();
+    }
+}
+
+impl Clone for AppState {
+    fn clone(&self) -> Self {
+        Self {
+            game: self.game.clone(),
+            difficulty: self.difficulty.clone(),
+            main_window: self.main_window.clone(),
+            turn_timer: self.turn_timer.clone(),
+            commands: self.commands.clone(),
+            command_timer: self.command_timer.clone(),
+            history: self.history.clone(),
+            bot_turn: self.bot_turn.clone(),
+            bot_mailbox: self.bot_mailbox.clone(),
+            sync_mailbox: self.sync_mailbox.clone(),
+            music: self.music.clone(),
+            presence: self.presence.clone(),
+            #[cfg(feature = "network")]
+            overlay: self.overlay.clone(),
+            recorder: self.recorder.clone(),
+            settings: self.settings.clone(),
+            bookmarks: self.bookmarks.clone(),
+            profile: self.profile.clone(),
+            paused: self.paused.clone(),
+            mode: self.mode.clone(),
+            session: self.session.clone(),
+            clock: self.clock.clone(),
+            diagnostics: self.diagnostics.clone(),
+            minimize: self.minimize.clone(),
+            #[cfg(feature = "network")]
+            lan: self.lan.clone(),
+            #[cfg(feature = "network")]
+            chat: self.chat.clone(),
+            view: self.view.clone(),
+        }
+    }
+}
+
+/// Every bot personality, in the order the menu lists them.
+fn personalities() -> Vec<BotPersonality> {
+    let first = BotPersonality::Balanced;
+    std::iter::successors(Some(first), |personality| {
+        Some(personality.next()).filter(|next| *next != first)
+    })
+    .collect()
+}
+
+/// Shows the image at `path` behind the table, or none if the path is empty
+/// or the image can't be loaded.
+fn load_background(window: &MainWindow, path: &str) {
+    let table = window.global::<TableStyle>();
+    let image = if path.is_empty() {
+        None
+    } else {
+        Image::load_from_path(Path::new(path))
+            .map_err(|_| warn!(path, "cannot load background image"))
+            .ok()
+    };
+    table.set_has_background(image.is_some());
+    table.set_background(image.unwrap_or_default());
+}
@@ -0,0 +1,72 @@
+//! The chat box at a LAN table: the lines shown, whose are muted, and how
+//! fast lines may go out. The wire side is [`crate::network::chat`].
+
+use super::{ChatLineUI, MainWindow};
+use crate::network::chat::FloodGuard;
+use slint::{Model, VecModel};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+// Older lines scroll away for good.
+const MAX_LINES: usize = 100;
+
+#[derive(Clone, Default)]
+pub struct Chat {
+    lines: Rc<VecModel<ChatLineUI>>,
+    // One guard for the user's own lines and, when hosting, one for the
+    // guest's.
+    own: Rc<RefCell<FloodGuard>>,
+    guest: Rc<RefCell<FloodGuard>>,
+    muted: Rc<RefCell<HashSet<usize>>>,
+}
+
+impl Chat {
+    /// Empties the box for a new match, with no one muted.
+    pub fn open(&self, window: &MainWindow) {
+        self.lines.set_vec(Vec::new());
+        self.muted.borrow_mut().clear();
+        self.own.take();
+        self.guest.take();
+        window.set_chat_lines(self.lines.clone().into());
+        window.set_chat_muted(false);
+    }
+
+    /// Whether the user may send a line now.
+    pub fn may_send(&self) -> bool {
+        self.own.borrow_mut().allow(Instant::now())
+    }
+
+    /// Whether a line the guest just sent is passed on.
+    pub fn may_pass(&self) -> bool {
+        self.guest.borrow_mut().allow(Instant::now())
+    }
+
+    /// Adds what `name` in `seat` said, unless that seat is muted.
+    pub fn show(&self, seat: usize, name: &str, text: &str) {
+        if self.muted.borrow().contains(&seat) {
+            return;
+        }
+        if self.lines.row_count() >= MAX_LINES {
+            self.lines.remove(0);
+        }
+        self.lines.push(ChatLineUI {
+            name: name.into(),
+            text: text.into(),
+            mine: seat == 0,
+        });
+    }
+
+    /// Mutes `seat`, or unmutes it; returns whether it is now muted.
+    pub fn toggle_mute(&self, seat: usize) -> bool {
+        let mut muted = self.muted.borrow_mut();
+        if !muted.remove(&seat) {
+            muted.insert(seat);
+        }
+        muted.contains(&seat)
+    }
+}
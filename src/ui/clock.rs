@@ -0,0 +1,115 @@
+use super::ClockWindow;
+use crate::game_mode::GameMode;
+use slint::{ComponentHandle, Timer, TimerMode};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tracing::warn;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// The tournament clock: a small always-on-top window with the blinds, the
+/// next level and how long the current level has run. Blinds go up by hands
+/// played rather than by time, so it counts down hands.
+#[derive(Clone, Default)]
+pub struct TournamentClock {
+    window: Rc<RefCell<Option<ClockWindow>>>,
+    timer: Rc<Timer>,
+    // The mode and hand number last dealt.
+    hand: Rc<Cell<(GameMode, u32)>>,
+    // The level on the clock and when it began.
+    level: Rc<Cell<Option<(u32, Instant)>>>,
+}
+
+impl TournamentClock {
+    pub fn open(&self) {
+        if self.window.borrow().is_none() {
+            let window = match ClockWindow::new() {
+                Ok(window) => window,
+                Err(error) => {
+                    warn!(%error, "cannot open the tournament clock");
+                    return;
+                }
+            };
+            *self.window.borrow_mut() = Some(window);
+        }
+        self.refresh();
+        if let Some(window) = self.window.borrow().as_ref() {
+            if let Err(error) = window.show() {
+                warn!(%error, "cannot show the tournament clock");
+            }
+        }
+        let clock = self.clone();
+        self.timer
+            .start(TimerMode::Repeated, Duration::from_secs(1), move || {
+                clock.refresh()
+            });
+    }
+
+    pub fn close(&self) {
+        self.timer.stop();
+        if let Some(window) = self.window.borrow_mut().take() {
+            if let Err(error) = window.hide() {
+                warn!(%error, "cannot hide the tournament clock");
+            }
+        }
+    }
+
+    /// Notes a new hand; the level's time restarts when the blinds go up.
+    pub fn hand_dealt(&self, mode: GameMode, hand_number: u32) {
+        self.hand.set((mode, hand_number));
+        let level = mode.blind_level(hand_number);
+        if self.level.get().map(|(shown, _)| shown) != Some(level) {
+            self.level.set(Some((level, Instant::now())));
+        }
+        self.refresh();
+    }
+
+    fn refresh(&self) {
+        let window = self.window.borrow();
+        let Some(window) = window.as_ref() else {
+            return;
+        };
+        let (mode, hand_number) = self.hand.get();
+        let (level, started) = self.level.get().unwrap_or((0, Instant::now()));
+        let (small_blind, big_blind) = mode.blinds(hand_number);
+        window.set_level(format!("{} · LEVEL {}", mode.name(), level + 1).into());
+        window.set_blinds(format!("{}/{}", small_blind, big_blind).into());
+        window.set_next_level(next_level_text(mode, hand_number).into());
+        let elapsed = started.elapsed().as_secs();
+        window.set_elapsed(format!("{:02}:{:02}", elapsed / 60, elapsed % 60).into());
+    }
+}
+
+fn next_level_text(mode: GameMode, hand_number: u32) -> String {
+    let Some(hands) = mode.hands_to_next_level(hand_number) else {
+        return "Top level: the blinds stay here".to_string();
+    };
+    let (small_blind, big_blind) = mode.blinds(hand_number + hands);
+    let when = if hands == 1 {
+        "after this hand".to_string()
+    } else {
+        format!("in {} hands", hands)
+    };
+    format!("Next {}/{} {}", small_blind, big_blind, when)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_level_text_counts_down_hands() {
+        assert_eq!(
+            next_level_text(GameMode::SitAndGo, 1),
+            "Next $20/$40 in 8 hands"
+        );
+        assert_eq!(
+            next_level_text(GameMode::SitAndGo, 8),
+            "Next $20/$40 after this hand"
+        );
+        assert!(next_level_text(GameMode::SitAndGo, 1000).starts_with("Top level"));
+    }
+}
@@ -0,0 +1,75 @@
+use poker_engine::{Action, BotMove};
+use std::collections::VecDeque;
+
+/// A request to change the game. Clicks, bot decisions and phase
+/// transitions are all queued as commands and run one at a time against
+/// whatever state the previous command left, so they can never interleave.
+#[derive(Clone, Debug)]
+pub enum Command {
+    Act(Action),
+    /// A move from the bot worker, tagged with the bot turn it was planned
+    /// for.
+    Bot {
+        turn: u64,
+        planned: Option<Box<BotMove>>,
+    },
+    NextPhase,
+    NewHand,
+    /// Calls the hand off from the pause menu.
+    AbandonHand,
+}
+
+impl Command {
+    fn is_user_request(&self) -> bool {
+        matches!(
+            self,
+            Command::Act(_) | Command::NewHand | Command::AbandonHand
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ActionQueue {
+    pending: VecDeque<Command>,
+}
+
+impl ActionQueue {
+    /// Queues `command`. A user command right behind another one of the same
+    /// kind is a double-click and is dropped.
+    pub fn push(&mut self, command: Command) {
+        if let Some(last) = self.pending.back() {
+            if command.is_user_request()
+                && std::mem::discriminant(last) == std::mem::discriminant(&command)
+            {
+                return;
+            }
+        }
+        self.pending.push_back(command);
+    }
+
+    pub fn pop(&mut self) -> Option<Command> {
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_keeps_order_and_drops_double_clicks() {
+        let mut queue = ActionQueue::default();
+        queue.push(Command::Act(Action::Call));
+        queue.push(Command::Act(Action::Call));
+        queue.push(Command::NextPhase);
+        queue.push(Command::NextPhase);
+        queue.push(Command::NewHand);
+        queue.push(Command::NewHand);
+
+        assert!(matches!(queue.pop(), Some(Command::Act(Action::Call))));
+        assert!(matches!(queue.pop(), Some(Command::NextPhase)));
+        assert!(matches!(queue.pop(), Some(Command::NextPhase)));
+        assert!(matches!(queue.pop(), Some(Command::NewHand)));
+        assert!(queue.pop().is_none());
+    }
+}
@@ -0,0 +1,146 @@
+use poker_engine::{compare_hands, evaluate_hand, Action, Chips, GameEvent, HandRank, PokerGame};
+
+// An uncontested pot this many big blinds deep is worth a remark.
+const BIG_POT_BB: u32 = 20;
+
+/// An announcer line for the most notable of `events`, if any of them is
+/// worth one.
+pub fn commentary(game: &PokerGame, events: &[GameEvent]) -> Option<String> {
+    events.iter().rev().find_map(|event| match event {
+        GameEvent::PotAwarded { seat, .. } if game.is_game_over() => Some(format!(
+            "{} {} every last chip. That's the match!",
+            name(game, *seat),
+            verb(game, *seat, "have", "has")
+        )),
+        GameEvent::PotAwarded { seat, amount, .. } => {
+            showdown_line(game).or_else(|| uncontested_line(game, *seat, *amount))
+        }
+        GameEvent::PlayerActed {
+            seat,
+            action: Action::AllIn,
+            ..
+        } => Some(format!(
+            "{} all in! Every chip is in the middle.",
+            if game.players[*seat].is_user {
+                "You're".to_string()
+            } else {
+                format!("{} is", game.players[*seat].name)
+            }
+        )),
+        _ => None,
+    })
+}
+
+fn showdown_line(game: &PokerGame) -> Option<String> {
+    let contested = game.players.iter().all(|player| !player.cards.is_empty());
+    if !game.showdown_done || !contested {
+        return None;
+    }
+    let hands: Vec<_> = game
+        .players
+        .iter()
+        .map(|player| evaluate_hand(&player.cards, &game.community_cards))
+        .collect();
+    let (user, bot) = (&hands[0], &hands[1]);
+    let (winner, winning, losing) = match compare_hands(user, bot) {
+        0 => {
+            return Some(format!(
+                "Chop it up! Both players hold {}.",
+                user.describe()
+            ))
+        }
+        order if order > 0 => (0, user, bot),
+        _ => (1, bot, user),
+    };
+
+    let line = if winning.rank == HandRank::ThreeOfAKind && losing.rank == HandRank::ThreeOfAKind {
+        "What a cooler — set over set!".to_string()
+    } else if losing.rank >= HandRank::ThreeOfAKind {
+        format!(
+            "What a cooler — {} beats {}!",
+            winning.describe(),
+            losing.describe()
+        )
+    } else if winning.rank >= HandRank::FourOfAKind {
+        format!(
+            "A monster! {} {} {}!",
+            name(game, winner),
+            verb(game, winner, "show", "shows"),
+            winning.describe()
+        )
+    } else {
+        format!(
+            "{} {} it with {}.",
+            name(game, winner),
+            verb(game, winner, "take", "takes"),
+            winning.describe()
+        )
+    };
+    Some(line)
+}
+
+fn uncontested_line(game: &PokerGame, seat: usize, amount: Chips) -> Option<String> {
+    (amount.amount() >= BIG_POT_BB * game.big_blind.amount()).then(|| {
+        format!(
+            "Big fold! {} {} down {} without a showdown.",
+            name(game, seat),
+            verb(game, seat, "take", "takes"),
+            amount
+        )
+    })
+}
+
+fn name(game: &PokerGame, seat: usize) -> &str {
+    &game.players[seat].name
+}
+
+fn verb<'a>(game: &PokerGame, seat: usize, user_verb: &'a str, other_verb: &'a str) -> &'a str {
+    if game.players[seat].is_user {
+        user_verb
+    } else {
+        other_verb
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::Card;
+
+    fn cards(list: &[(&str, &str, i32)]) -> Vec<Card> {
+        list.iter()
+            .map(|&(rank, suit, value)| Card::new(rank, suit, value))
+            .collect()
+    }
+
+    #[test]
+    fn test_set_over_set_is_a_cooler() {
+        let mut game = PokerGame::new();
+        game.players[0].cards = cards(&[("K", "♠", 13), ("K", "♥", 13)]);
+        game.players[1].cards = cards(&[("7", "♠", 7), ("7", "♥", 7)]);
+        game.community_cards = cards(&[
+            ("K", "♦", 13),
+            ("7", "♦", 7),
+            ("2", "♣", 2),
+            ("9", "♠", 9),
+            ("4", "♥", 4),
+        ]);
+        game.showdown_done = true;
+
+        let award = GameEvent::PotAwarded {
+            seat: 0,
+            amount: Chips::new(400),
+            pot: 0,
+        };
+        assert_eq!(
+            commentary(&game, &[award]).as_deref(),
+            Some("What a cooler — set over set!")
+        );
+        let check = GameEvent::PlayerActed {
+            seat: 1,
+            action: Action::Check,
+            amount: Chips::ZERO,
+        };
+        assert_eq!(commentary(&game, &[check]), None);
+    }
+}
@@ -0,0 +1,56 @@
+use poker_engine::{Card, PokerGame, BIG_BLIND};
+
+/// Table setups the debug panel can force, for reproducing reports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scenario {
+    PocketAces,
+    ShortStacks,
+    NearlyBust,
+}
+
+impl Scenario {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pocket-aces" => Some(Scenario::PocketAces),
+            "short-stacks" => Some(Scenario::ShortStacks),
+            "nearly-bust" => Some(Scenario::NearlyBust),
+            _ => None,
+        }
+    }
+
+    /// Adjusts the stacks and deals a fresh hand.
+    pub fn apply(self, game: &mut PokerGame) {
+        match self {
+            Scenario::PocketAces => {}
+            Scenario::ShortStacks => {
+                for player in &mut game.players {
+                    player.chips = BIG_BLIND * 8;
+                }
+            }
+            Scenario::NearlyBust => game.players[0].chips = BIG_BLIND,
+        }
+        game.start_hand();
+        if self == Scenario::PocketAces {
+            let aces = vec![Card::new("A", "♠", 14), Card::new("A", "♥", 14)];
+            game.set_hole_cards(0, aces);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenarios_set_up_the_table() {
+        let mut game = PokerGame::new();
+        Scenario::from_name("short-stacks")
+            .unwrap()
+            .apply(&mut game);
+        assert!(game.is_push_fold());
+
+        Scenario::PocketAces.apply(&mut game);
+        assert!(game.players[0].cards.iter().all(|card| card.value == 14));
+        assert_eq!(Scenario::from_name("royal-flush"), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! The performance overlay (F3): how long frames take to draw, which timers
+//! are waiting to fire and how long each engine command held the event
+//! loop, for spotting jank and attaching to reports.
+
+use super::MainWindow;
+use slint::{ComponentHandle, RenderingState, SharedString, Timer, TimerMode, VecModel};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tracing::warn;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+const REFRESH_MS: u64 = 500;
+const SAMPLE_COUNT: usize = 120;
+
+/// The most recent durations of something, with when each ended.
+#[derive(Debug, Default)]
+struct Samples {
+    recent: VecDeque<(Instant, Duration)>,
+}
+
+impl Samples {
+    fn push(&mut self, ended: Instant, duration: Duration) {
+        if self.recent.len() == SAMPLE_COUNT {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((ended, duration));
+    }
+
+    /// "last 1.2 ms · avg 0.8 ms · max 4.0 ms", or "none yet".
+    fn summary(&self) -> String {
+        let Some(&(_, last)) = self.recent.back() else {
+            return "none yet".to_string();
+        };
+        let total: Duration = self.recent.iter().map(|&(_, duration)| duration).sum();
+        let max = self.recent.iter().map(|&(_, duration)| duration).max();
+        format!(
+            "last {} · avg {} · max {}",
+            millis(last),
+            millis(total / self.recent.len() as u32),
+            millis(max.unwrap_or_default())
+        )
+    }
+
+    fn count_since(&self, since: Instant) -> usize {
+        self.recent
+            .iter()
+            .filter(|&&(ended, _)| ended >= since)
+            .count()
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[derive(Clone, Default)]
+pub struct Diagnostics {
+    frames: Rc<RefCell<Samples>>,
+    frame_started: Rc<Cell<Option<Instant>>>,
+    ticks: Rc<RefCell<Samples>>,
+    timer: Rc<Timer>,
+    recording: Rc<Cell<bool>>,
+    notifier_set: Rc<Cell<bool>>,
+}
+
+impl Diagnostics {
+    /// Starts timing frames and refreshing the overlay, asking `timers` for
+    /// the timers to list.
+    pub fn start(
+        &self,
+        window: &MainWindow,
+        timers: impl Fn() -> Vec<(&'static str, bool)> + 'static,
+    ) {
+        // A window takes only one notifier, so it stays and just stops
+        // recording while the overlay is closed.
+        if !self.notifier_set.replace(true) {
+            let diagnostics = self.clone();
+            let notified = window
+                .window()
+                .set_rendering_notifier(move |state, _| diagnostics.frame(state));
+            if let Err(error) = notified {
+                warn!(%error, "cannot time frames with this renderer");
+            }
+        }
+        self.recording.set(true);
+
+        let diagnostics = self.clone();
+        let window = window.as_weak();
+        self.timer.start(
+            TimerMode::Repeated,
+            Duration::from_millis(REFRESH_MS),
+            move || {
+                if let Some(window) = window.upgrade() {
+                    let lines: Vec<SharedString> = diagnostics
+                        .lines(&timers())
+                        .into_iter()
+                        .map(Into::into)
+                        .collect();
+                    window.set_diagnostics(Rc::new(VecModel::from(lines)).into());
+                }
+            },
+        );
+    }
+
+    pub fn stop(&self) {
+        self.timer.stop();
+        self.recording.set(false);
+    }
+
+    /// Runs one engine command, noting how long it held the event loop.
+    pub fn time_tick(&self, work: impl FnOnce()) {
+        if !self.recording.get() {
+            return work();
+        }
+        let started = Instant::now();
+        work();
+        self.ticks
+            .borrow_mut()
+            .push(Instant::now(), started.elapsed());
+    }
+
+    fn frame(&self, state: RenderingState) {
+        if !self.recording.get() {
+            return;
+        }
+        match state {
+            RenderingState::BeforeRendering => self.frame_started.set(Some(Instant::now())),
+            RenderingState::AfterRendering => {
+                if let Some(started) = self.frame_started.take() {
+                    self.frames
+                        .borrow_mut()
+                        .push(Instant::now(), started.elapsed());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lines(&self, timers: &[(&str, bool)]) -> Vec<String> {
+        let frames = self.frames.borrow();
+        let second_ago = Instant::now()
+            .checked_sub(Duration::from_secs(1))
+            .unwrap_or_else(Instant::now);
+        let running: Vec<&str> = timers
+            .iter()
+            .filter(|&&(_, running)| running)
+            .map(|&(name, _)| name)
+            .collect();
+        vec![
+            format!("Frames/s: {}", frames.count_since(second_ago)),
+            format!("Frame draw: {}", frames.summary()),
+            format!("Engine ticks: {}", self.ticks.borrow().summary()),
+            format!(
+                "Timers pending: {}",
+                if running.is_empty() {
+                    "none".to_string()
+                } else {
+                    running.join(", ")
+                }
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_summarize_the_recent_durations() {
+        let mut samples = Samples::default();
+        assert_eq!(samples.summary(), "none yet");
+
+        let now = Instant::now();
+        for ms in [4, 1, 1] {
+            samples.push(now, Duration::from_millis(ms));
+        }
+        assert_eq!(samples.summary(), "last 1.0 ms · avg 2.0 ms · max 4.0 ms");
+        assert_eq!(samples.count_since(now), 3);
+
+        for _ in 0..SAMPLE_COUNT {
+            samples.push(now, Duration::from_millis(2));
+        }
+        assert_eq!(samples.recent.len(), SAMPLE_COUNT);
+        assert_eq!(samples.summary(), "last 2.0 ms · avg 2.0 ms · max 2.0 ms");
+    }
+}
@@ -0,0 +1,189 @@
+//! Emotes: short reactions shown in a speech bubble over a player's avatar.
+//! The user picks them from the table; the bot answers them and reacts to
+//! big pots on its own unless emotes are muted.
+
+use super::MainWindow;
+use poker_engine::{BotPersonality, GameEvent, PokerGame};
+use slint::{SharedString, Timer, TimerMode, VecModel};
+use std::rc::Rc;
+use std::time::Duration;
+
+const BUBBLE_MS: u64 = 3000;
+const REPLY_DELAY_MS: u64 = 1200;
+// A pot worth reacting to, in big blinds.
+const BIG_POT_BLINDS: u32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Emote {
+    Nice,
+    Laugh,
+    Cool,
+    Angry,
+    Thinking,
+    Wave,
+}
+
+impl Emote {
+    /// In the order of the picker.
+    pub const ALL: [Emote; 6] = [
+        Emote::Nice,
+        Emote::Laugh,
+        Emote::Cool,
+        Emote::Angry,
+        Emote::Thinking,
+        Emote::Wave,
+    ];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Emote::Nice => "👍",
+            Emote::Laugh => "😂",
+            Emote::Cool => "😎",
+            Emote::Angry => "😤",
+            Emote::Thinking => "🤔",
+            Emote::Wave => "👋",
+        }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            Emote::Nice => "Nice hand",
+            Emote::Laugh => "Ha!",
+            Emote::Cool => "Too easy",
+            Emote::Angry => "Seriously?",
+            Emote::Thinking => "Hmm…",
+            Emote::Wave => "Good game",
+        }
+    }
+
+    fn bubble(&self) -> SharedString {
+        format!("{} {}", self.icon(), self.text()).into()
+    }
+}
+
+/// The picker's icons, in the order of `Emote::ALL`.
+pub fn fill(window: &MainWindow) {
+    let icons: Vec<SharedString> = Emote::ALL.iter().map(|emote| emote.icon().into()).collect();
+    window.set_emote_icons(Rc::new(VecModel::from(icons)).into());
+}
+
+/// The bubbles over both avatars and the bot's delayed replies.
+#[derive(Clone, Default)]
+pub struct Emotes {
+    player_timer: Rc<Timer>,
+    bot_timer: Rc<Timer>,
+    reply_timer: Rc<Timer>,
+}
+
+impl Emotes {
+    /// Shows the user's emote, and the bot's answer a moment later.
+    pub fn send(&self, window: &slint::Weak<MainWindow>, game: &PokerGame, emote: Emote) {
+        self.show(window, 0, emote);
+        let reply = reply_to(emote, game.bot_personality);
+        let emotes = self.clone();
+        let window = window.clone();
+        self.reply_timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(REPLY_DELAY_MS),
+            move || emotes.show(&window, 1, reply),
+        );
+    }
+
+    /// Lets the bot react to anything notable in `events`.
+    pub fn notice(&self, window: &slint::Weak<MainWindow>, game: &PokerGame, events: &[GameEvent]) {
+        if let Some(emote) = bot_emote(game, events) {
+            self.show(window, 1, emote);
+        }
+    }
+
+    fn show(&self, window: &slint::Weak<MainWindow>, seat: usize, emote: Emote) {
+        let Some(main_window) = window.upgrade() else {
+            return;
+        };
+        let timer = if seat == 0 {
+            main_window.set_player_emote(emote.bubble());
+            &self.player_timer
+        } else {
+            if main_window.get_mute_emotes() {
+                return;
+            }
+            main_window.set_bot_emote(emote.bubble());
+            &self.bot_timer
+        };
+        let window = window.clone();
+        timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(BUBBLE_MS),
+            move || {
+                if let Some(main_window) = window.upgrade() {
+                    if seat == 0 {
+                        main_window.set_player_emote("".into());
+                    } else {
+                        main_window.set_bot_emote("".into());
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// How the bot answers the user's emote; the maniac laughs at everything
+/// but a goodbye.
+fn reply_to(emote: Emote, personality: BotPersonality) -> Emote {
+    match (emote, personality) {
+        (Emote::Wave, _) => Emote::Wave,
+        (_, BotPersonality::Maniac) => Emote::Laugh,
+        (Emote::Nice, _) => Emote::Nice,
+        (Emote::Laugh, _) => Emote::Cool,
+        (Emote::Cool | Emote::Thinking, _) => Emote::Thinking,
+        (Emote::Angry, _) => Emote::Laugh,
+    }
+}
+
+/// The bot's own reaction to a big pot at a showdown: smug if it won,
+/// gracious (or, for the maniac, annoyed) if it lost.
+fn bot_emote(game: &PokerGame, events: &[GameEvent]) -> Option<Emote> {
+    let contested = game.players.iter().all(|player| !player.cards.is_empty());
+    if !contested {
+        return None;
+    }
+    let big_pot = game.big_blind * BIG_POT_BLINDS;
+    events.iter().find_map(|event| match event {
+        GameEvent::PotAwarded { seat, amount, .. } if *amount >= big_pot => {
+            Some(if !game.players[*seat].is_user {
+                Emote::Cool
+            } else if game.bot_personality == BotPersonality::Maniac {
+                Emote::Angry
+            } else {
+                Emote::Nice
+            })
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::Chips;
+
+    #[test]
+    fn test_bot_reacts_to_big_showdown_pots() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        let award = |seat, amount| GameEvent::PotAwarded {
+            seat,
+            amount: Chips::new(amount),
+            pot: 0,
+        };
+        assert_eq!(bot_emote(&game, &[award(1, 40)]), None);
+        assert_eq!(bot_emote(&game, &[award(1, 400)]), Some(Emote::Cool));
+        assert_eq!(bot_emote(&game, &[award(0, 400)]), Some(Emote::Nice));
+        game.bot_personality = BotPersonality::Maniac;
+        assert_eq!(bot_emote(&game, &[award(0, 400)]), Some(Emote::Angry));
+        assert_eq!(reply_to(Emote::Nice, game.bot_personality), Emote::Laugh);
+
+        game.players[0].cards.clear();
+        assert_eq!(bot_emote(&game, &[award(1, 400)]), None);
+    }
+}
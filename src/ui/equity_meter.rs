@@ -0,0 +1,60 @@
+use super::worker;
+use super::MainWindow;
+use poker_engine::{estimate_equity, PokerGame};
+use rand::thread_rng;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+const EQUITY_ITERATIONS: u32 = 3000;
+
+/// Keeps the win probability bar up to date. The Monte Carlo run happens on
+/// a worker thread once per street; a result that arrives after the table
+/// moved on is dropped.
+#[derive(Clone, Default)]
+pub struct EquityMeter {
+    latest_request: Arc<AtomicU64>,
+    // Hand number and board size the bar was last computed for.
+    shown_for: Rc<Cell<Option<(u32, usize)>>>,
+}
+
+impl EquityMeter {
+    pub fn update(&self, window: &slint::Weak<MainWindow>, game: &PokerGame) {
+        let hole = &game.players[0].cards;
+        if hole.is_empty() || game.hand_complete {
+            self.shown_for.set(None);
+            self.latest_request.fetch_add(1, Ordering::SeqCst);
+            if let Some(window) = window.upgrade() {
+                window.set_win_probability(-1.0);
+            }
+            return;
+        }
+        let key = (game.hand_number, game.community_cards.len());
+        if self.shown_for.get() == Some(key) {
+            return;
+        }
+        self.shown_for.set(Some(key));
+
+        let request = self.latest_request.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest_request = self.latest_request.clone();
+        let hole = hole.clone();
+        let board = game.community_cards.clone();
+        let window = window.clone();
+        worker::spawn(move || {
+            let equity = estimate_equity(&hole, &board, EQUITY_ITERATIONS, &mut thread_rng());
+            let posted = slint::invoke_from_event_loop(move || {
+                if latest_request.load(Ordering::SeqCst) != request {
+                    return;
+                }
+                if let Some(window) = window.upgrade() {
+                    window.set_win_probability(equity as f32);
+                }
+            });
+            if let Err(error) = posted {
+                warn!(%error, "cannot hand the win probability back to the UI");
+            }
+        });
+    }
+}
@@ -0,0 +1,162 @@
+//! The rules and hand-ranking help screen.
+
+use super::models::cards_model;
+use super::{HandRankUI, HelpTerm, MainWindow};
+use poker_engine::{Card, HandRank};
+use slint::{SharedString, VecModel};
+use std::rc::Rc;
+
+// Strongest first, each with a five-card example.
+const HAND_RANKS: [(HandRank, &str, &str, &str); 10] = [
+    (
+        HandRank::StraightFlush,
+        "Royal Flush",
+        "A K Q J 10, all one suit.",
+        "A♠ K♠ Q♠ J♠ 10♠",
+    ),
+    (
+        HandRank::StraightFlush,
+        "Straight Flush",
+        "Five in a row, all one suit.",
+        "9♥ 8♥ 7♥ 6♥ 5♥",
+    ),
+    (
+        HandRank::FourOfAKind,
+        "Four of a Kind",
+        "Four cards of one rank.",
+        "Q♣ Q♦ Q♥ Q♠ 4♦",
+    ),
+    (
+        HandRank::FullHouse,
+        "Full House",
+        "Three of one rank and two of another.",
+        "J♠ J♥ J♦ 8♣ 8♠",
+    ),
+    (
+        HandRank::Flush,
+        "Flush",
+        "Any five of one suit.",
+        "K♦ 10♦ 7♦ 4♦ 2♦",
+    ),
+    (
+        HandRank::Straight,
+        "Straight",
+        "Five in a row. The ace can be high or low.",
+        "10♣ 9♦ 8♠ 7♥ 6♣",
+    ),
+    (
+        HandRank::ThreeOfAKind,
+        "Three of a Kind",
+        "Three cards of one rank.",
+        "7♠ 7♥ 7♣ K♦ 2♠",
+    ),
+    (
+        HandRank::TwoPair,
+        "Two Pair",
+        "Two different pairs.",
+        "A♥ A♣ 9♠ 9♦ 5♥",
+    ),
+    (
+        HandRank::Pair,
+        "Pair",
+        "Two cards of one rank.",
+        "10♥ 10♠ K♣ 6♦ 3♠",
+    ),
+    (
+        HandRank::HighCard,
+        "High Card",
+        "Nothing else: the highest card plays.",
+        "A♦ J♣ 8♥ 5♠ 3♣",
+    ),
+];
+
+const RULES: [&str; 7] = [
+    "Each player is dealt two hole cards that only they can see.",
+    "Before the cards are dealt the blinds are posted. Heads up, the dealer posts the small blind and acts first before the flop, then last on every later street.",
+    "There are four betting rounds: before the flop, then after the flop (three shared cards), the turn (a fourth) and the river (a fifth).",
+    "On your turn you can fold, check if nobody has bet, call to match a bet, or raise to make it bigger.",
+    "A raise must be at least as big as the bet or raise before it. You can always go all-in with what's left.",
+    "If nobody folds, the hands are shown and the best five cards out of your two and the five shared cards win.",
+    "Equal hands split the pot. Suits never break a tie.",
+];
+
+const GLOSSARY: [(&str, &str); 12] = [
+    ("All-in", "Betting every chip you have left."),
+    ("Blinds", "Forced bets posted before the cards are dealt."),
+    ("Board", "The shared cards in the middle of the table."),
+    ("Button", "The dealer's seat, which moves every hand."),
+    ("Check", "Passing the action without betting."),
+    ("Flop", "The first three shared cards, dealt together."),
+    (
+        "Kicker",
+        "A side card that breaks ties between equal hands.",
+    ),
+    ("Nuts", "The best possible hand on the current board."),
+    ("Outs", "Cards still to come that would improve your hand."),
+    (
+        "Pot odds",
+        "The price of a call compared with the size of the pot.",
+    ),
+    ("River", "The fifth and last shared card."),
+    ("Turn", "The fourth shared card."),
+];
+
+/// Fills the help screen's models; its text never changes.
+pub fn fill(window: &MainWindow) {
+    let ranks: Vec<HandRankUI> = HAND_RANKS
+        .iter()
+        .map(|&(_, name, description, example)| HandRankUI {
+            name: name.into(),
+            description: description.into(),
+            example: cards_model(&cards(example)),
+        })
+        .collect();
+    window.set_help_hand_ranks(Rc::new(VecModel::from(ranks)).into());
+
+    let rules: Vec<SharedString> = RULES.iter().map(|&rule| rule.into()).collect();
+    window.set_help_rules(Rc::new(VecModel::from(rules)).into());
+
+    let glossary: Vec<HelpTerm> = GLOSSARY
+        .iter()
+        .map(|&(term, meaning)| HelpTerm {
+            term: term.into(),
+            meaning: meaning.into(),
+        })
+        .collect();
+    window.set_help_glossary(Rc::new(VecModel::from(glossary)).into());
+}
+
+/// Cards written like "A♠ 10♦".
+fn cards(text: &str) -> Vec<Card> {
+    text.split_whitespace()
+        .filter_map(|card| {
+            let suit_at = card.char_indices().last()?.0;
+            let (rank, suit) = card.split_at(suit_at);
+            let value = match rank {
+                "A" => 14,
+                "K" => 13,
+                "Q" => 12,
+                "J" => 11,
+                number => number.parse().ok()?,
+            };
+            Some(Card::new(rank, suit, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::evaluate_hand;
+
+    #[test]
+    fn test_hand_rank_examples_make_their_hands() {
+        for (rank, name, _, example) in HAND_RANKS {
+            let cards = cards(example);
+            assert_eq!(cards.len(), 5, "{}", name);
+            let evaluated = evaluate_hand(&cards, &[]);
+            assert_eq!(evaluated.rank, rank, "{}", name);
+            assert!(evaluated.describe().starts_with(name), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,564 @@
+//! The table's end of a LAN match: browsing the lobby, waiting for a guest
+//! or connecting to a host, then passing messages while the match is on.
+//! A host keeps taking connections once the guest is seated, as spectators.
+//! If the other player drops, their seat is held for [`GRACE`] while the
+//! guest reconnects; the host then replays the hand so far. Only a
+//! connection presenting the token the guest was welcomed with gets the
+//! held seat back; any other watches. An online match
+//! through a rendezvous service plays the same way, but can't be rejoined.
+//! What the messages mean for the game is up to
+//! [`super::app_state::AppState`].
+
+use crate::network::discovery::{Announcer, Listing, TableBrowser};
+use crate::network::online::{self, Hosting};
+use crate::network::protocol::{TableInfo, PROTOCOL_VERSION};
+use crate::network::{self, Connection, Lobby, Message};
+use poker_engine::{Chips, GameEvent, PokerGame};
+use slint::{Timer, TimerMode};
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::net::{Shutdown, TcpStream};
+use std::rc::Rc;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tracing::{debug, info, warn};
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+const POLL_MS: u64 = 50;
+const ANNOUNCE_MS: u64 = 1000;
+const BROWSE_MS: u64 = 500;
+const VARIANT: &str = "No-Limit Hold'em, heads up";
+/// How long a dropped player's seat is held.
+pub const GRACE: Duration = Duration::from_secs(90);
+// How often a dropped guest tries to reach the host again.
+const RETRY: Duration = Duration::from_secs(2);
+// How long a connection to a held seat has to present the guest's token.
+const CLAIM_WAIT: Duration = Duration::from_secs(3);
+/// How long a dropped guest's turn waits before checking or folding.
+pub const AWAY_TURN_MS: u64 = 15_000;
+
+/// The blinds a table can be created with, in the lobby's order.
+pub const STAKES: [(u32, u32); 4] = [(5, 10), (10, 20), (25, 50), (50, 100)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Deals and runs the game; the guest is seat 1.
+    Host,
+    /// Follows the host's game, sending only its own actions.
+    Guest,
+}
+
+pub enum LanEvent {
+    /// The code of the table opened online.
+    Code(String),
+    Connected,
+    /// The other player dropped; their seat is held.
+    Away,
+    /// The other player is back in the held seat.
+    Resumed,
+    Received(Message),
+    Lost(String),
+}
+
+#[derive(Clone, Default)]
+pub struct Lan {
+    role: Rc<Cell<Option<Role>>>,
+    lobby: Rc<RefCell<Option<Lobby>>>,
+    joining: Rc<RefCell<Option<Receiver<io::Result<Connection>>>>>,
+    hosting: Rc<RefCell<Option<Receiver<io::Result<Hosting>>>>>,
+    // The table open at the rendezvous service, until a guest comes.
+    rendezvous: Rc<RefCell<Option<TcpStream>>>,
+    online: Rc<Cell<bool>>,
+    connection: Rc<RefCell<Option<Connection>>>,
+    // The host a guest joined, to reconnect to.
+    address: Rc<RefCell<String>>,
+    // What reclaims the guest's seat: given out by the host, kept by the
+    // guest.
+    token: Rc<RefCell<Option<String>>>,
+    // Connections to a held seat, with when they came, until they present
+    // the token or turn out to be spectators.
+    claims: Rc<RefCell<Vec<(Connection, Instant)>>>,
+    // When the other player dropped, while their seat is held.
+    away: Rc<Cell<Option<Instant>>>,
+    retry_at: Rc<Cell<Option<Instant>>>,
+    // What the guest has been sent since the hand began, to catch it up
+    // on reconnecting.
+    dealt: Rc<RefCell<Vec<Message>>>,
+    spectators: Rc<RefCell<Vec<Connection>>>,
+    // What spectators have been sent since the hand began, for any who
+    // join partway through it.
+    watched: Rc<RefCell<Vec<Message>>>,
+    timer: Rc<Timer>,
+    // The hosted table, as announced to the lobby.
+    table: Rc<RefCell<Option<TableInfo>>>,
+    announcer: Rc<RefCell<Option<Announcer>>>,
+    announce_timer: Rc<Timer>,
+    browser: Rc<RefCell<Option<TableBrowser>>>,
+    browse_timer: Rc<Timer>,
+}
+
+impl Lan {
+    /// The role at a connected table, or one whose other player's seat is
+    /// held; `None` while waiting or offline.
+    pub fn role(&self) -> Option<Role> {
+        let seated = self.connection.borrow().is_some() || self.is_away();
+        self.role.get().filter(|_| seated)
+    }
+
+    /// Whether the other player has dropped and their seat is held.
+    pub fn is_away(&self) -> bool {
+        self.away.get().is_some()
+    }
+
+    /// The blinds of the table being hosted.
+    pub fn stakes(&self) -> Option<(Chips, Chips)> {
+        let table = self.table.borrow();
+        let info = table.as_ref()?;
+        Some((Chips::new(info.small_blind), Chips::new(info.big_blind)))
+    }
+
+    /// Opens a table named `name` to a guest and announces it to the
+    /// lobby; returns the address to give a guest who joins by hand.
+    pub fn host(
+        &self,
+        name: &str,
+        (small_blind, big_blind): (u32, u32),
+        poll: impl Fn() + 'static,
+    ) -> io::Result<String> {
+        self.close();
+        *self.lobby.borrow_mut() = Some(Lobby::open()?);
+        self.role.set(Some(Role::Host));
+        self.timer
+            .start(TimerMode::Repeated, Duration::from_millis(POLL_MS), poll);
+        let info = TableInfo {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            variant: VARIANT.to_string(),
+            small_blind,
+            big_blind,
+            seats: 2,
+            seats_open: 1,
+            spectators: 0,
+            port: network::DEFAULT_PORT,
+            websocket_port: network::WEBSOCKET_PORT,
+        };
+        *self.table.borrow_mut() = Some(info);
+        match Announcer::open() {
+            Ok(announcer) => {
+                *self.announcer.borrow_mut() = Some(announcer);
+                let (table, announcer) = (self.table.clone(), self.announcer.clone());
+                self.announce_timer.start(
+                    TimerMode::Repeated,
+                    Duration::from_millis(ANNOUNCE_MS),
+                    move || {
+                        if let (Some(announcer), Some(info)) =
+                            (announcer.borrow().as_ref(), table.borrow().as_ref())
+                        {
+                            announcer.announce(info);
+                        }
+                    },
+                );
+            }
+            Err(error) => warn!(%error, "cannot announce the table; guests must join by address"),
+        }
+        let address = network::local_address().unwrap_or_else(|| "this computer".into());
+        Ok(format!(
+            "{}:{} (browsers: ws://{}:{})",
+            address,
+            network::DEFAULT_PORT,
+            address,
+            network::WEBSOCKET_PORT
+        ))
+    }
+
+    /// Opens a table named `name` on the rendezvous service at `server`;
+    /// its code comes as [`LanEvent::Code`].
+    pub fn host_online(
+        &self,
+        server: &str,
+        name: &str,
+        (small_blind, big_blind): (u32, u32),
+        poll: impl Fn() + 'static,
+    ) {
+        self.close();
+        *self.hosting.borrow_mut() = Some(online::host(server, name));
+        self.online.set(true);
+        self.role.set(Some(Role::Host));
+        *self.table.borrow_mut() = Some(TableInfo {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            variant: VARIANT.to_string(),
+            small_blind,
+            big_blind,
+            seats: 2,
+            seats_open: 1,
+            ..TableInfo::default()
+        });
+        self.timer
+            .start(TimerMode::Repeated, Duration::from_millis(POLL_MS), poll);
+    }
+
+    /// Joins the table with `code` on the rendezvous service at `server`.
+    pub fn join_online(&self, server: &str, code: &str, poll: impl Fn() + 'static) {
+        self.close();
+        *self.joining.borrow_mut() = Some(online::join(server, code));
+        self.online.set(true);
+        self.role.set(Some(Role::Guest));
+        self.timer
+            .start(TimerMode::Repeated, Duration::from_millis(POLL_MS), poll);
+    }
+
+    pub fn join(&self, address: &str, poll: impl Fn() + 'static) {
+        self.close();
+        *self.joining.borrow_mut() = Some(network::join(address));
+        *self.address.borrow_mut() = address.to_string();
+        self.role.set(Some(Role::Guest));
+        self.timer
+            .start(TimerMode::Repeated, Duration::from_millis(POLL_MS), poll);
+    }
+
+    /// Leaves the match or stops waiting for one.
+    pub fn close(&self) {
+        self.timer.stop();
+        self.announce_timer.stop();
+        self.table.take();
+        self.announcer.take();
+        self.role.set(None);
+        self.lobby.take();
+        self.joining.take();
+        self.hosting.take();
+        self.online.set(false);
+        if let Some(socket) = self.rendezvous.take() {
+            let _ = socket.shutdown(Shutdown::Both);
+        }
+        self.spectators.take();
+        self.watched.take();
+        self.dealt.take();
+        self.token.take();
+        self.claims.take();
+        self.away.take();
+        self.retry_at.take();
+        if self.connection.take().is_some() {
+            info!("left the LAN match");
+        }
+    }
+
+    /// What has happened since the last poll.
+    pub fn poll(&self) -> Vec<LanEvent> {
+        let mut events = Vec::new();
+        if let Some(connected) = self.try_connect(&mut events) {
+            let seated = self.connection.borrow().is_some();
+            let hosting = self.role.get() == Some(Role::Host);
+            match connected {
+                Ok(spectator) if seated => self.watch(spectator),
+                Err(error) if seated => warn!(%error, "cannot take a spectator"),
+                Ok(claim) if hosting && self.is_away() => {
+                    self.claims.borrow_mut().push((claim, Instant::now()));
+                }
+                Ok(connection) => self.seat(connection, &mut events),
+                Err(error) if self.is_away() => {
+                    debug!(%error, "host not back yet");
+                    self.joining.take();
+                    self.retry_at.set(Some(Instant::now() + RETRY));
+                }
+                Err(error) => {
+                    warn!(%error, "cannot start the LAN match");
+                    self.close();
+                    events.push(LanEvent::Lost(error.to_string()));
+                    return events;
+                }
+            }
+        }
+        self.settle_claims(&mut events);
+        self.keep_spectators();
+        let dropped = loop {
+            let received = match self.connection.borrow().as_ref() {
+                Some(connection) => connection.receive(),
+                None => break false,
+            };
+            match received {
+                // This app has no spectator's view of its own.
+                Ok(Some(Message::Watching)) => {
+                    self.close();
+                    events.push(LanEvent::Lost("That table is full".into()));
+                    return events;
+                }
+                Ok(Some(Message::Welcome { token })) => *self.token.borrow_mut() = Some(token),
+                Ok(Some(message)) => events.push(LanEvent::Received(message)),
+                Ok(None) => break false,
+                Err(_) => break true,
+            }
+        };
+        if dropped && self.online.get() {
+            warn!("online match connection dropped");
+            self.close();
+            events.push(LanEvent::Lost("The other player left".into()));
+            return events;
+        }
+        if dropped {
+            warn!("LAN connection dropped; holding the seat");
+            self.connection.take();
+            self.away.set(Some(Instant::now()));
+            if let Some(info) = self.table.borrow_mut().as_mut() {
+                info.seats_open = 1;
+            }
+            if self.role.get() == Some(Role::Guest) {
+                self.retry_at.set(Some(Instant::now()));
+            }
+            events.push(LanEvent::Away);
+        }
+        if let Some(since) = self.away.get() {
+            if since.elapsed() >= GRACE {
+                warn!("the other player didn't come back");
+                self.close();
+                events.push(LanEvent::Lost("The other player left".into()));
+            } else if self.retry_at.get().is_some_and(|at| Instant::now() >= at) {
+                self.retry_at.set(None);
+                *self.joining.borrow_mut() = Some(network::join(&self.address.borrow()));
+            }
+        }
+        events
+    }
+
+    /// Puts `connection` in the other player's seat, or back in the held
+    /// one.
+    fn seat(&self, connection: Connection, events: &mut Vec<LanEvent>) {
+        let resumed = self.is_away();
+        match self.role.get() {
+            Some(Role::Host) if !resumed => {
+                let token = format!("{:032x}", rand::random::<u128>());
+                if let Err(error) = connection.send(&Message::Welcome {
+                    token: token.clone(),
+                }) {
+                    warn!(%error, "cannot welcome the guest");
+                }
+                *self.token.borrow_mut() = Some(token);
+            }
+            Some(Role::Guest) if resumed => {
+                if let Some(token) = self.token.borrow().clone() {
+                    if let Err(error) = connection.send(&Message::Rejoin { token }) {
+                        warn!(%error, "cannot reclaim the seat");
+                    }
+                }
+            }
+            _ => {}
+        }
+        *self.connection.borrow_mut() = Some(connection);
+        if let Some(info) = self.table.borrow_mut().as_mut() {
+            info.seats_open = 0;
+        }
+        self.joining.take();
+        self.hosting.take();
+        self.rendezvous.take();
+        if self.away.take().is_some() {
+            info!(role = ?self.role.get(), "LAN match resumed");
+            for message in self.dealt.borrow().iter() {
+                self.send(message);
+            }
+            events.push(LanEvent::Resumed);
+        } else {
+            info!(role = ?self.role.get(), "LAN match connected");
+            events.push(LanEvent::Connected);
+        }
+    }
+
+    /// Gives the held seat back to the connection presenting the guest's
+    /// token. Any other, or one silent for [`CLAIM_WAIT`], only watches.
+    fn settle_claims(&self, events: &mut Vec<LanEvent>) {
+        let claims = self.claims.take();
+        let mut waiting = Vec::new();
+        for (claim, since) in claims {
+            let presented = match claim.receive() {
+                Ok(Some(Message::Rejoin { token })) => {
+                    Some(self.token.borrow().as_deref() == Some(token.as_str()))
+                }
+                Ok(Some(_)) => Some(false),
+                Ok(None) => None,
+                Err(_) => continue,
+            };
+            match presented {
+                Some(true) if self.is_away() => self.seat(claim, events),
+                Some(false) => {
+                    warn!("a connection to the held seat lacked the guest's token");
+                    self.watch(claim);
+                }
+                None if self.is_away() && since.elapsed() < CLAIM_WAIT => {
+                    waiting.push((claim, since))
+                }
+                _ => self.watch(claim),
+            }
+        }
+        self.claims.borrow_mut().extend(waiting);
+    }
+
+    /// Seats a connection that came after the guest in the audience, and
+    /// catches it up on the hand so far.
+    fn watch(&self, mut spectator: Connection) {
+        if let Err(error) = spectator.watch() {
+            warn!(%error, "spectator left at once");
+            return;
+        }
+        for message in self.watched.borrow().iter() {
+            let _ = spectator.send(message);
+        }
+        info!("a spectator joined");
+        self.spectators.borrow_mut().push(spectator);
+    }
+
+    /// Drops spectators who have left; what they send is ignored.
+    fn keep_spectators(&self) {
+        let mut spectators = self.spectators.borrow_mut();
+        spectators.retain(|spectator| loop {
+            match spectator.receive() {
+                Ok(Some(_)) => {}
+                Ok(None) => break true,
+                Err(_) => break false,
+            }
+        });
+        if let Some(info) = self.table.borrow_mut().as_mut() {
+            info.spectators = spectators.len() as u32;
+        }
+    }
+
+    fn try_connect(&self, events: &mut Vec<LanEvent>) -> Option<io::Result<Connection>> {
+        if let Some(lobby) = self.lobby.borrow().as_ref() {
+            return lobby.accept().transpose();
+        }
+        if let Some(hosting) = self.hosting.borrow().as_ref() {
+            return match hosting.try_recv() {
+                Ok(Ok(Hosting::Open { code, socket })) => {
+                    *self.rendezvous.borrow_mut() = Some(socket);
+                    events.push(LanEvent::Code(code));
+                    None
+                }
+                Ok(Ok(Hosting::Paired(connection))) => Some(Ok(connection)),
+                Ok(Err(error)) => Some(Err(error)),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => Some(Err(io::ErrorKind::Interrupted.into())),
+            };
+        }
+        let joining = self.joining.borrow();
+        match joining.as_ref()?.try_recv() {
+            Ok(connected) => Some(connected),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(io::ErrorKind::Interrupted.into())),
+        }
+    }
+
+    /// Lists the tables on the LAN with `show` until browsing stops.
+    pub fn browse(&self, show: impl Fn(Vec<Listing>) + 'static) -> io::Result<()> {
+        if self.browser.borrow().is_none() {
+            *self.browser.borrow_mut() = Some(TableBrowser::open()?);
+        }
+        let browser = self.browser.clone();
+        self.browse_timer.start(
+            TimerMode::Repeated,
+            Duration::from_millis(BROWSE_MS),
+            move || {
+                let listings = browser.borrow_mut().as_mut().map(TableBrowser::poll);
+                if let Some(listings) = listings {
+                    show(listings);
+                }
+            },
+        );
+        Ok(())
+    }
+
+    pub fn stop_browsing(&self) {
+        self.browse_timer.stop();
+        self.browser.take();
+    }
+
+    /// Passes on the chat line `seat` said to the guest and the spectators.
+    pub fn relay_chat(&self, seat: usize, text: &str) {
+        self.send(&Message::Chat {
+            seat: 1 - seat,
+            text: text.to_string(),
+        });
+        let line = Message::Chat {
+            seat,
+            text: text.to_string(),
+        };
+        for spectator in self.spectators.borrow().iter() {
+            let _ = spectator.send(&line);
+        }
+    }
+
+    pub fn send(&self, message: &Message) {
+        let connection = self.connection.borrow();
+        let Some(connection) = connection.as_ref() else {
+            return;
+        };
+        if let Err(error) = connection.send(message) {
+            warn!(%error, ?message, "cannot send to the other player");
+        }
+    }
+
+    /// Passes what happened in the host's game on to the guest and the
+    /// spectators.
+    pub fn forward(&self, game: &PokerGame, events: &[GameEvent]) {
+        if self.role() != Some(Role::Host) {
+            return;
+        }
+        for message in network::messages_for_guest(game, events) {
+            self.send(&message);
+            record(&mut self.dealt.borrow_mut(), message);
+        }
+        let spectators = self.spectators.borrow();
+        for message in network::messages_for_spectators(game, events) {
+            for spectator in spectators.iter() {
+                let _ = spectator.send(&message);
+            }
+            record(&mut self.watched.borrow_mut(), message);
+        }
+    }
+}
+
+/// Adds `message` to the hand so far, for catching up on it later.
+fn record(transcript: &mut Vec<Message>, message: Message) {
+    match message {
+        Message::Hand { .. } => transcript.clear(),
+        // Only the latest snapshot is worth catching up on.
+        Message::Table(_) => transcript.retain(|sent| !matches!(sent, Message::Table(_))),
+        _ => {}
+    }
+    transcript.push(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::Action;
+
+    #[test]
+    fn test_transcript_keeps_the_hand_so_far() {
+        let hand = |hand_number| Message::Hand {
+            hand_number,
+            dealer: 0,
+            stacks: vec![Chips::new(1000); 2],
+            small_blind: Chips::new(5),
+            big_blind: Chips::new(10),
+        };
+        let act = Message::Act {
+            seat: 0,
+            action: Action::Call,
+        };
+        let table = |hand| {
+            Message::Table(network::protocol::TableSnapshot {
+                hand,
+                ..Default::default()
+            })
+        };
+        let mut transcript = Vec::new();
+        for message in [hand(1), act.clone(), table(1), hand(2), act.clone()] {
+            record(&mut transcript, message);
+        }
+        record(&mut transcript, table(2));
+        record(&mut transcript, act.clone());
+        record(&mut transcript, table(3));
+        assert_eq!(transcript, [hand(2), act.clone(), act, table(3)]);
+    }
+}
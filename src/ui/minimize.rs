@@ -0,0 +1,73 @@
+//! What a table does while its window is minimized: play on, or hold every
+//! queued move until the window comes back. Either way the title, which is
+//! what the taskbar shows, says when it's the user's turn. Where there is a
+//! system tray the window hides in it instead; see [`Tray`].
+
+use super::tray::Tray;
+use super::MainWindow;
+use slint::{ComponentHandle, Timer, TimerMode};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+// Slint sends no event when a window is minimized, so it is polled.
+const POLL_MS: u64 = 500;
+
+#[derive(Clone, Default)]
+pub struct MinimizeWatch {
+    timer: Rc<Timer>,
+    // Set while play is held because of the minimize. Kept apart from the
+    // pause menu, which coming back doesn't close.
+    holding: Rc<Cell<bool>>,
+    // The icon the window is hidden behind, while it is.
+    tray: Rc<RefCell<Option<Tray>>>,
+}
+
+impl MinimizeWatch {
+    /// Watches `window`, holding play while it is minimized with the
+    /// "Pause" choice.
+    pub fn start(&self, window: &MainWindow) {
+        let window = window.as_weak();
+        let holding = self.holding.clone();
+        let tray = self.tray.clone();
+        self.timer.start(
+            TimerMode::Repeated,
+            Duration::from_millis(POLL_MS),
+            move || {
+                let Some(window) = window.upgrade() else {
+                    return;
+                };
+                // A hidden window isn't minimized as far as the OS knows;
+                // it's back once its icon is clicked.
+                let minimized = match &*tray.borrow() {
+                    Some(icon) => {
+                        icon.set_your_turn(window.get_show_actions());
+                        !icon.restored()
+                    }
+                    None => window.window().is_minimized(),
+                };
+                if minimized == window.get_minimized() {
+                    return;
+                }
+                debug!(minimized, "window minimized");
+                window.set_minimized(minimized);
+                if !minimized {
+                    tray.borrow_mut().take();
+                } else if let Some(icon) = Tray::open(&window) {
+                    icon.set_your_turn(window.get_show_actions());
+                    match window.hide() {
+                        Ok(()) => *tray.borrow_mut() = Some(icon),
+                        Err(error) => warn!(%error, "cannot hide the window in the tray"),
+                    }
+                }
+                holding.set(minimized && window.get_while_minimized() == "Pause");
+            },
+        );
+    }
+
+    /// Whether play is on hold until the window comes back.
+    pub fn holding(&self) -> bool {
+        self.holding.get()
+    }
+}
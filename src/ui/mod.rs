@@ -0,0 +1,32 @@
+mod action_log;
+mod app_state;
+#[cfg(feature = "network")]
+mod chat;
+mod clock;
+mod commands;
+mod commentary;
+mod debug;
+mod diagnostics;
+mod emotes;
+mod equity_meter;
+mod help;
+#[cfg(feature = "network")]
+mod lan;
+mod minimize;
+mod models;
+mod pots;
+mod recap;
+mod share;
+mod stats;
+mod tables;
+mod toasts;
+mod tray;
+mod tutorial;
+mod view;
+mod worker;
+
+pub use app_state::AppState;
+pub use commands::Command;
+pub use tables::Tables;
+
+slint::include_modules!();
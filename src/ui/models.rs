@@ -0,0 +1,169 @@
+use super::{CardSuit, CardUI, ChipPileUI, HotkeyUI};
+use crate::hotkeys::{HotkeyAction, Hotkeys};
+use poker_engine::{Card, Chips};
+use slint::{ModelRc, VecModel};
+use std::fmt;
+use std::rc::Rc;
+
+// Chip values, biggest first, each drawn in its own color.
+const CHIP_DENOMINATIONS: [u32; 5] = [500, 100, 25, 5, 1];
+
+pub fn create_card_ui_data(card: &Card) -> CardUI {
+    CardUI {
+        rank: card.rank.clone().into(),
+        suit: card.suit.clone().into(),
+        suit_kind: match card.suit.as_str() {
+            "♥" => CardSuit::Hearts,
+            "♦" => CardSuit::Diamonds,
+            "♣" => CardSuit::Clubs,
+            _ => CardSuit::Spades,
+        },
+        label: card_label(card).into(),
+        face_down: false,
+        highlighted: false,
+        dimmed: false,
+    }
+}
+
+/// `10♥`, the way [`Card`] writes itself.
+impl fmt::Display for CardUI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// The card spelled out for screen readers, e.g. "Ten of Spades".
+fn card_label(card: &Card) -> String {
+    let rank = match card.rank.as_str() {
+        "A" => "Ace",
+        "K" => "King",
+        "Q" => "Queen",
+        "J" => "Jack",
+        "10" => "Ten",
+        "9" => "Nine",
+        "8" => "Eight",
+        "7" => "Seven",
+        "6" => "Six",
+        "5" => "Five",
+        "4" => "Four",
+        "3" => "Three",
+        "2" => "Two",
+        other => other,
+    };
+    let suit = match card.suit.as_str() {
+        "♠" => "Spades",
+        "♥" => "Hearts",
+        "♦" => "Diamonds",
+        "♣" => "Clubs",
+        other => other,
+    };
+    format!("{} of {}", rank, suit)
+}
+
+/// A card shown at showdown: lit up if it is one of the winning five, grayed
+/// out otherwise.
+pub fn showdown_card_ui_data(card: &Card, winning_cards: &[Card]) -> CardUI {
+    let in_winning_hand = winning_cards.contains(card);
+    let card_ui = create_card_ui_data(card);
+    let label = if in_winning_hand {
+        format!("{}, in the winning hand", card_ui.label)
+    } else {
+        card_ui.label.to_string()
+    };
+    CardUI {
+        label: label.into(),
+        highlighted: in_winning_hand,
+        dimmed: !in_winning_hand,
+        ..card_ui
+    }
+}
+
+/// `cards` face up, as a model for a row of cards.
+pub fn cards_model(cards: &[Card]) -> ModelRc<CardUI> {
+    let cards: Vec<CardUI> = cards.iter().map(create_card_ui_data).collect();
+    Rc::new(VecModel::from(cards)).into()
+}
+
+pub fn hotkeys_model(hotkeys: &Hotkeys) -> ModelRc<HotkeyUI> {
+    let rows: Vec<HotkeyUI> = HotkeyAction::ALL
+        .iter()
+        .map(|&action| HotkeyUI {
+            action: action.name().into(),
+            key: hotkeys.key(action).to_string().into(),
+            lowercase: hotkeys.key(action).to_ascii_lowercase().to_string().into(),
+        })
+        .collect();
+    Rc::new(VecModel::from(rows)).into()
+}
+
+pub fn hidden_card_ui_data() -> CardUI {
+    CardUI {
+        rank: "".into(),
+        suit: "".into(),
+        suit_kind: CardSuit::Hidden,
+        label: "Face-down card".into(),
+        face_down: true,
+        highlighted: false,
+        dimmed: false,
+    }
+}
+
+/// `amount` broken into as few chips as possible, one pile per
+/// denomination used.
+pub fn chip_piles(amount: Chips) -> Vec<ChipPileUI> {
+    let mut left = amount.amount();
+    CHIP_DENOMINATIONS
+        .into_iter()
+        .filter_map(|denomination| {
+            let count = left / denomination;
+            left %= denomination;
+            (count > 0).then_some(ChipPileUI {
+                denomination: denomination as i32,
+                count: count as i32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_suit_kinds_follow_suit() {
+        let hearts = create_card_ui_data(&Card::new("A", "♥", 14));
+        let spades = create_card_ui_data(&Card::new("10", "♠", 10));
+        assert_eq!(hearts.rank, "A");
+        assert_eq!(hearts.suit_kind, CardSuit::Hearts);
+        assert_eq!(spades.suit_kind, CardSuit::Spades);
+        assert_eq!(spades.label, "Ten of Spades");
+    }
+
+    #[test]
+    fn test_showdown_cards_follow_the_winning_hand() {
+        let ace = Card::new("A", "♥", 14);
+        let two = Card::new("2", "♣", 2);
+        let winning = [ace.clone()];
+        assert!(showdown_card_ui_data(&ace, &winning).highlighted);
+        let unused = showdown_card_ui_data(&two, &winning);
+        assert!(unused.dimmed && !unused.highlighted);
+    }
+
+    #[test]
+    fn test_hidden_card_has_no_rank() {
+        let hidden = hidden_card_ui_data();
+        assert!(hidden.face_down);
+        assert!(hidden.rank.is_empty() && hidden.suit.is_empty());
+        assert_eq!(hidden.suit_kind, CardSuit::Hidden);
+    }
+
+    #[test]
+    fn test_chip_piles_use_the_fewest_chips() {
+        let piles: Vec<(i32, i32)> = chip_piles(Chips::new(1140))
+            .iter()
+            .map(|pile| (pile.denomination, pile.count))
+            .collect();
+        assert_eq!(piles, [(500, 2), (100, 1), (25, 1), (5, 3)]);
+        assert!(chip_piles(Chips::ZERO).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use super::PotUI;
+use poker_engine::{Chips, GameEvent, PokerGame};
+use std::collections::BTreeMap;
+
+/// The pots of the hand in progress, or none while everything is in the
+/// main pot.
+pub fn side_pots(game: &PokerGame) -> Vec<PotUI> {
+    let pots = game.pots();
+    if pots.len() < 2 {
+        return Vec::new();
+    }
+    pots.iter()
+        .enumerate()
+        .map(|(index, pot)| PotUI {
+            name: pot_name(index).into(),
+            amount: pot.amount.as_i32(),
+            players: names(game, &pot.eligible).into(),
+            winner: "".into(),
+        })
+        .collect()
+}
+
+/// Who took each pot at the end of a hand, or none if there was only the
+/// main pot.
+pub fn awarded_pots(game: &PokerGame, events: &[GameEvent]) -> Vec<PotUI> {
+    let mut awards: BTreeMap<usize, (Chips, Vec<usize>)> = BTreeMap::new();
+    for event in events {
+        if let GameEvent::PotAwarded { seat, amount, pot } = event {
+            let (total, winners) = awards.entry(*pot).or_default();
+            *total += *amount;
+            winners.push(*seat);
+        }
+    }
+    if awards.len() < 2 {
+        return Vec::new();
+    }
+    let live: Vec<usize> = (0..game.players.len())
+        .filter(|&seat| !game.players[seat].cards.is_empty())
+        .collect();
+    awards
+        .into_iter()
+        .map(|(index, (amount, winners))| {
+            // Only the main pot is contested; a side pot's winners are the
+            // only seats that could win it.
+            let eligible = if index == 0 { &live } else { &winners };
+            let winner = match winners[..] {
+                [seat] => format!("{} ✓", game.players[seat].name),
+                _ => "Split".to_string(),
+            };
+            PotUI {
+                name: pot_name(index).into(),
+                amount: amount.as_i32(),
+                players: names(game, eligible).into(),
+                winner: winner.into(),
+            }
+        })
+        .collect()
+}
+
+/// One line per chip award in `events`, e.g. "Side pot: $300 to Bot".
+pub fn pot_breakdown(game: &PokerGame, events: &[GameEvent]) -> Vec<String> {
+    let awards: Vec<(usize, Chips, usize)> = events
+        .iter()
+        .filter_map(|event| match event {
+            GameEvent::PotAwarded { seat, amount, pot } => Some((*seat, *amount, *pot)),
+            _ => None,
+        })
+        .collect();
+    let single_pot = awards.iter().all(|&(_, _, pot)| pot == 0);
+    awards
+        .into_iter()
+        .map(|(seat, amount, pot)| {
+            let name = if single_pot {
+                "Pot".to_string()
+            } else {
+                pot_name(pot)
+            };
+            format!("{}: {} to {}", name, amount, game.players[seat].name)
+        })
+        .collect()
+}
+
+fn pot_name(index: usize) -> String {
+    match index {
+        0 => "Main pot".to_string(),
+        1 => "Side pot".to_string(),
+        _ => format!("Side pot {}", index),
+    }
+}
+
+fn names(game: &PokerGame, seats: &[usize]) -> String {
+    seats
+        .iter()
+        .map(|&seat| game.players[seat].name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_awarded_pots_show_each_pot_and_its_winner() {
+        let game = PokerGame::new();
+        let award = |seat, amount, pot| GameEvent::PotAwarded {
+            seat,
+            amount: Chips::new(amount),
+            pot,
+        };
+        assert!(awarded_pots(&game, &[award(0, 400, 0)]).is_empty());
+
+        let pots = awarded_pots(
+            &game,
+            &[award(0, 200, 0), award(1, 200, 0), award(1, 600, 1)],
+        );
+        assert_eq!(pots.len(), 2);
+        assert_eq!(pots[0].amount, 400);
+        assert_eq!(pots[0].winner, "Split");
+        assert_eq!(pots[1].name, "Side pot");
+        assert_eq!(pots[1].players, game.players[1].name.as_str());
+        assert_eq!(pots[1].winner, format!("{} ✓", game.players[1].name));
+
+        let breakdown = pot_breakdown(&game, &[award(0, 400, 0), award(1, 600, 1)]);
+        assert_eq!(
+            breakdown[0],
+            format!("Main pot: $400 to {}", game.players[0].name)
+        );
+        assert_eq!(
+            breakdown[1],
+            format!("Side pot: $600 to {}", game.players[1].name)
+        );
+    }
+}
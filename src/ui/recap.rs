@@ -0,0 +1,95 @@
+use super::action_log::{describe_action, subject};
+use super::models::{cards_model, hidden_card_ui_data};
+use super::HandRecapUI;
+use poker_engine::{GameEvent, GamePhase, PokerGame};
+use slint::{SharedString, VecModel};
+use std::rc::Rc;
+
+/// A summary of the hand that just finished: the cards that were seen, one
+/// line of actions per street and what seat 0 won or lost.
+pub fn recap(game: &PokerGame) -> HandRecapUI {
+    let mut player_cards = Vec::new();
+    let mut streets: Vec<(&str, Vec<String>)> = Vec::new();
+    for event in game.events() {
+        match event {
+            GameEvent::HandStarted { .. } => streets.push(("Preflop", Vec::new())),
+            GameEvent::CardsDealt {
+                seat: Some(0),
+                cards,
+            } => player_cards = cards.clone(),
+            GameEvent::PhaseAdvanced { phase } => {
+                let street = match phase {
+                    GamePhase::Flop => "Flop",
+                    GamePhase::Turn => "Turn",
+                    GamePhase::River => "River",
+                    _ => continue,
+                };
+                streets.push((street, Vec::new()));
+            }
+            GameEvent::PlayerActed {
+                seat,
+                action,
+                amount,
+            } => {
+                if let Some((_, actions)) = streets.last_mut() {
+                    actions.push(describe_action(game, *seat, *action, *amount));
+                }
+            }
+            _ => {}
+        }
+    }
+    let actions: Vec<String> = streets
+        .into_iter()
+        .filter(|(_, actions)| !actions.is_empty())
+        .map(|(street, actions)| format!("{}: {}", street, actions.join(", ")))
+        .collect();
+
+    // The bot's cards only if they were shown down.
+    let contested = game.players.iter().all(|player| !player.cards.is_empty());
+    let bot_cards = if game.showdown_done && contested {
+        cards_model(&game.players[1].cards)
+    } else {
+        Rc::new(VecModel::from(vec![hidden_card_ui_data(); 2])).into()
+    };
+
+    let net = game.players[0].chips.as_i32() - game.hand_start_chips[0].as_i32();
+    let result = match net {
+        0 => subject(game, 0, "break even", "breaks even"),
+        net if net > 0 => format!("{} ${}", subject(game, 0, "win", "wins"), net),
+        net => format!("{} ${}", subject(game, 0, "lose", "loses"), -net),
+    };
+
+    let actions: Vec<SharedString> = actions.into_iter().map(Into::into).collect();
+    HandRecapUI {
+        hand_number: game.hand_number as i32,
+        player_cards: cards_model(&player_cards),
+        bot_cards,
+        board: cards_model(&game.community_cards),
+        actions: Rc::new(VecModel::from(actions)).into(),
+        result: result.into(),
+        net,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_engine::Action;
+    use slint::Model;
+
+    #[test]
+    fn test_recap_of_a_folded_hand() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        game.apply(Action::Fold).unwrap();
+
+        let recap = recap(&game);
+        assert_eq!(recap.hand_number, 1);
+        assert_eq!(recap.player_cards.row_count(), 2);
+        assert!(recap.bot_cards.iter().all(|card| card.face_down));
+        assert_eq!(recap.actions.row_count(), 1);
+        assert_eq!(recap.actions.row_data(0).unwrap(), "Preflop: You fold");
+        assert_eq!(recap.net, -10);
+        assert_eq!(recap.result, "You lose $10");
+    }
+}
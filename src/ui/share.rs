@@ -0,0 +1,164 @@
+//! "Share" on the last-hand recap: the finished hand is drawn as a card
+//! filling the window, captured and saved as a PNG in the profile's
+//! `shares` directory, ready to send to friends. "Copy" puts it on the
+//! clipboard as text instead, for pasting into chats and forums.
+
+use super::toasts::Toasts;
+use super::{CardUI, HandRecapUI, MainWindow};
+use crate::profiles;
+use slint::{ComponentHandle, Model, ModelRc, Rgba8Pixel, SharedPixelBuffer, Timer};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// Time for the share card to be laid out before it is captured.
+const DRAW_MS: u64 = 50;
+
+thread_local! {
+    // Kept open: on X11 copied text is served by this process, and is gone
+    // once the last handle closes.
+    static CLIPBOARD: RefCell<Option<arboard::Clipboard>> = const { RefCell::new(None) };
+}
+
+/// Shows the share card for the last hand, then saves a capture of it.
+pub fn share(window: &slint::Weak<MainWindow>, profile: &str, toasts: &Toasts) {
+    let Some(main_window) = window.upgrade() else {
+        return;
+    };
+    let hand_number = main_window.get_last_hand().hand_number;
+    main_window.set_sharing(true);
+    let window = window.clone();
+    let toasts = toasts.clone();
+    let path = profiles::path(profile, &format!("shares/hand-{}.png", hand_number));
+    Timer::single_shot(Duration::from_millis(DRAW_MS), move || {
+        let Some(main_window) = window.upgrade() else {
+            return;
+        };
+        let captured = main_window.window().take_snapshot();
+        main_window.set_sharing(false);
+        let saved = match (captured, path) {
+            (Ok(image), Some(path)) => save_png(&path, &image).map(|()| path),
+            (Err(error), _) => Err(io::Error::other(error.to_string())),
+            (_, None) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no config directory",
+            )),
+        };
+        let text = match saved {
+            Ok(path) => {
+                info!(path = %path.display(), "hand shared");
+                format!("Saved hand #{} to {}", hand_number, path.display())
+            }
+            Err(error) => {
+                warn!(%error, "cannot share the hand");
+                format!("Couldn't save the hand: {}", error)
+            }
+        };
+        toasts.push(&window, text);
+    });
+}
+
+/// Copies the last hand to the clipboard as text.
+pub fn copy(window: &slint::Weak<MainWindow>, toasts: &Toasts) {
+    let Some(main_window) = window.upgrade() else {
+        return;
+    };
+    let recap = main_window.get_last_hand();
+    let copied = CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        let clipboard = match &mut *clipboard {
+            Some(clipboard) => clipboard,
+            empty => empty.insert(arboard::Clipboard::new()?),
+        };
+        clipboard.set_text(summary(&recap))
+    });
+    let text = match copied {
+        Ok(()) => format!("Copied hand #{}", recap.hand_number),
+        Err(error) => {
+            warn!(%error, "cannot copy the hand");
+            format!("Couldn't copy the hand: {}", error)
+        }
+    };
+    toasts.push(window, text);
+}
+
+/// The hand as plain text: cards, one line of actions per street and the
+/// result.
+pub fn summary(recap: &HandRecapUI) -> String {
+    let mut lines = vec![format!("Hand #{}: {}", recap.hand_number, recap.result)];
+    lines.push(format!("Hole cards: {}", cards_text(&recap.player_cards)));
+    if recap.bot_cards.iter().all(|card| !card.face_down) {
+        lines.push(format!("Opponent: {}", cards_text(&recap.bot_cards)));
+    }
+    if recap.board.row_count() > 0 {
+        lines.push(format!("Board: {}", cards_text(&recap.board)));
+    }
+    lines.extend(recap.actions.iter().map(String::from));
+    lines.join("\n")
+}
+
+fn cards_text(cards: &ModelRc<CardUI>) -> String {
+    let cards: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+    cards.join(" ")
+}
+
+fn save_png(path: &Path, image: &SharedPixelBuffer<Rgba8Pixel>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        image.width(),
+        image.height(),
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(image.as_bytes())
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_summary_lists_cards_actions_and_result() {
+        let mut game = poker_engine::PokerGame::new();
+        game.start_hand();
+        game.apply(poker_engine::Action::Fold).unwrap();
+        let recap = super::super::recap::recap(&game);
+
+        let summary = summary(&recap);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Hand #1: You lose $10");
+        assert!(lines[1].starts_with("Hole cards: "));
+        // The bot's cards weren't shown and no board was dealt.
+        assert_eq!(lines[2..], ["Preflop: You fold"]);
+    }
+
+    #[test]
+    fn test_saved_png_decodes_to_the_same_pixels() {
+        let mut image = SharedPixelBuffer::<Rgba8Pixel>::new(3, 2);
+        image.make_mut_slice()[4] = Rgba8Pixel::new(255, 0, 0, 255);
+        let path: PathBuf = std::env::temp_dir().join(format!(
+            "slint-poker-share-test-{}/hand.png",
+            std::process::id()
+        ));
+        save_png(&path, &image).unwrap();
+
+        let decoder = png::Decoder::new(File::open(&path).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((frame.width, frame.height), (3, 2));
+        assert_eq!(&pixels[16..20], &[255, 0, 0, 255]);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,153 @@
+use super::{MainWindow, StandingUI, StatLineUI};
+use crate::game_mode::GameMode;
+use crate::hand_db::{stats_since, HandDb, HandRecord, Standing, Summary};
+use poker_engine::{PlayerStats, PokerGame};
+use slint::{ModelRc, VecModel};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Writes every hand the user plays to the hand database as it finishes,
+/// and keeps the opponent's lifetime stats at hand for the HUD.
+#[derive(Clone)]
+pub struct HandRecorder {
+    pub db: Rc<HandDb>,
+    pub profile: Rc<RefCell<String>>,
+    pub mode: Rc<Cell<GameMode>>,
+    // Each seat's stats as of the last hand recorded, so only what a hand
+    // added is written.
+    pub counted: Rc<RefCell<[PlayerStats; 2]>>,
+    pub hud: Rc<RefCell<Option<PlayerStats>>>,
+}
+
+impl HandRecorder {
+    pub fn record(&self, game: &PokerGame) {
+        let mode = self.mode.get();
+        let profile = self.profile.borrow();
+        if profile.is_empty() || !mode.is_playing() || mode == GameMode::Tutorial {
+            return;
+        }
+        let counted = self.counted.borrow().clone();
+        let seats = [
+            stats_since(&game.user_stats, &counted[0]),
+            stats_since(&game.bot_stats, &counted[1]),
+        ];
+        self.db.record(&HandRecord {
+            profile: &profile,
+            opponent: &opponent(game, mode),
+            mode: mode.name(),
+            log: game.hand_log(),
+            board: &game.community_cards,
+            big_blind: game.big_blind,
+            net: i64::from(game.players[0].chips.as_i32() - game.hand_start_chips[0].as_i32()),
+            seats,
+        });
+        drop(profile);
+        self.sync(game);
+    }
+
+    /// Counts from `game`'s stats as they are now, and looks up its
+    /// opponent for the HUD.
+    pub fn sync(&self, game: &PokerGame) {
+        *self.counted.borrow_mut() = [game.user_stats.clone(), game.bot_stats.clone()];
+        let profile = self.profile.borrow();
+        let mode = self.mode.get();
+        *self.hud.borrow_mut() = if profile.is_empty() || !mode.is_playing() {
+            None
+        } else {
+            self.db.opponent_stats(&profile, &opponent(game, mode))
+        };
+    }
+}
+
+// Bots are told apart by personality; a LAN opponent only by seat name.
+fn opponent(game: &PokerGame, mode: GameMode) -> String {
+    if mode == GameMode::Lan {
+        game.players[1].name.clone()
+    } else {
+        game.bot_personality.name().to_string()
+    }
+}
+
+/// The stats screen's lines for `summary`.
+pub fn summary_lines(summary: &Summary) -> ModelRc<StatLineUI> {
+    let percent =
+        |value: Option<u32>| value.map_or("—".to_string(), |value| format!("{}%", value));
+    let stats = &summary.stats;
+    let lines = [
+        ("Hands played", summary.hands.to_string()),
+        ("Won", dollars(summary.net)),
+        (
+            "Big blinds per 100 hands",
+            format!("{:.1}", summary.bb_per_100),
+        ),
+        ("Biggest win", dollars(summary.biggest_win)),
+        ("Voluntarily put money in", percent(stats.vpip())),
+        ("Raised preflop", percent(stats.pfr())),
+        ("Folded to a bet", percent(stats.fold_to_bet())),
+        (
+            "Aggression factor",
+            stats
+                .aggression_factor()
+                .map_or("—".to_string(), |af| af.to_string()),
+        ),
+    ];
+    let lines: Vec<StatLineUI> = lines
+        .into_iter()
+        .map(|(label, value)| StatLineUI {
+            label: label.into(),
+            value: value.into(),
+        })
+        .collect();
+    Rc::new(VecModel::from(lines)).into()
+}
+
+pub fn leaderboard(standings: &[Standing], profile: &str) -> ModelRc<StandingUI> {
+    let rows: Vec<StandingUI> = standings
+        .iter()
+        .map(|standing| StandingUI {
+            profile: standing.profile.as_str().into(),
+            hands: standing.hands as i32,
+            net: dollars(standing.net).into(),
+            bb_per_100: format!("{:.1}", standing.bb_per_100).into(),
+            mine: standing.profile == profile,
+        })
+        .collect();
+    Rc::new(VecModel::from(rows)).into()
+}
+
+fn dollars(amount: i64) -> String {
+    if amount < 0 {
+        format!("-${}", -amount)
+    } else {
+        format!("${}", amount)
+    }
+}
+
+pub fn show(window: &MainWindow, db: &HandDb, profile: &str) {
+    window.set_stats_lines(summary_lines(&db.summary(profile)));
+    window.set_leaderboard(leaderboard(&db.leaderboard(), profile));
+    window.set_show_stats(true);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slint::Model;
+
+    #[test]
+    fn test_leaderboard_marks_the_users_row() {
+        let standing = |profile: &str, net| Standing {
+            profile: profile.to_string(),
+            hands: 100,
+            net,
+            bb_per_100: net as f64 / 20.0,
+        };
+        let rows = leaderboard(&[standing("zed", 400), standing("amy", -60)], "amy");
+        let amy = rows.row_data(1).unwrap();
+        assert!(amy.mine && !rows.row_data(0).unwrap().mine);
+        assert_eq!(
+            (amy.net.as_str(), amy.bb_per_100.as_str()),
+            ("-$60", "-3.0")
+        );
+    }
+}
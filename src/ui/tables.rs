@@ -0,0 +1,352 @@
+use super::{emotes, help, AppState, Command, MainWindow};
+use crate::audio::MusicPlayer;
+use crate::hand_db::HandDb;
+#[cfg(feature = "network")]
+use crate::network::overlay::Overlay;
+use crate::presence::Presence;
+use poker_engine::{Action, MIN_RAISE};
+use slint::{CloseRequestResponse, ComponentHandle, Timer};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+pub type TableId = u32;
+
+struct OpenTable {
+    // Held so the window stays alive while the table is open.
+    _window: MainWindow,
+    state: Rc<AppState>,
+}
+
+/// Every open table, keyed by id. Each has its own window, game and bot;
+/// only the music, the Discord presence, the stream overlay and the hand
+/// database are shared.
+#[derive(Clone)]
+pub struct Tables {
+    open: Rc<RefCell<BTreeMap<TableId, OpenTable>>>,
+    last_id: Rc<Cell<TableId>>,
+    music: Rc<MusicPlayer>,
+    presence: Rc<Presence>,
+    #[cfg(feature = "network")]
+    overlay: Rc<Overlay>,
+    hand_db: Rc<HandDb>,
+}
+
+impl Tables {
+    pub fn new() -> Self {
+        Tables {
+            open: Rc::default(),
+            last_id: Rc::default(),
+            music: Rc::new(MusicPlayer::open()),
+            presence: Rc::default(),
+            #[cfg(feature = "network")]
+            overlay: Rc::default(),
+            hand_db: Rc::new(HandDb::open()),
+        }
+    }
+
+    /// Opens a table in a window of its own. The first table starts at the
+    /// splash screen; later ones reuse `profile` and go to the main menu.
+    pub fn open(&self, profile: Option<String>) -> Result<Rc<AppState>, slint::PlatformError> {
+        let id = self.last_id.get() + 1;
+        self.last_id.set(id);
+        let main_window = MainWindow::new()?;
+        main_window.set_table_number(id as i32);
+        main_window.set_networking(cfg!(feature = "network"));
+        help::fill(&main_window);
+        emotes::fill(&main_window);
+
+        let state = Rc::new(AppState::new(
+            main_window.as_weak(),
+            self.music.clone(),
+            self.presence.clone(),
+            #[cfg(feature = "network")]
+            self.overlay.clone(),
+            self.hand_db.clone(),
+        ));
+        wire(&main_window, &state, self);
+        state.start_commands();
+        match &profile {
+            Some(profile) => state.join(profile),
+            None => state.start_loading(),
+        }
+
+        let tables = self.clone();
+        let state_close = state.clone();
+        main_window.window().on_close_requested(move || {
+            state_close.save_layout();
+            // The window can't be dropped from inside its own callback.
+            let tables = tables.clone();
+            Timer::single_shot(Duration::ZERO, move || tables.close(id));
+            CloseRequestResponse::HideWindow
+        });
+        main_window.show()?;
+        info!(table = id, "table opened");
+        self.open.borrow_mut().insert(
+            id,
+            OpenTable {
+                _window: main_window,
+                state: state.clone(),
+            },
+        );
+        Ok(state)
+    }
+
+    fn close(&self, id: TableId) {
+        let closed = self.open.borrow_mut().remove(&id);
+        if let Some(table) = closed {
+            info!(table = id, "table closed");
+            table.state.shut_down();
+        }
+        // Tables hidden in the tray are still open, so the event loop is
+        // only told to stop with the last one.
+        if self.open.borrow().is_empty() {
+            let _ = slint::quit_event_loop();
+        }
+    }
+}
+
+/// Connects the window's callbacks to its table.
+fn wire(main_window: &MainWindow, state: &Rc<AppState>, tables: &Tables) {
+    #[cfg(feature = "network")]
+    wire_network(main_window, state);
+
+    let state_check = state.clone();
+    main_window.on_check(move || {
+        debug!("user checks");
+        state_check.push(Command::Act(Action::Check));
+    });
+
+    let state_call = state.clone();
+    main_window.on_call(move || {
+        debug!("user calls");
+        state_call.push(Command::Act(Action::Call));
+    });
+
+    let state_fold = state.clone();
+    main_window.on_fold(move || {
+        debug!("user folds");
+        state_fold.push(Command::Act(Action::Fold));
+    });
+
+    let state_raise = state.clone();
+    main_window.on_raise(move || {
+        let amount = {
+            let game = state_raise.game.borrow();
+            game.current_bet + MIN_RAISE
+        };
+        debug!(%amount, "user raises");
+        state_raise.push(Command::Act(Action::Raise(amount)));
+    });
+
+    let state_all_in = state.clone();
+    main_window.on_all_in(move || {
+        debug!("user goes all-in");
+        state_all_in.push(Command::Act(Action::AllIn));
+    });
+
+    let state_difficulty = state.clone();
+    main_window.on_toggle_adaptive_difficulty(move || {
+        state_difficulty.toggle_adaptive_difficulty();
+    });
+
+    let state_style = state.clone();
+    main_window.on_toggle_bot_style(move || {
+        state_style.toggle_bot_style();
+    });
+
+    let state_personality = state.clone();
+    main_window.on_cycle_bot_personality(move || {
+        state_personality.cycle_bot_personality();
+    });
+
+    let state_debug = state.clone();
+    main_window.on_toggle_debug_panel(move || {
+        state_debug.toggle_debug_panel();
+    });
+
+    let state_diagnostics = state.clone();
+    main_window.on_toggle_diagnostics(move || {
+        state_diagnostics.toggle_diagnostics();
+    });
+
+    let state_bookmark = state.clone();
+    main_window.on_toggle_bookmark(move || {
+        state_bookmark.toggle_bookmark();
+    });
+
+    let state_share = state.clone();
+    main_window.on_share_hand(move || {
+        state_share.share_hand();
+    });
+
+    let state_copy = state.clone();
+    main_window.on_copy_hand(move || {
+        state_copy.copy_hand();
+    });
+
+    let state_note = state.clone();
+    main_window.on_save_note(move |color, text| {
+        state_note.save_note(&color, &text);
+    });
+
+    let state_open_bookmark = state.clone();
+    main_window.on_open_bookmark(move |index| {
+        state_open_bookmark.open_bookmark(index as usize);
+    });
+
+    let state_import = state.clone();
+    main_window.on_import_hands(move |path| {
+        state_import.import_hands(&path);
+    });
+
+    let state_hotkey = state.clone();
+    main_window.on_rebind_hotkey(move |index, text| {
+        state_hotkey.rebind_hotkey(index as usize, &text);
+    });
+
+    let state_scenario = state.clone();
+    main_window.on_debug_scenario(move |name| {
+        state_scenario.force_scenario(&name);
+    });
+
+    let state_history = state.clone();
+    main_window.on_debug_history(move |direction| {
+        state_history.step_history(&direction);
+    });
+
+    let state_bot = state.clone();
+    main_window.on_bot_move_ready(move || {
+        state_bot.finish_bot_move();
+    });
+
+    let state_sync = state.clone();
+    main_window.on_sync_now(move || {
+        state_sync.sync_profile();
+    });
+
+    let state_synced = state.clone();
+    main_window.on_sync_ready(move || {
+        state_synced.finish_sync();
+    });
+
+    let state_settings = state.clone();
+    main_window.on_settings_changed(move || {
+        state_settings.apply_settings();
+    });
+
+    let state_pause = state.clone();
+    let weak_pause = main_window.as_weak();
+    main_window.on_pause_changed(move || {
+        if let Some(window) = weak_pause.upgrade() {
+            state_pause.set_paused(window.get_paused());
+        }
+    });
+
+    let state_save = state.clone();
+    main_window.on_save_and_quit(move || {
+        state_save.save_and_quit();
+    });
+
+    let state_abandon = state.clone();
+    main_window.on_abandon_hand(move || {
+        debug!("user abandons the hand");
+        state_abandon.push(Command::AbandonHand);
+    });
+
+    let state_profile = state.clone();
+    main_window.on_select_profile(move |name| {
+        state_profile.select_profile(&name);
+    });
+
+    let state_create = state.clone();
+    main_window.on_create_profile(move |name| {
+        state_create.create_profile(&name);
+    });
+
+    let state_match = state.clone();
+    main_window.on_start_match(move || {
+        state_match.start_match();
+    });
+
+    let state_open_stats = state.clone();
+    main_window.on_open_stats(move || {
+        state_open_stats.open_stats();
+    });
+
+    let state_menu = state.clone();
+    main_window.on_main_menu(move || {
+        state_menu.open_main_menu();
+    });
+
+    let state_clock = state.clone();
+    main_window.on_open_clock(move || {
+        state_clock.open_clock();
+    });
+
+    let state_emote = state.clone();
+    main_window.on_send_emote(move |index| {
+        state_emote.send_emote(index as usize);
+    });
+
+    let state_new = state.clone();
+    main_window.on_new_hand(move || {
+        debug!("new hand requested");
+        state_new.push(Command::NewHand);
+    });
+
+    let tables = tables.clone();
+    let state_table = state.clone();
+    main_window.on_open_table(move || {
+        let profile = state_table.profile();
+        if let Err(error) = tables.open(Some(profile)) {
+            warn!(%error, "cannot open another table");
+        }
+    });
+}
+
+/// The LAN lobby, online tables and chat.
+#[cfg(feature = "network")]
+fn wire_network(main_window: &MainWindow, state: &Rc<AppState>) {
+    let state_host = state.clone();
+    main_window.on_host_lan(move |name, stakes| {
+        state_host.host_lan(&name, stakes.max(0) as usize);
+    });
+
+    let state_open_lobby = state.clone();
+    main_window.on_open_lobby(move || {
+        state_open_lobby.open_lobby();
+    });
+
+    let state_close_lobby = state.clone();
+    main_window.on_close_lobby(move || {
+        state_close_lobby.close_lobby();
+    });
+
+    let state_join = state.clone();
+    main_window.on_join_lan(move |address| {
+        state_join.join_lan(&address);
+    });
+
+    let state_host_online = state.clone();
+    main_window.on_host_online(move |server, name, stakes| {
+        state_host_online.host_online(&server, &name, stakes.max(0) as usize);
+    });
+
+    let state_join_online = state.clone();
+    main_window.on_join_online(move |server, code| {
+        state_join_online.join_online(&server, &code);
+    });
+
+    let state_chat = state.clone();
+    main_window.on_send_chat(move |text| {
+        state_chat.send_chat(&text);
+    });
+
+    let state_mute = state.clone();
+    main_window.on_toggle_chat_mute(move || {
+        state_mute.toggle_chat_mute();
+    });
+}
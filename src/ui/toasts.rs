@@ -0,0 +1,129 @@
+use super::MainWindow;
+use poker_engine::{Action, Chips, GameEvent, PokerGame};
+use slint::{Timer, TimerMode};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+
+const TOAST_MS: u64 = 2500;
+// Long enough for one toast to fade out before the next fades in.
+const TOAST_GAP_MS: u64 = 300;
+
+/// Short notices shown above the table one at a time; anything pushed while
+/// a toast is up waits its turn.
+#[derive(Clone, Default)]
+pub struct Toasts {
+    queue: Rc<RefCell<VecDeque<String>>>,
+    timer: Rc<Timer>,
+    // The blinds of the last hand seen, to notice when they change.
+    blinds: Rc<Cell<Option<(Chips, Chips)>>>,
+}
+
+impl Toasts {
+    pub fn push(&self, window: &slint::Weak<MainWindow>, text: String) {
+        self.queue.borrow_mut().push_back(text);
+        if !self.timer.running() {
+            self.show_next(window);
+        }
+    }
+
+    /// Queues a toast for each notable thing in `events`.
+    pub fn notice(&self, window: &slint::Weak<MainWindow>, game: &PokerGame, events: &[GameEvent]) {
+        for text in toasts_for(game, events, &self.blinds) {
+            self.push(window, text);
+        }
+    }
+
+    fn show_next(&self, window: &slint::Weak<MainWindow>) {
+        let Some(text) = self.queue.borrow_mut().pop_front() else {
+            return;
+        };
+        let Some(main_window) = window.upgrade() else {
+            return;
+        };
+        main_window.set_toast(text.into());
+        main_window.set_show_toast(true);
+
+        let toasts = self.clone();
+        let window = window.clone();
+        self.timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(TOAST_MS),
+            move || toasts.expire(&window),
+        );
+    }
+
+    fn expire(&self, window: &slint::Weak<MainWindow>) {
+        if let Some(main_window) = window.upgrade() {
+            main_window.set_show_toast(false);
+        }
+        if self.queue.borrow().is_empty() {
+            return;
+        }
+        let toasts = self.clone();
+        let window = window.clone();
+        self.timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(TOAST_GAP_MS),
+            move || toasts.show_next(&window),
+        );
+    }
+}
+
+fn toasts_for(
+    game: &PokerGame,
+    events: &[GameEvent],
+    blinds: &Cell<Option<(Chips, Chips)>>,
+) -> Vec<String> {
+    let mut toasts = Vec::new();
+    for event in events {
+        match event {
+            GameEvent::HandStarted { .. } => {
+                let current = (game.small_blind, game.big_blind);
+                if blinds.get().is_some_and(|previous| previous != current) {
+                    toasts.push(format!("Blinds are now {}/{}", current.0, current.1));
+                }
+                blinds.set(Some(current));
+            }
+            GameEvent::StateRestored => blinds.set(Some((game.small_blind, game.big_blind))),
+            GameEvent::PlayerActed {
+                seat,
+                action: Action::AllIn,
+                ..
+            } if !game.players[*seat].is_user => {
+                toasts.push(format!("{} is all-in", game.players[*seat].name));
+            }
+            _ => {}
+        }
+    }
+    toasts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_for_blind_changes_and_bot_all_in() {
+        let mut game = PokerGame::new();
+        let blinds = Cell::new(None);
+        let started = GameEvent::HandStarted {
+            hand_number: 1,
+            dealer: 0,
+        };
+        assert!(toasts_for(&game, std::slice::from_ref(&started), &blinds).is_empty());
+
+        game.small_blind = Chips::new(50);
+        game.big_blind = Chips::new(100);
+        let all_in = GameEvent::PlayerActed {
+            seat: 1,
+            action: Action::AllIn,
+            amount: Chips::new(900),
+        };
+        assert_eq!(
+            toasts_for(&game, &[started, all_in], &blinds),
+            ["Blinds are now $50/$100", "Bot is all-in"]
+        );
+    }
+}
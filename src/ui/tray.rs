@@ -0,0 +1,150 @@
+//! The system tray icon a minimized table hides behind. It says when it's
+//! the user's turn and brings the window back when clicked. Only Linux
+//! desktops with a StatusNotifier tray get one, and only in builds with
+//! the `tray` feature; everywhere else [`Tray::open`] finds no tray and
+//! the window stays on the taskbar.
+
+use super::MainWindow;
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+pub use sni::Tray;
+
+#[cfg(not(all(feature = "tray", target_os = "linux")))]
+pub struct Tray;
+
+#[cfg(not(all(feature = "tray", target_os = "linux")))]
+impl Tray {
+    pub fn open(_window: &MainWindow) -> Option<Tray> {
+        None
+    }
+
+    pub fn restored(&self) -> bool {
+        false
+    }
+
+    pub fn set_your_turn(&self, _yours: bool) {}
+}
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+mod sni {
+    use super::MainWindow;
+    use ksni::blocking::{Handle, TrayMethods};
+    use ksni::{Status, ToolTip};
+    use slint::ComponentHandle;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tracing::{debug, warn};
+
+    /// A table's icon, there until this is dropped.
+    pub struct Tray {
+        handle: Handle<TableIcon>,
+        restored: Arc<AtomicBool>,
+        your_turn: Cell<bool>,
+    }
+
+    impl Tray {
+        /// Puts an icon for `window` in the tray, or `None` if the desktop
+        /// has no tray to put it in.
+        pub fn open(window: &MainWindow) -> Option<Tray> {
+            let restored = Arc::new(AtomicBool::new(false));
+            let icon = TableIcon {
+                table: window.get_table_number(),
+                your_turn: false,
+                restored: restored.clone(),
+                window: window.as_weak(),
+            };
+            match icon.spawn() {
+                Ok(handle) => Some(Tray {
+                    handle,
+                    restored,
+                    your_turn: Cell::new(false),
+                }),
+                Err(error) => {
+                    warn!(%error, "no system tray to minimize to");
+                    None
+                }
+            }
+        }
+
+        /// Whether the icon has been clicked to bring the window back.
+        pub fn restored(&self) -> bool {
+            self.restored.load(Ordering::Relaxed)
+        }
+
+        pub fn set_your_turn(&self, yours: bool) {
+            if self.your_turn.replace(yours) != yours {
+                self.handle.update(|icon| icon.your_turn = yours);
+            }
+        }
+    }
+
+    impl Drop for Tray {
+        fn drop(&mut self) {
+            // Waiting for the tray's thread to finish would stall the UI.
+            let _ = self.handle.shutdown();
+        }
+    }
+
+    // Runs on the tray's thread, so the window is only reached through the
+    // event loop.
+    struct TableIcon {
+        table: i32,
+        your_turn: bool,
+        restored: Arc<AtomicBool>,
+        window: slint::Weak<MainWindow>,
+    }
+
+    impl ksni::Tray for TableIcon {
+        fn id(&self) -> String {
+            format!("texas-holdem-table-{}", self.table)
+        }
+
+        fn title(&self) -> String {
+            if self.your_turn {
+                format!("Your turn — Table {}", self.table)
+            } else {
+                format!("Table {}", self.table)
+            }
+        }
+
+        fn status(&self) -> Status {
+            if self.your_turn {
+                Status::NeedsAttention
+            } else {
+                Status::Active
+            }
+        }
+
+        fn icon_name(&self) -> String {
+            "applications-games".into()
+        }
+
+        fn attention_icon_name(&self) -> String {
+            "dialog-information".into()
+        }
+
+        fn tool_tip(&self) -> ToolTip {
+            ToolTip {
+                title: self.title(),
+                description: if self.your_turn {
+                    "Click to play".into()
+                } else {
+                    "Not your turn yet".into()
+                },
+                ..Default::default()
+            }
+        }
+
+        fn activate(&mut self, _x: i32, _y: i32) {
+            debug!(table = self.table, "restored from the tray");
+            self.restored.store(true, Ordering::Relaxed);
+            let _ = self.window.upgrade_in_event_loop(|window| {
+                if let Err(error) = window.show() {
+                    warn!(%error, "cannot show the window again");
+                }
+                window.window().set_minimized(false);
+            });
+        }
+    }
+}
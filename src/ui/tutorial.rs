@@ -0,0 +1,161 @@
+//! The tutorial's scripted hand: the user is dealt A♠ K♠ against the bot's
+//! pocket queens, and the board runs out K♦ 7♣ 2♥, A♥, 5♠. The bot only
+//! checks or calls, so the hand goes the way the coaching describes unless
+//! the user folds.
+
+use poker_engine::{evaluate_hand, Action, Card, GamePhase, PokerGame};
+
+/// What the coach says at this point of the hand, and which action button
+/// it points at ("" for none).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coaching {
+    pub text: String,
+    pub highlight: &'static str,
+}
+
+/// Stacks the scripted cards into a hand that has just been dealt.
+pub fn stack(game: &mut PokerGame) {
+    game.set_hole_cards(0, vec![card("A", "♠", 14), card("K", "♠", 13)]);
+    game.set_hole_cards(1, vec![card("Q", "♥", 12), card("Q", "♦", 12)]);
+    game.stack_deck(&[
+        card("K", "♦", 13),
+        card("7", "♣", 7),
+        card("2", "♥", 2),
+        card("A", "♥", 14),
+        card("5", "♠", 5),
+    ]);
+}
+
+/// The bot's move in the tutorial: never a bet, so the user leads.
+pub fn bot_action(game: &PokerGame) -> Action {
+    let bot = &game.players[game.current_player];
+    if game.current_bet > bot.bet {
+        Action::Call
+    } else {
+        Action::Check
+    }
+}
+
+pub fn coaching(game: &PokerGame) -> Coaching {
+    let user = &game.players[0];
+    if game.hand_complete && user.cards.is_empty() {
+        return Coaching {
+            text: "You folded, so the bot takes the pot without showing its cards. \
+                   Press New Hand to try again, or open the menu for a real game."
+                .to_string(),
+            highlight: "",
+        };
+    }
+    if game.hand_complete || game.phase == GamePhase::Showdown {
+        return Coaching {
+            text: format!(
+                "Showdown! Both hands are turned up and the best five cards win. \
+                 Your {} beats the bot's {}. That's a whole hand — \
+                 open the menu and pick a game when you're ready.",
+                evaluate_hand(&user.cards, &game.community_cards).describe(),
+                evaluate_hand(&game.players[1].cards, &game.community_cards).describe()
+            ),
+            highlight: "",
+        };
+    }
+
+    let made = evaluate_hand(&user.cards, &game.community_cards).describe();
+    let (text, highlight) = match game.phase {
+        GamePhase::PreFlop => {
+            let (seat, next, highlight) = if game.small_blind_seat() == 0 {
+                (
+                    "You have the dealer button, so you post the small blind and act first",
+                    "Call to see the flop.",
+                    "call",
+                )
+            } else {
+                (
+                    "The bot has the dealer button, so you post the big blind and act last",
+                    "Once the bot calls, check to see the flop for free.",
+                    "check",
+                )
+            };
+            let text = format!(
+                "Every hand starts with the blinds, forced bets so there's something \
+                 to win: {} and {}. {}. You're dealt {} — a strong start. {}",
+                game.small_blind,
+                game.big_blind,
+                seat,
+                cards_text(&user.cards),
+                next
+            );
+            (text, highlight)
+        }
+        GamePhase::Flop => (
+            format!(
+                "The flop: three shared cards that both players use. \
+                 With them you have {}. Make the bot pay to see more — press Raise.",
+                made
+            ),
+            "raise",
+        ),
+        GamePhase::Turn => (
+            format!(
+                "The turn, a fourth shared card. Now you have {}. \
+                 Raise again to build the pot.",
+                made
+            ),
+            "raise",
+        ),
+        GamePhase::River | GamePhase::Showdown => (
+            format!(
+                "The river, the fifth and last card. You have {}. \
+                 Check, and the hands are compared at the showdown.",
+                made
+            ),
+            "check",
+        ),
+    };
+    Coaching { text, highlight }
+}
+
+fn card(rank: &str, suit: &str, value: i32) -> Card {
+    Card::new(rank, suit, value)
+}
+
+fn cards_text(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(Card::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted_hand_follows_the_coaching() {
+        let mut game = PokerGame::new();
+        game.start_hand();
+        stack(&mut game);
+        assert!(coaching(&game).text.contains("A♠ K♠"));
+
+        for _ in 0..40 {
+            if game.hand_complete {
+                break;
+            }
+            let action = if game.is_user_turn() {
+                match coaching(&game).highlight {
+                    "raise" => Action::Raise(game.current_bet + poker_engine::MIN_RAISE),
+                    "call" => Action::Call,
+                    _ => Action::Check,
+                }
+            } else {
+                bot_action(&game)
+            };
+            game.apply(action).unwrap();
+        }
+
+        assert!(game.hand_complete);
+        let last = coaching(&game);
+        assert!(last.text.contains("Two Pair, Aces and Kings"));
+        assert!(last.text.contains("Pair of Queens"));
+    }
+}
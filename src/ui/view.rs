@@ -0,0 +1,565 @@
+use super::action_log::action_log;
+use super::commentary::commentary;
+use super::emotes::Emotes;
+use super::equity_meter::EquityMeter;
+use super::models::{chip_piles, create_card_ui_data, hidden_card_ui_data, showdown_card_ui_data};
+use super::pots::{awarded_pots, pot_breakdown, side_pots};
+use super::recap::recap;
+use super::toasts::Toasts;
+use super::tutorial;
+use super::{CardUI, ChipPileUI, HudStats, MainWindow, Motion, PotUI};
+use crate::game_mode::GameMode;
+use crate::notes::Notes;
+use poker_engine::{
+    best_five, draws, evaluate_hand, Action, BotStyle, Card, Chips, DifficultyController,
+    GameEvent, GamePhase, HandRank, History, Player, PlayerStats, PokerGame, MIN_RAISE,
+};
+use slint::{ComponentHandle, ModelRc, SharedString, Timer, TimerMode, VecModel};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::trace;
+
+const DEBUG_EVENT_COUNT: usize = 20;
+// Long enough for the chip to be drawn at its start before it takes off.
+const CHIP_FLIGHT_START_MS: u64 = 20;
+// The beat between the last card of a showdown and the winner banner.
+const WINNER_DELAY_MS: u64 = 900;
+// How long the winner banner stays up unless it is clicked away.
+const WINNER_SHOW_MS: u64 = 5000;
+// An action's flourish at normal speed; matches the 600ms in ui.slint.
+const ACTION_ANIMATION_MS: u64 = 600;
+
+/// Renders a [`PokerGame`] into the main window. It is registered as an
+/// engine observer, so it is handed the game instead of borrowing it.
+#[derive(Clone)]
+pub struct TableView {
+    pub main_window: slint::Weak<MainWindow>,
+    pub difficulty: Rc<RefCell<DifficultyController>>,
+    pub turn_timer: Rc<Timer>,
+    pub chip_timer: Rc<Timer>,
+    pub action_timer: Rc<Timer>,
+    pub winner_timer: Rc<Timer>,
+    pub history: Rc<RefCell<History>>,
+    pub equity: EquityMeter,
+    pub toasts: Toasts,
+    pub emotes: Emotes,
+    pub notes: Rc<RefCell<Notes>>,
+    // Set once the bot's cards are turned up ahead of a showdown.
+    pub bot_revealed: Rc<Cell<bool>>,
+    pub mode: Rc<Cell<GameMode>>,
+    // The opponent's stats over every hand in the hand database, if any.
+    pub hud: Rc<RefCell<Option<PlayerStats>>>,
+}
+
+impl TableView {
+    pub fn render(&self, game: &PokerGame) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+        self.refresh_cards(&window, game);
+        self.refresh_chips(&window, game);
+        self.refresh_decision_log(&window, game);
+        self.refresh_action_log(&window, game);
+        self.refresh_controls(&window, game);
+        self.refresh_debug(&window, game);
+    }
+
+    /// Refreshes only the parts of the window the events touched; turn
+    /// controls are always refreshed.
+    pub fn render_events(&self, game: &PokerGame, events: &[GameEvent]) {
+        let Some(window) = self.main_window.upgrade() else {
+            return;
+        };
+
+        let (mut cards, mut chips, mut decisions) = (false, false, false);
+        let mut flight = None;
+        let mut flourish = None;
+        for event in events {
+            match event {
+                GameEvent::HandStarted { .. } => {
+                    self.hide_winner(&window);
+                    window.set_counting_up(false);
+                    window.set_hand_bookmarked(false);
+                    cards = true;
+                    chips = true;
+                }
+                GameEvent::PotAwarded { seat, amount, .. } => {
+                    window.set_counting_up(true);
+                    cards = true;
+                    chips = true;
+                    flight = Some(("pot", seat_end(game, *seat), *amount));
+                }
+                GameEvent::StateRestored => {
+                    self.hide_winner(&window);
+                    window.set_counting_up(false);
+                    window.set_pots(pots_model(Vec::new()));
+                    cards = true;
+                    chips = true;
+                    decisions = true;
+                }
+                GameEvent::CardsDealt { .. } => cards = true,
+                GameEvent::BlindPosted { seat, amount } => {
+                    chips = true;
+                    flight = Some((seat_end(game, *seat), "pot", *amount));
+                }
+                GameEvent::PlayerActed {
+                    seat,
+                    action,
+                    amount,
+                } => {
+                    trace!(seat, action = action.name(), %amount, "rendering action");
+                    chips = true;
+                    decisions |= !game.players[*seat].is_user;
+                    let kind = match action {
+                        Action::Fold => Some("fold"),
+                        Action::Check => Some("check"),
+                        Action::Bet(_) | Action::Raise(_) | Action::AllIn => Some("raise"),
+                        Action::Call => None,
+                    };
+                    if let Some(kind) = kind {
+                        flourish = Some((seat_end(game, *seat), kind, game.players[*seat].bet));
+                    }
+                    if !amount.is_zero() {
+                        flight = Some((seat_end(game, *seat), "pot", *amount));
+                    }
+                }
+                GameEvent::PhaseAdvanced { .. } => {
+                    cards = true;
+                    chips = true;
+                }
+            }
+        }
+
+        if cards {
+            self.refresh_cards(&window, game);
+        }
+        if chips {
+            self.refresh_chips(&window, game);
+        }
+        if decisions {
+            self.refresh_decision_log(&window, game);
+        }
+        let awarded = awarded_pots(game, events);
+        if !awarded.is_empty() {
+            window.set_pots(pots_model(awarded));
+        }
+        if let Some((from, to, amount)) = flight {
+            self.start_chip_flight(&window, from, to, amount);
+        }
+        if let Some((seat, kind, amount)) = flourish {
+            self.start_action_animation(&window, seat, kind, amount);
+        }
+        self.refresh_action_log(&window, game);
+        if let Some(line) = commentary(game, events) {
+            window.set_commentary(line.into());
+        }
+        self.toasts.notice(&self.main_window, game, events);
+        self.emotes.notice(&self.main_window, game, events);
+        self.refresh_controls(&window, game);
+        self.refresh_debug(&window, game);
+    }
+
+    /// Shows the last chip movement of a batch as a token flying from one
+    /// end to the other.
+    fn start_chip_flight(&self, window: &MainWindow, from: &str, to: &str, amount: Chips) {
+        window.set_chip_flight_from(from.into());
+        window.set_chip_flight_to(to.into());
+        window.set_chip_flight_amount(amount.as_i32());
+        window.set_chip_flight_landed(false);
+        let weak_window = self.main_window.clone();
+        self.chip_timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(CHIP_FLIGHT_START_MS),
+            move || {
+                if let Some(window) = weak_window.upgrade() {
+                    window.set_chip_flight_landed(true);
+                }
+            },
+        );
+    }
+
+    /// Plays an action's flourish at `seat`, unless animations are off.
+    fn start_action_animation(&self, window: &MainWindow, seat: &str, kind: &str, amount: Chips) {
+        let scale = window.global::<Motion>().get_scale();
+        if scale <= 0.0 {
+            return;
+        }
+        window.set_action_anim(kind.into());
+        window.set_action_anim_seat(seat.into());
+        window.set_action_anim_amount(amount.as_i32());
+        window.set_action_anim_serial(window.get_action_anim_serial() + 1);
+        let weak_window = self.main_window.clone();
+        self.action_timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis((ACTION_ANIMATION_MS as f32 * scale) as u64),
+            move || {
+                if let Some(window) = weak_window.upgrade() {
+                    window.set_action_anim("".into());
+                }
+            },
+        );
+    }
+
+    pub fn render_controls(&self, game: &PokerGame) {
+        if let Some(window) = self.main_window.upgrade() {
+            self.refresh_controls(&window, game);
+        }
+    }
+
+    /// Turns the bot's cards face up, ahead of a showdown.
+    pub fn reveal_bot_cards(&self, game: &PokerGame) {
+        self.bot_revealed.set(true);
+        if let Some(window) = self.main_window.upgrade() {
+            self.refresh_cards(&window, game);
+        }
+    }
+
+    pub fn bot_revealed(&self) -> bool {
+        self.bot_revealed.get()
+    }
+
+    /// Announces the result a beat after the last card lands.
+    pub fn show_winner(&self, game: &PokerGame) {
+        let net = game.players[0].chips.as_i32() - game.hand_start_chips[0].as_i32();
+        let winner = match net.cmp(&0) {
+            Ordering::Greater if game.players[0].is_user => "YOU WIN!".to_string(),
+            Ordering::Greater => format!("{} WINS!", game.players[0].name.to_uppercase()),
+            Ordering::Less => format!("{} WINS!", game.players[1].name.to_uppercase()),
+            Ordering::Equal => "TIE GAME!".to_string(),
+        };
+        let hand = showdown_result(game)
+            .map(|(_, name)| format!(" {}", name))
+            .unwrap_or_default();
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_last_hand(recap(game));
+            window.set_has_last_hand(true);
+        }
+
+        let breakdown = pot_breakdown(game, game.events());
+        let announcement = format!("{}{}. {}", winner, hand, breakdown.join(". "));
+
+        let weak_window = self.main_window.clone();
+        let timer = self.winner_timer.clone();
+        self.winner_timer.start(
+            TimerMode::SingleShot,
+            Duration::from_millis(WINNER_DELAY_MS),
+            move || {
+                let Some(window) = weak_window.upgrade() else {
+                    return;
+                };
+                window.set_announcement(announcement.as_str().into());
+                window.set_winner_name(winner.as_str().into());
+                let breakdown: Vec<SharedString> =
+                    breakdown.iter().map(|line| line.into()).collect();
+                window.set_winner_pots(Rc::new(VecModel::from(breakdown)).into());
+                window.set_show_winner(true);
+
+                let weak_window = weak_window.clone();
+                timer.start(
+                    TimerMode::SingleShot,
+                    Duration::from_millis(WINNER_SHOW_MS),
+                    move || {
+                        if let Some(window) = weak_window.upgrade() {
+                            window.set_show_winner(false);
+                        }
+                    },
+                );
+            },
+        );
+    }
+
+    fn hide_winner(&self, window: &MainWindow) {
+        self.winner_timer.stop();
+        self.bot_revealed.set(false);
+        window.set_show_winner(false);
+    }
+
+    pub fn set_bot_thinking(&self, thinking: bool) {
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_bot_thinking(thinking);
+        }
+    }
+
+    pub fn set_error_message(&self, message: String) {
+        if let Some(window) = self.main_window.upgrade() {
+            window.set_error_message(message.into());
+        }
+    }
+
+    fn refresh_cards(&self, window: &MainWindow, game: &PokerGame) {
+        let showdown = showdown_result(game);
+        let card_ui = |card: &Card| match &showdown {
+            Some((winning_cards, _)) => showdown_card_ui_data(card, winning_cards),
+            None => create_card_ui_data(card),
+        };
+
+        let player_cards: Vec<CardUI> = game.players[0].cards.iter().map(card_ui).collect();
+        window.set_player_cards(Rc::new(VecModel::from(player_cards)).into());
+
+        // The bot's cards are turned up when shown down, and also once the
+        // hand is over if the user wants to see what it folded or mucked.
+        let bot = &game.players[1];
+        let contested = game.players.iter().all(|player| !player.cards.is_empty());
+        let shown = self.bot_revealed.get()
+            || self.mode.get().shows_bot_cards()
+            || (game.phase == GamePhase::Showdown || game.hand_complete) && contested;
+        let mucked = game.hand_complete && window.get_show_mucked_cards();
+        let bot_cards: Vec<CardUI> = if !(shown || mucked) {
+            vec![hidden_card_ui_data(); 2]
+        } else if bot.cards.is_empty() {
+            bot.mucked_cards
+                .iter()
+                .map(|card| CardUI {
+                    dimmed: true,
+                    ..create_card_ui_data(card)
+                })
+                .collect()
+        } else {
+            bot.cards.iter().map(card_ui).collect()
+        };
+        window.set_bot_cards(Rc::new(VecModel::from(bot_cards)).into());
+
+        let community_cards: Vec<CardUI> = game.community_cards.iter().map(card_ui).collect();
+        window.set_community_cards(Rc::new(VecModel::from(community_cards)).into());
+
+        let winning_hand = showdown.map(|(_, name)| name).unwrap_or_default();
+        window.set_winning_hand(winning_hand.into());
+        window.set_current_hand(current_hand(game).into());
+        self.equity.update(&self.main_window, game);
+    }
+
+    fn refresh_chips(&self, window: &MainWindow, game: &PokerGame) {
+        window.set_pot(game.pot.as_i32());
+        window.set_pot_chip_piles(piles_model(game.pot));
+        if !game.hand_complete {
+            window.set_pots(pots_model(side_pots(game)));
+        }
+        window.set_blinds(format!("{}/{}", game.small_blind, game.big_blind).into());
+        window.set_current_bet(game.current_bet.as_i32());
+        window.set_dealer_seat(game.dealer_position as i32);
+        window.set_small_blind_seat(game.small_blind_seat() as i32);
+        window.set_big_blind_seat(game.big_blind_seat() as i32);
+
+        window.set_player_chips(game.players[0].chips.as_i32());
+        window.set_player_chip_piles(piles_model(game.players[0].chips));
+        window.set_player_bet(game.players[0].bet.as_i32());
+
+        window.set_bot_chips(game.players[1].chips.as_i32());
+        window.set_bot_chip_piles(piles_model(game.players[1].chips));
+        window.set_bot_bet(game.players[1].bet.as_i32());
+
+        let stack_bb = |player: &Player| {
+            ((player.chips + player.bet).amount() / game.big_blind.amount()) as i32
+        };
+        window.set_player_stack_bb(stack_bb(&game.players[0]));
+        window.set_bot_stack_bb(stack_bb(&game.players[1]));
+    }
+
+    fn refresh_decision_log(&self, window: &MainWindow, game: &PokerGame) {
+        let decision_log: Vec<slint::SharedString> = game
+            .decision_log
+            .iter()
+            .rev()
+            .map(|d| d.summary().into())
+            .collect();
+        window.set_decision_log(Rc::new(VecModel::from(decision_log)).into());
+    }
+
+    fn refresh_action_log(&self, window: &MainWindow, game: &PokerGame) {
+        let lines: Vec<SharedString> = action_log(game).into_iter().map(Into::into).collect();
+        if let Some(latest) = lines.last() {
+            window.set_announcement(latest.clone());
+        }
+        window.set_action_log(Rc::new(VecModel::from(lines)).into());
+    }
+
+    /// Fills the debug panel; skipped while the panel is hidden.
+    fn refresh_debug(&self, window: &MainWindow, game: &PokerGame) {
+        // The replayer shows the history position too.
+        let history = self.history.borrow();
+        window.set_debug_history_position(history.position() as i32);
+        window.set_debug_history_len(history.len() as i32);
+        if !window.get_show_debug_panel() {
+            return;
+        }
+        window.set_debug_state(format!("{:#?}", game.snapshot()).into());
+        window.set_debug_deck_remaining(game.cards_remaining() as i32);
+        let events: Vec<SharedString> = game
+            .events()
+            .iter()
+            .rev()
+            .take(DEBUG_EVENT_COUNT)
+            .map(|event| format!("{:?}", event).into())
+            .collect();
+        window.set_debug_events(Rc::new(VecModel::from(events)).into());
+    }
+
+    fn refresh_controls(&self, window: &MainWindow, game: &PokerGame) {
+        window.set_phase_name(game.get_phase_name().into());
+        window.set_current_player_name(game.players[game.current_player].name.clone().into());
+        window.set_hand_complete(game.hand_complete);
+
+        let is_user_turn = game.is_user_turn();
+        let call_amount = game.current_bet.saturating_sub(game.players[0].bet);
+        let can_check = call_amount.is_zero();
+        let can_call = game.players[0].chips >= call_amount;
+        let min_raise = game.current_bet + MIN_RAISE;
+
+        window.set_show_actions(is_user_turn && !self.turn_timer.running());
+        window.set_can_check(can_check);
+        window.set_can_call(can_call);
+        window.set_can_fold(true);
+        window.set_can_raise(game.players[0].chips >= min_raise);
+        window.set_push_fold_mode(game.is_push_fold());
+        window.set_min_raise_amount(min_raise.as_i32());
+        let pot_odds = game
+            .pot_odds(0)
+            .filter(|_| is_user_turn)
+            .map(|odds| {
+                format!(
+                    "{} to win {} — need {:.0}%",
+                    odds.call,
+                    odds.pot,
+                    odds.required_equity() * 100.0
+                )
+            })
+            .unwrap_or_default();
+        window.set_pot_odds(pot_odds.into());
+
+        window.set_game_over(match_over(game));
+        window.set_error_message("".into());
+
+        let difficulty = self.difficulty.borrow();
+        window.set_adaptive_difficulty(difficulty.enabled);
+        window.set_bot_level(difficulty.level);
+
+        window.set_exploitative_bot(game.bot_style == BotStyle::Exploitative);
+        window.set_bot_personality(game.bot_personality.name().into());
+        let exploit_report = match (&game.last_exploit, game.hand_complete) {
+            (Some(description), true) => description.clone(),
+            _ => String::new(),
+        };
+        window.set_bot_exploit(exploit_report.into());
+        match &*self.hud.borrow() {
+            Some(lifetime) => window.set_bot_hud(hud_stats(lifetime)),
+            None => window.set_bot_hud(hud_stats(&game.bot_stats)),
+        }
+        let note = self.notes.borrow().get(game.bot_personality.name());
+        window.set_bot_note(note.text.into());
+        window.set_bot_note_color(note.color.into());
+
+        let (coaching, highlight) = if self.mode.get() == GameMode::Tutorial {
+            let coaching = tutorial::coaching(game);
+            (coaching.text, coaching.highlight)
+        } else {
+            (String::new(), "")
+        };
+        window.set_tutorial_text(coaching.into());
+        window.set_tutorial_highlight(highlight.into());
+    }
+}
+
+/// The opponent HUD figures; a stat reads "—" until there are enough
+/// samples for it to mean anything.
+fn hud_stats(stats: &PlayerStats) -> HudStats {
+    let stat = |value: Option<u32>| value.map_or("—".into(), |value| value.to_string().into());
+    HudStats {
+        vpip: stat(stats.vpip()),
+        pfr: stat(stats.pfr()),
+        af: stat(stats.aggression_factor()),
+        hands: stats.hands as i32,
+    }
+}
+
+fn piles_model(amount: Chips) -> ModelRc<ChipPileUI> {
+    Rc::new(VecModel::from(chip_piles(amount))).into()
+}
+
+fn pots_model(pots: Vec<PotUI>) -> ModelRc<PotUI> {
+    Rc::new(VecModel::from(pots)).into()
+}
+
+/// The end of a chip flight that stands for `seat`'s stack.
+fn seat_end(game: &PokerGame, seat: usize) -> &'static str {
+    if game.players[seat].is_user {
+        "player"
+    } else {
+        "bot"
+    }
+}
+
+/// What the user holds right now, e.g. "Pair of Tens" or "Flush draw +
+/// gutshot"; empty once they have folded or the hand is over.
+fn current_hand(game: &PokerGame) -> String {
+    let hole = &game.players[0].cards;
+    if hole.is_empty() || game.hand_complete {
+        return String::new();
+    }
+    let made = evaluate_hand(hole, &game.community_cards);
+    let draws = draws(hole, &game.community_cards);
+    let mut parts: Vec<String> = draws.iter().map(|draw| draw.name().to_string()).collect();
+    if made.rank != HandRank::HighCard || parts.is_empty() {
+        parts.insert(0, made.describe());
+    }
+    let label = parts.join(" + ");
+    let mut chars = label.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Whether the match is over, which locks the action buttons. A stack at
+/// zero mid-hand is only an all-in.
+fn match_over(game: &PokerGame) -> bool {
+    game.hand_complete && game.is_game_over()
+}
+
+/// After a hand that went to showdown, the cards making up the winning hand
+/// (both hands on a split pot) and its name.
+fn showdown_result(game: &PokerGame) -> Option<(Vec<Card>, String)> {
+    let winners = game.showdown_winners();
+    let first = game.players.get(*winners.first()?)?;
+    let name = evaluate_hand(&first.cards, &game.community_cards).describe();
+    let winning_cards = winners
+        .iter()
+        .flat_map(|&seat| best_five(&game.players[seat].cards, &game.community_cards))
+        .collect();
+    Some((winning_cards, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buttons_stay_enabled_while_the_bot_is_all_in() {
+        let mut game = PokerGame::new();
+        game.start_hand_seeded(5);
+        game.set_hole_cards(0, vec![Card::new("A", "♠", 14), Card::new("A", "♥", 14)]);
+        game.set_hole_cards(1, vec![Card::new("7", "♦", 7), Card::new("2", "♣", 2)]);
+        game.stack_deck(&[
+            Card::new("K", "♣", 13),
+            Card::new("9", "♦", 9),
+            Card::new("4", "♥", 4),
+            Card::new("J", "♠", 11),
+            Card::new("3", "♦", 3),
+        ]);
+        if game.current_player == 0 {
+            game.seat_action(0, Action::Call).unwrap();
+        }
+        game.seat_action(1, Action::AllIn).unwrap();
+        assert!(game.is_game_over());
+        assert!(!match_over(&game));
+
+        // Once the user calls and the hand plays out, the bot is bust.
+        game.seat_action(0, Action::Call).unwrap();
+        while !game.hand_complete {
+            game.next_phase();
+        }
+        assert!(game.players[1].chips.is_zero());
+        assert!(match_over(&game));
+    }
+}
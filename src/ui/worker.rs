@@ -0,0 +1,13 @@
+//! Work kept off the UI thread. A browser has no threads, so there the job
+//! runs straight away; either way it hands its result back through
+//! `slint::invoke_from_event_loop`.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn(job: impl FnOnce() + Send + 'static) {
+    std::thread::spawn(job);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn spawn(job: impl FnOnce() + Send + 'static) {
+    job();
+}